@@ -88,6 +88,8 @@ struct ComponentsForJs {
     default: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     route: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "global-error")]
+    global_error: Option<String>,
     metadata: MetadataForJs,
 }
 
@@ -131,6 +133,7 @@ async fn prepare_components_for_js(
         not_found,
         default,
         route,
+        global_error,
         metadata,
     } = &*components.await?;
     let mut result = ComponentsForJs::default();
@@ -152,6 +155,7 @@ async fn prepare_components_for_js(
     add(&mut result.not_found, project_path, not_found).await?;
     add(&mut result.default, project_path, default).await?;
     add(&mut result.route, project_path, route).await?;
+    add(&mut result.global_error, project_path, global_error).await?;
     async fn add_meta<'a>(
         meta: &mut Vec<MetadataForJsItem>,
         project_path: FileSystemPathVc,
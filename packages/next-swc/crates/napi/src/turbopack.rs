@@ -53,6 +53,7 @@ impl TryFrom<NextBuildContext> for NextBuildOptions {
             show_all: true,
             log_detail: true,
             full_stats: true,
+            bundle_stats: false,
             memory_limit: None,
             build_context: Some(BuildContext {
                 build_id: value
@@ -25,6 +25,10 @@ pub struct BuildOptions {
     /// Whether to compute full stats.
     pub full_stats: bool,
 
+    /// Whether to write a `stats.json` describing the client chunks, for
+    /// bundle analysis tools.
+    pub bundle_stats: bool,
+
     /// The Next.js build context.
     pub build_context: Option<BuildContext>,
 }
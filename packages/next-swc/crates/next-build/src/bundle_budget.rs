@@ -0,0 +1,138 @@
+//! Bundle size budgets, configured by `experimental.bundleBudget`. The size
+//! of a route is the size of the client chunks it loads initially, and every
+//! route over its budget is reported as an issue.
+
+use anyhow::Result;
+use next_core::next_config::{BundleBudgetConfig, BundleBudgetSeverity, NextConfigVc};
+use turbo_tasks::primitives::{BoolVc, StringVc};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileContent, FileSystemPathVc},
+    turbopack::core::{
+        asset::Asset,
+        issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+    },
+};
+
+use crate::next_pages::page_chunks::PageChunksVc;
+
+/// Emits a [BundleBudgetIssue] for every page whose client chunks exceed its
+/// budget. Returns whether the build should fail.
+#[turbo_tasks::function]
+pub(crate) async fn check_bundle_budgets(
+    page_chunks: PageChunksVc,
+    next_config: NextConfigVc,
+) -> Result<BoolVc> {
+    let bundle_budget = next_config.bundle_budget().await?;
+    let Some(config) = bundle_budget.as_ref() else {
+        return Ok(BoolVc::cell(false));
+    };
+    let is_error = config.severity == BundleBudgetSeverity::Error;
+
+    let mut has_errors = false;
+    for page_chunk in page_chunks.await?.iter() {
+        let page_chunk = page_chunk.await?;
+        let pathname = page_chunk.pathname.await?;
+
+        let mut size = 0;
+        for chunk in page_chunk.client_chunks.await?.iter() {
+            if let FileContent::Content(file) = &*chunk.content().file_content().await? {
+                size += file.content().to_bytes()?.len() as u64;
+            }
+        }
+
+        if let Some(budget) = exceeded_budget(config, &pathname, size) {
+            has_errors |= is_error;
+            BundleBudgetIssue {
+                path: page_chunk.source_path,
+                pathname: pathname.clone_value(),
+                size,
+                budget,
+                is_error,
+            }
+            .cell()
+            .as_issue()
+            .emit();
+        }
+    }
+
+    Ok(BoolVc::cell(has_errors))
+}
+
+/// Returns the budget of the route at `pathname` when its `size` exceeds it.
+fn exceeded_budget(config: &BundleBudgetConfig, pathname: &str, size: u64) -> Option<u64> {
+    config.budget_for(pathname).filter(|budget| size > *budget)
+}
+
+#[turbo_tasks::value(shared)]
+struct BundleBudgetIssue {
+    path: FileSystemPathVc,
+    pathname: String,
+    size: u64,
+    budget: u64,
+    is_error: bool,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for BundleBudgetIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        if self.is_error {
+            IssueSeverity::Error.into()
+        } else {
+            IssueSeverity::Warning.into()
+        }
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(format!(
+            "The route \"{}\" exceeds its bundle budget",
+            self.pathname
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("bundle budget".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(format!(
+            "The client chunks of \"{}\" are {} bytes, {} bytes over its budget of {} bytes. \
+             Budgets are configured by `experimental.bundleBudget`.",
+            self.pathname,
+            self.size,
+            self.size - self.budget,
+            self.budget
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use next_core::next_config::{BundleBudgetConfig, BundleBudgetSeverity};
+
+    use super::exceeded_budget;
+
+    #[test]
+    fn test_exceeded_budget() {
+        let config = BundleBudgetConfig {
+            max_size: Some(100_000),
+            routes: [("/dashboard".to_string(), 300_000)].into_iter().collect(),
+            severity: BundleBudgetSeverity::Warning,
+        };
+        assert_eq!(exceeded_budget(&config, "/", 150_000), Some(100_000));
+        assert_eq!(exceeded_budget(&config, "/", 100_000), None);
+        assert_eq!(exceeded_budget(&config, "/dashboard", 150_000), None);
+        assert_eq!(
+            exceeded_budget(&config, "/dashboard", 300_001),
+            Some(300_000)
+        );
+    }
+}
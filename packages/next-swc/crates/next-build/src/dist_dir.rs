@@ -0,0 +1,78 @@
+//! Cleanup of the output of previous builds, like Next.js does for
+//! `cleanDistDir`.
+
+use std::{io::ErrorKind, path::Path};
+
+use anyhow::{Context, Result};
+
+/// The entries of the dist directory that are kept. `cache` holds the cache
+/// of previous builds. `chunks` and `assets` hold the code evaluated while
+/// building, like `next.config.js`, which may already have been written by
+/// the current build when the dist directory is cleaned.
+const KEPT_ENTRIES: &[&str] = &["cache", "chunks", "assets"];
+
+/// Removes everything in `dist_dir` except for [KEPT_ENTRIES], so that removed
+/// pages don't leave orphaned chunks behind.
+pub(crate) async fn clean_dist_dir(dist_dir: &Path) -> Result<()> {
+    let mut entries = match tokio::fs::read_dir(dist_dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", dist_dir.display()))
+        }
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let file_name = entry.file_name();
+        if file_name
+            .to_str()
+            .map_or(false, |name| KEPT_ENTRIES.contains(&name))
+        {
+            continue;
+        }
+        let path = entry.path();
+        let result = if entry.file_type().await?.is_dir() {
+            tokio::fs::remove_dir_all(&path).await
+        } else {
+            tokio::fs::remove_file(&path).await
+        };
+        result.with_context(|| format!("failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::clean_dist_dir;
+
+    #[tokio::test]
+    async fn test_clean_dist_dir() {
+        let dist_dir = tempfile::tempdir().unwrap();
+        let dist_dir = dist_dir.path();
+        for file in [
+            "build-manifest.json",
+            "server/pages/removed.js",
+            "static/chunks/removed.js",
+            "cache/turbopack/cache.bin",
+            "chunks/next.config.js",
+        ] {
+            let path = dist_dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        clean_dist_dir(dist_dir).await.unwrap();
+
+        let mut entries: Vec<_> = fs::read_dir(dist_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, ["cache", "chunks"]);
+        assert!(dist_dir.join("cache/turbopack/cache.bin").exists());
+
+        // A missing dist directory is already clean.
+        clean_dist_dir(&dist_dir.join("missing")).await.unwrap();
+    }
+}
@@ -4,9 +4,15 @@ use turbopack_binding::turbo::{
 };
 
 pub mod build_options;
+pub(crate) mod bundle_budget;
+pub(crate) mod dist_dir;
+pub(crate) mod lint;
 pub mod manifests;
 pub(crate) mod next_build;
 pub(crate) mod next_pages;
+pub mod stats;
+pub(crate) mod type_check;
+pub(crate) mod typed_routes;
 
 use anyhow::Result;
 use turbo_tasks::{StatsType, TurboTasksBackendApi};
@@ -50,7 +56,8 @@ fn setup_tracing() {
 
     let subscriber = subscriber.with(EnvFilter::from_default_env());
 
-    subscriber.init();
+    // Tests build several projects in the same process.
+    let _ = subscriber.try_init();
 }
 
 pub fn register() {
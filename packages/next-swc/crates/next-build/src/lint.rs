@@ -0,0 +1,281 @@
+//! Linting for builds that aren't driven by Next.js, which lints the project
+//! itself. The project's own ESLint is run over the linted directories and
+//! the problems it reports are emitted as issues. Errors fail the build.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use next_core::next_config::NextConfigVc;
+use serde::Deserialize;
+use tokio::process::Command;
+use turbo_tasks::primitives::{BoolVc, StringVc};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileContent, FileSystemPathVc},
+    turbopack::core::{
+        file_source::FileSourceVc,
+        issue::{
+            Issue, IssueSeverity, IssueSeverityVc, IssueSourceVc, IssueVc, OptionIssueSourceVc,
+        },
+    },
+};
+
+use crate::type_check::byte_offset;
+
+/// See https://eslint.org/docs/latest/use/configure/configuration-files
+const ESLINT_CONFIG_FILES: &[&str] = &[
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.yaml",
+    ".eslintrc.yml",
+    ".eslintrc.json",
+    ".eslintrc",
+];
+
+/// Runs ESLint over the project in `project_dir`, the directory of
+/// `project_path` on disk, and emits a [LintIssue] for every problem.
+/// Returns whether the build should fail. Projects without an ESLint
+/// configuration or without ESLint installed are not linted, and neither are
+/// projects that set `eslint.ignoreDuringBuilds`.
+#[turbo_tasks::function]
+pub(crate) async fn lint(
+    project_path: FileSystemPathVc,
+    project_dir: &str,
+    next_config: NextConfigVc,
+) -> Result<BoolVc> {
+    if *next_config.eslint_ignore_during_builds().await? {
+        return Ok(BoolVc::cell(false));
+    }
+
+    let project_dir = Path::new(project_dir);
+    if !has_eslint_config(project_dir).await? {
+        return Ok(BoolVc::cell(false));
+    }
+    let Some(eslint) = project_dir
+        .ancestors()
+        .map(|dir| dir.join("node_modules/eslint/bin/eslint.js"))
+        .find(|eslint| eslint.exists())
+    else {
+        return Ok(BoolVc::cell(false));
+    };
+
+    let dirs = next_config.eslint_dirs().await?;
+    let dirs: Vec<_> = dirs
+        .iter()
+        .filter(|dir| project_dir.join(dir).exists())
+        .collect();
+    if dirs.is_empty() {
+        return Ok(BoolVc::cell(false));
+    }
+
+    let output = Command::new("node")
+        .arg(&eslint)
+        .args(["--format", "json"])
+        .args(dirs)
+        .current_dir(project_dir)
+        .output()
+        .await
+        .context("failed to run ESLint")?;
+
+    // ESLint exits with 2 when it couldn't lint at all, e.g. because of an
+    // invalid configuration.
+    if output.status.code() == Some(2) {
+        LintIssue {
+            path: project_path,
+            is_error: true,
+            title: "ESLint failed to run".to_string(),
+            description: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            source: None,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+        return Ok(BoolVc::cell(true));
+    }
+
+    let results: Vec<LintResult> =
+        serde_json::from_slice(&output.stdout).context("failed to parse ESLint output")?;
+    let mut has_errors = false;
+    for result in results {
+        let Ok(file) = Path::new(&result.file_path).strip_prefix(project_dir) else {
+            continue;
+        };
+        let Some(file) = file.to_str() else {
+            continue;
+        };
+        let path = project_path.join(&file.replace(std::path::MAIN_SEPARATOR, "/"));
+        let file = path.read().await?;
+        let content = match &*file {
+            FileContent::Content(content) => Some(content.content().to_str()?),
+            FileContent::NotFound => None,
+        };
+        for message in result.messages {
+            let is_error = message.severity == 2;
+            has_errors |= is_error;
+            let source = content.as_ref().and_then(|content| {
+                let start = byte_offset(content, message.line, message.column)?;
+                let end = match (message.end_line, message.end_column) {
+                    (Some(line), Some(column)) => byte_offset(content, line, column)?,
+                    _ => start,
+                };
+                Some(IssueSourceVc::from_byte_offset(
+                    FileSourceVc::new(path).into(),
+                    start,
+                    end,
+                ))
+            });
+            LintIssue {
+                path,
+                is_error,
+                title: match message.rule_id {
+                    Some(rule_id) => format!("ESLint rule {rule_id}"),
+                    None => "ESLint".to_string(),
+                },
+                description: message.message,
+                source,
+            }
+            .cell()
+            .as_issue()
+            .emit();
+        }
+    }
+
+    Ok(BoolVc::cell(has_errors))
+}
+
+/// Whether the project is configured for ESLint, either with a configuration
+/// file in one of its parent directories or in its package.json.
+async fn has_eslint_config(project_dir: &Path) -> Result<bool> {
+    if project_dir.ancestors().any(|dir| {
+        ESLINT_CONFIG_FILES
+            .iter()
+            .any(|file| dir.join(file).exists())
+    }) {
+        return Ok(true);
+    }
+    let Ok(package_json) = tokio::fs::read(project_dir.join("package.json")).await else {
+        return Ok(false);
+    };
+    let package_json: serde_json::Value = serde_json::from_slice(&package_json)?;
+    Ok(package_json["eslintConfig"]
+        .as_object()
+        .map_or(false, |config| !config.is_empty()))
+}
+
+/// A file in the output of `eslint --format json`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LintResult {
+    file_path: String,
+    messages: Vec<LintMessage>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LintMessage {
+    /// `None` for parsing errors.
+    rule_id: Option<String>,
+    /// 1 for warnings, 2 for errors.
+    severity: u8,
+    message: String,
+    line: usize,
+    column: usize,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+}
+
+#[turbo_tasks::value(shared)]
+struct LintIssue {
+    path: FileSystemPathVc,
+    is_error: bool,
+    title: String,
+    description: String,
+    source: Option<IssueSourceVc>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for LintIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        if self.is_error {
+            IssueSeverity::Error.into()
+        } else {
+            IssueSeverity::Warning.into()
+        }
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(self.title.clone())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("linting".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(self.description.clone())
+    }
+
+    #[turbo_tasks::function]
+    fn source(&self) -> OptionIssueSourceVc {
+        OptionIssueSourceVc::cell(self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LintResult;
+
+    #[test]
+    fn test_parse_results() {
+        let output = r#"[
+            {
+                "filePath": "/project/pages/index.js",
+                "messages": [
+                    {
+                        "ruleId": "react/no-unescaped-entities",
+                        "severity": 2,
+                        "message": "`'` can be escaped with `&apos;`.",
+                        "line": 3,
+                        "column": 14,
+                        "endLine": 3,
+                        "endColumn": 15,
+                        "nodeType": "JSXText"
+                    },
+                    {
+                        "ruleId": null,
+                        "fatal": true,
+                        "severity": 2,
+                        "message": "Parsing error: Unexpected token",
+                        "line": 5,
+                        "column": 1
+                    }
+                ],
+                "errorCount": 2,
+                "warningCount": 0
+            }
+        ]"#;
+        let results: Vec<LintResult> = serde_json::from_str(output).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_path, "/project/pages/index.js");
+        let messages = &results[0].messages;
+        assert_eq!(
+            messages[0].rule_id.as_deref(),
+            Some("react/no-unescaped-entities")
+        );
+        assert_eq!(messages[0].severity, 2);
+        assert_eq!(
+            (messages[0].end_line, messages[0].end_column),
+            (Some(3), Some(15))
+        );
+        assert_eq!(messages[1].rule_id, None);
+        assert_eq!((messages[1].end_line, messages[1].end_column), (None, None));
+    }
+}
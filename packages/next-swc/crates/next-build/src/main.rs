@@ -43,6 +43,11 @@ pub struct BuildCliArgs {
     #[clap(long)]
     pub full_stats: bool,
 
+    /// Write a webpack-style stats.json of the client chunks to the dist
+    /// directory, for bundle analysis tools.
+    #[clap(long)]
+    pub bundle_stats: bool,
+
     /// Enable experimental garbage collection with the provided memory limit in
     /// MB.
     #[clap(long)]
@@ -96,6 +101,7 @@ async fn main_inner() -> Result<()> {
         show_all: args.show_all,
         log_detail: args.log_detail,
         full_stats: args.full_stats,
+        bundle_stats: args.bundle_stats,
         build_context: None,
     })
     .await
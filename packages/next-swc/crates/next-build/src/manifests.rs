@@ -2,7 +2,14 @@
 
 use std::collections::HashMap;
 
-use next_core::next_config::Rewrites;
+use anyhow::Result;
+use next_core::{
+    middleware::{
+        get_middleware_matcher_regexp, DEFAULT_MIDDLEWARE_MATCHER_REGEXP,
+        DEFAULT_MIDDLEWARE_MATCHER_SOURCE,
+    },
+    next_config::Rewrites,
+};
 use serde::Serialize;
 
 #[derive(Serialize, Default, Debug)]
@@ -39,10 +46,75 @@ impl Default for MiddlewaresManifest {
 }
 
 #[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct MiddlewaresManifestV2 {
-    pub sorted_middleware: Vec<()>,
-    pub middleware: HashMap<String, ()>,
-    pub functions: HashMap<String, ()>,
+    pub sorted_middleware: Vec<String>,
+    pub middleware: HashMap<String, EdgeFunctionDefinition>,
+    pub functions: HashMap<String, EdgeFunctionDefinition>,
+}
+
+impl MiddlewaresManifestV2 {
+    /// Creates a manifest for a root middleware compiled to `files`, matching
+    /// the requests described by its `config.matcher` (or every request when
+    /// there is none).
+    pub fn from_middleware(files: Vec<String>, matchers: Option<&[String]>) -> Result<Self> {
+        let matchers = match matchers {
+            Some(matchers) => matchers
+                .iter()
+                .map(|source| {
+                    Ok(MiddlewareMatcher {
+                        regexp: get_middleware_matcher_regexp(source)?,
+                        original_source: source.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![MiddlewareMatcher {
+                regexp: DEFAULT_MIDDLEWARE_MATCHER_REGEXP.to_string(),
+                original_source: DEFAULT_MIDDLEWARE_MATCHER_SOURCE.to_string(),
+            }],
+        };
+
+        Ok(Self {
+            sorted_middleware: vec!["/".to_string()],
+            middleware: HashMap::from([(
+                "/".to_string(),
+                EdgeFunctionDefinition {
+                    files,
+                    name: "middleware".to_string(),
+                    page: "/".to_string(),
+                    matchers,
+                    ..Default::default()
+                },
+            )]),
+            functions: Default::default(),
+        })
+    }
+}
+
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeFunctionDefinition {
+    pub files: Vec<String>,
+    pub name: String,
+    pub page: String,
+    pub matchers: Vec<MiddlewareMatcher>,
+    pub wasm: Vec<AssetBinding>,
+    pub assets: Vec<AssetBinding>,
+    pub env: HashMap<String, String>,
+}
+
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MiddlewareMatcher {
+    pub regexp: String,
+    pub original_source: String,
+}
+
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBinding {
+    pub name: String,
+    pub file_path: String,
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -59,6 +131,10 @@ pub struct ReactLoadableManifestEntry {
     pub files: Vec<String>,
 }
 
+/// The font files each page preloads, relative to the `.next` directory.
+///
+/// Only self-hosted fonts are listed: `next/font/local` files are emitted as
+/// assets, while Google fonts keep pointing to the Google Fonts CDN.
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct NextFontManifest {
@@ -68,6 +144,19 @@ pub struct NextFontManifest {
     pub pages_using_size_adjust: bool,
 }
 
+impl NextFontManifest {
+    /// Whether an emitted asset is a font file that belongs in the manifest.
+    pub fn is_font_file(path: &str) -> bool {
+        let Some((_, extension)) = path.rsplit_once('.') else {
+            return false;
+        };
+        matches!(
+            extension.to_ascii_lowercase().as_str(),
+            "woff" | "woff2" | "eot" | "ttf" | "otf"
+        )
+    }
+}
+
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AppPathsManifest {
@@ -177,3 +266,150 @@ pub struct ClientBuildManifest<'a> {
     #[serde(flatten)]
     pub pages: HashMap<String, Vec<&'a str>>,
 }
+
+/// Lists the files a minimal server needs to serve the build, for standalone
+/// deployments.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredServerFilesManifest {
+    pub version: u32,
+    pub config: serde_json::Value,
+    pub app_dir: String,
+    pub relative_app_dir: String,
+    pub files: Vec<String>,
+    pub ignore: Vec<String>,
+}
+
+impl RequiredServerFilesManifest {
+    /// The files written by the build that the server reads, relative to the
+    /// dist directory.
+    const FILES: &'static [&'static str] = &[
+        "build-manifest.json",
+        "server/pages-manifest.json",
+        "server/middleware-manifest.json",
+        "server/app-paths-manifest.json",
+        "app-build-manifest.json",
+        "server/server-reference-manifest.json",
+        "server/client-reference-manifest.json",
+        "server/flight-server-css-manifest.json",
+        "react-loadable-manifest.json",
+        "server/font-manifest.json",
+        "BUILD_ID",
+        "server/next-font-manifest.json",
+    ];
+
+    /// Creates a manifest for the project in `app_dir`, which is at
+    /// `relative_app_dir` in the workspace. `config` is the resolved Next.js
+    /// config.
+    pub fn new(
+        mut config: serde_json::Value,
+        app_dir: String,
+        relative_app_dir: String,
+        dist_dir: &str,
+    ) -> Self {
+        // The config is read from the manifest, not from the config file.
+        if let Some(config) = config.as_object_mut() {
+            config.remove("configFile");
+        }
+
+        Self {
+            version: 1,
+            config,
+            app_dir,
+            relative_app_dir,
+            files: Self::FILES
+                .iter()
+                .map(|file| format!("{dist_dir}/{file}"))
+                .collect(),
+            ignore: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use next_core::middleware::{get_middleware_matcher_regexp, DEFAULT_MIDDLEWARE_MATCHER_REGEXP};
+    use serde_json::json;
+
+    use super::{MiddlewaresManifestV2, NextFontManifest, RequiredServerFilesManifest};
+
+    #[test]
+    fn test_required_server_files_manifest() {
+        let manifest = RequiredServerFilesManifest::new(
+            json!({
+                "configFile": "/repo/apps/web/next.config.js",
+                "configFileName": "next.config.js",
+                "basePath": "/docs",
+            }),
+            "/repo/apps/web".to_string(),
+            "apps/web".to_string(),
+            ".next",
+        );
+        let manifest = serde_json::to_value(manifest).unwrap();
+
+        assert_eq!(manifest["version"], 1);
+        assert_eq!(
+            manifest["config"],
+            json!({ "configFileName": "next.config.js", "basePath": "/docs" })
+        );
+        assert_eq!(manifest["appDir"], "/repo/apps/web");
+        assert_eq!(manifest["relativeAppDir"], "apps/web");
+        let files = manifest["files"].as_array().unwrap();
+        assert!(files.contains(&json!(".next/BUILD_ID")));
+        assert!(files.contains(&json!(".next/server/pages-manifest.json")));
+        assert_eq!(manifest["ignore"], json!([]));
+    }
+
+    #[test]
+    fn test_next_font_manifest_is_font_file() {
+        assert!(NextFontManifest::is_font_file(
+            "static/media/my-font.a1b2c3.woff2"
+        ));
+        assert!(NextFontManifest::is_font_file("static/media/Font.TTF"));
+        assert!(!NextFontManifest::is_font_file(
+            "static/chunks/pages_index.js"
+        ));
+        assert!(!NextFontManifest::is_font_file("static/media/woff2"));
+    }
+
+    #[test]
+    fn test_middlewares_manifest_from_middleware() {
+        let manifest = MiddlewaresManifestV2::from_middleware(
+            vec!["server/edge/chunks/middleware.js".to_string()],
+            Some(&["/api/:path*".to_string()]),
+        )
+        .unwrap();
+        let manifest = serde_json::to_value(manifest).unwrap();
+
+        assert_eq!(manifest["sortedMiddleware"], json!(["/"]));
+        let middleware = &manifest["middleware"]["/"];
+        assert_eq!(middleware["name"], "middleware");
+        assert_eq!(middleware["page"], "/");
+        assert_eq!(
+            middleware["files"],
+            json!(["server/edge/chunks/middleware.js"])
+        );
+        assert_eq!(
+            middleware["matchers"],
+            json!([{
+                "regexp": get_middleware_matcher_regexp("/api/:path*").unwrap(),
+                "originalSource": "/api/:path*",
+            }])
+        );
+        assert_eq!(manifest["functions"], json!({}));
+    }
+
+    #[test]
+    fn test_middlewares_manifest_from_middleware_without_matchers() {
+        let manifest = MiddlewaresManifestV2::from_middleware(vec![], None).unwrap();
+        let manifest = serde_json::to_value(manifest).unwrap();
+
+        assert_eq!(
+            manifest["middleware"]["/"]["matchers"],
+            json!([{
+                "regexp": DEFAULT_MIDDLEWARE_MATCHER_REGEXP,
+                "originalSource": "/:path*",
+            }])
+        );
+    }
+}
@@ -1,14 +1,20 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     env::current_dir,
-    path::{PathBuf, MAIN_SEPARATOR},
+    path::{Path, PathBuf, MAIN_SEPARATOR},
 };
 
 use anyhow::{anyhow, Context, Result};
 use dunce::canonicalize;
 use next_core::{
-    self, next_config::load_next_config, pages_structure::find_pages_structure,
-    turbopack::ecmascript::utils::StringifyJs, url_node::get_sorted_routes,
+    self,
+    app_structure::{find_app_dir_if_enabled, get_entrypoints},
+    middleware::{get_middleware_chunks, MiddlewareChunks},
+    mode::NextMode,
+    next_config::{load_next_config, NextConfigVc},
+    pages_structure::find_pages_structure,
+    turbopack::ecmascript::utils::StringifyJs,
+    url_node::get_sorted_routes,
 };
 use serde::Serialize;
 use turbo_tasks::{
@@ -17,7 +23,10 @@ use turbo_tasks::{
     ValueToString,
 };
 use turbopack_binding::{
-    turbo::tasks_fs::{DiskFileSystemVc, FileContent, FileSystem, FileSystemPathVc, FileSystemVc},
+    turbo::{
+        tasks_fs::{DiskFileSystemVc, FileContent, FileSystem, FileSystemPathVc, FileSystemVc},
+        tasks_hash::hash_xxh3_hash64,
+    },
     turbopack::{
         cli_utils::issue::{ConsoleUiVc, LogOptions},
         core::{
@@ -25,6 +34,7 @@ use turbopack_binding::{
             environment::ServerAddrVc,
             issue::{IssueReporter, IssueReporterVc, IssueSeverity, IssueVc},
             reference::AssetReference,
+            source_map::{source_map_asset::SourceMapAssetVc, GenerateSourceMapVc},
             virtual_fs::VirtualFileSystemVc,
         },
         dev::DevChunkingContextVc,
@@ -36,12 +46,19 @@ use turbopack_binding::{
 
 use crate::{
     build_options::{BuildContext, BuildOptions},
+    bundle_budget::check_bundle_budgets,
+    dist_dir::clean_dist_dir,
+    lint::lint,
     manifests::{
         AppBuildManifest, AppPathsManifest, BuildManifest, ClientBuildManifest,
         ClientCssReferenceManifest, ClientReferenceManifest, FontManifest, MiddlewaresManifest,
-        NextFontManifest, PagesManifest, ReactLoadableManifest, ServerReferenceManifest,
+        MiddlewaresManifestV2, NextFontManifest, PagesManifest, ReactLoadableManifest,
+        RequiredServerFilesManifest, ServerReferenceManifest,
     },
-    next_pages::page_chunks::get_page_chunks,
+    next_pages::{custom_server::get_custom_server_chunk, page_chunks::get_page_chunks},
+    stats::BundleStats,
+    type_check::type_check,
+    typed_routes::link_types,
 };
 
 #[turbo_tasks::function]
@@ -92,6 +109,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         .strip_prefix(MAIN_SEPARATOR)
         .unwrap_or(project_relative)
         .replace(MAIN_SEPARATOR, "/");
+    let project_dir = project_root;
     let project_root = workspace_fs.root().join(&project_relative);
 
     let next_router_fs = VirtualFileSystemVc::new().as_file_system();
@@ -111,6 +129,25 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let execution_context = ExecutionContextVc::new(project_root, build_chunking_context, env);
     let next_config = load_next_config(execution_context.with_layer("next_config"));
 
+    // Builds driven by Next.js are cleaned, type checked and linted by Next.js
+    // itself.
+    if options.build_context.is_none() {
+        if *next_config.clean_dist_dir().await? {
+            let dist_dir = Path::new(&project_dir).join(&*next_config.dist_dir().await?);
+            clean_dist_dir(&dist_dir).await?;
+        }
+        let has_type_errors = type_check(project_root, &project_dir, next_config);
+        handle_issues(has_type_errors, issue_reporter).await?;
+        if *has_type_errors.await? {
+            return Err(anyhow!("Type errors occurred"));
+        }
+        let has_lint_errors = lint(project_root, &project_dir, next_config);
+        handle_issues(has_lint_errors, issue_reporter).await?;
+        if *has_lint_errors.await? {
+            return Err(anyhow!("Lint errors occurred"));
+        }
+    }
+
     let pages_structure = find_pages_structure(project_root, next_router_root, next_config);
 
     let page_chunks = get_page_chunks(
@@ -128,6 +165,22 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
 
     handle_issues(page_chunks, issue_reporter).await?;
 
+    let middleware_chunks = get_middleware_chunks(
+        project_root,
+        execution_context,
+        node_root.join("server/edge"),
+        env,
+        NextMode::Build,
+        next_config,
+    );
+    handle_issues(middleware_chunks, issue_reporter).await?;
+
+    let has_bundle_budget_errors = check_bundle_budgets(page_chunks, next_config);
+    handle_issues(has_bundle_budget_errors, issue_reporter).await?;
+    if *has_bundle_budget_errors.await? {
+        return Err(anyhow!("Bundle budgets were exceeded"));
+    }
+
     let filter_pages = std::env::var("NEXT_TURBO_FILTER_PAGES");
     let filter_pages = filter_pages
         .as_ref()
@@ -140,9 +193,11 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         let mut build_manifest: BuildManifest = Default::default();
         // Server manifest.
         let mut pages_manifest: PagesManifest = Default::default();
+        let mut next_font_manifest: NextFontManifest = Default::default();
 
         let build_manifest_path = client_root.join("build-manifest.json");
         let pages_manifest_path = node_root.join("server/pages-manifest.json");
+        let next_font_manifest_path = node_root.join("server/next-font-manifest.json");
 
         let page_chunks_and_url = page_chunks
             .await?
@@ -215,6 +270,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
 
             let mut deduplicated_node_assets = HashMap::new();
             let mut deduplicated_client_assets = HashMap::new();
+            let mut stats_entrypoints = Vec::new();
 
             // TODO(alexkirsz) We want all assets to emit them to the output directory, but
             // we only want runtime assets in the manifest. Furthermore, the pages
@@ -241,6 +297,23 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
                         .join("\n")
                 );
 
+                let mut font_files = Vec::new();
+                for asset in all_client_assets.iter() {
+                    let asset_path = asset.ident().path().await?;
+                    if !NextFontManifest::is_font_file(&asset_path.path) {
+                        continue;
+                    }
+                    if let Some(asset_path) = build_manifest_dir_path.get_path_to(&asset_path) {
+                        font_files.push(asset_path.to_string());
+                    }
+                }
+                if !font_files.is_empty() {
+                    font_files.sort();
+                    next_font_manifest
+                        .pages
+                        .insert(pathname.clone_value(), font_files);
+                }
+
                 // TODO(alexkirsz) Deduplication should not happen at this level, but
                 // right now we have chunks with the same path being generated
                 // from different entrypoints, and writing them multiple times causes
@@ -260,6 +333,8 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
                         .await?
                 );
 
+                stats_entrypoints.push((pathname.clone_value(), client_chunks));
+
                 let build_manifest_pages_entry = build_manifest
                     .pages
                     .entry(pathname.clone_value())
@@ -299,6 +374,19 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
                 .try_join()
                 .await?;
 
+            if options.bundle_stats {
+                let stats = BundleStats::new(
+                    &*client_root.await?,
+                    &stats_entrypoints,
+                    &deduplicated_client_assets,
+                )
+                .await?;
+                client_root
+                    .join("stats.json")
+                    .write(FileContent::Content(serde_json::to_string(&stats)?.into()).cell())
+                    .await?;
+            }
+
             tracing::debug!(
                 "all client assets: {}",
                 deduplicated_client_assets
@@ -310,28 +398,64 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
                     .await?
                     .join("\n")
             );
+            let hidden_source_maps = *next_config.hidden_source_maps().await?;
             deduplicated_client_assets
                 .into_values()
                 .map(|asset| async move {
                     emit(asset).await?;
+                    // Hidden source maps aren't referenced by their chunks, so they aren't
+                    // part of the asset graph and are emitted separately.
+                    if hidden_source_maps
+                        && GenerateSourceMapVc::resolve_from(asset).await?.is_some()
+                    {
+                        emit(SourceMapAssetVc::new(asset).into()).await?;
+                    }
                     Ok(())
                 })
                 .try_join()
                 .await?;
         }
 
+        let middleware_manifest = if let Some(middleware_chunks) = *middleware_chunks.await? {
+            let MiddlewareChunks {
+                chunks,
+                ref matchers,
+            } = *middleware_chunks.await?;
+
+            all_assets_from_entries(chunks)
+                .await?
+                .iter()
+                .map(|asset| async move {
+                    if asset.ident().path().await?.is_inside(&*node_root.await?) {
+                        emit(*asset).await?;
+                    }
+                    Ok(())
+                })
+                .try_join()
+                .await?;
+
+            let node_root_path = node_root.await?;
+            let mut files = vec![];
+            for chunk in chunks.await?.iter() {
+                let chunk_path = chunk.ident().path().await?;
+                if let Some(chunk_path) = node_root_path.get_path_to(&chunk_path) {
+                    files.push(chunk_path.to_string());
+                }
+            }
+
+            MiddlewaresManifest::MiddlewaresManifestV2(MiddlewaresManifestV2::from_middleware(
+                files,
+                matchers.as_deref(),
+            )?)
+        } else {
+            MiddlewaresManifest::default()
+        };
         write_placeholder_manifest(
-            &MiddlewaresManifest::default(),
+            &middleware_manifest,
             node_root,
             "server/middleware-manifest.json",
         )
         .await?;
-        write_placeholder_manifest(
-            &NextFontManifest::default(),
-            node_root,
-            "server/next-font-manifest.json",
-        )
-        .await?;
         write_placeholder_manifest(
             &FontManifest::default(),
             node_root,
@@ -375,86 +499,135 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         )
         .await?;
 
-        if let Some(build_context) = &options.build_context {
-            let BuildContext { build_id, rewrites } = build_context;
+        let (build_id, rewrites) = match &options.build_context {
+            Some(BuildContext { build_id, rewrites }) => (build_id.clone(), rewrites.clone()),
+            None => (
+                get_build_id(next_config, &build_manifest, &pages_manifest).await?,
+                next_config.rewrites().await?.clone_value(),
+            ),
+        };
+
+        node_root
+            .join("BUILD_ID")
+            .write(FileContent::Content(build_id.clone().into()).cell())
+            .await?;
 
-            tracing::debug!("writing _ssgManifest.js for build id: {}", build_id);
+        tracing::debug!("writing _ssgManifest.js for build id: {}", build_id);
 
-            let ssg_manifest_path = format!("static/{build_id}/_ssgManifest.js");
+        let ssg_manifest_path = format!("static/{build_id}/_ssgManifest.js");
 
-            let ssg_manifest_fs_path = node_root.join(&ssg_manifest_path);
-            ssg_manifest_fs_path
-                .write(
-                    FileContent::Content(
-                        "self.__SSG_MANIFEST=new \
-                         Set;self.__SSG_MANIFEST_CB&&self.__SSG_MANIFEST_CB()"
-                            .into(),
-                    )
-                    .cell(),
+        let ssg_manifest_fs_path = node_root.join(&ssg_manifest_path);
+        ssg_manifest_fs_path
+            .write(
+                FileContent::Content(
+                    "self.__SSG_MANIFEST=new Set;self.__SSG_MANIFEST_CB&&self.__SSG_MANIFEST_CB()"
+                        .into(),
                 )
-                .await?;
+                .cell(),
+            )
+            .await?;
 
-            build_manifest.low_priority_files.push(ssg_manifest_path);
+        build_manifest.low_priority_files.push(ssg_manifest_path);
 
-            let sorted_pages =
-                get_sorted_routes(&pages_manifest.pages.keys().cloned().collect::<Vec<_>>())?;
+        let sorted_pages =
+            get_sorted_routes(&pages_manifest.pages.keys().cloned().collect::<Vec<_>>())?;
 
-            let app_dependencies: HashSet<&str> = pages_manifest
-                .pages
-                .get("/_app")
-                .iter()
-                .map(|s| s.as_str())
-                .collect();
-            let mut pages = HashMap::new();
+        if *next_config.typed_routes().await? {
+            let mut routes = sorted_pages.clone();
+            if let Some(app_dir) = *find_app_dir_if_enabled(project_root, next_config).await? {
+                let entrypoints = get_entrypoints(app_dir, next_config.page_extensions()).await?;
+                routes.extend(entrypoints.keys().cloned());
+            }
 
-            for page in &sorted_pages {
-                if page == "_app" {
-                    continue;
-                }
+            node_root
+                .join("types/link.d.ts")
+                .write(
+                    FileContent::Content(link_types(routes.iter().map(String::as_str)).into())
+                        .cell(),
+                )
+                .await?;
+        }
 
-                let dependencies = build_manifest
-                    .pages
-                    .get(page)
-                    .unwrap()
-                    .iter()
-                    .map(|dep| dep.as_str())
-                    .filter(|dep| !app_dependencies.contains(*dep))
-                    .collect::<Vec<_>>();
+        let app_dependencies: HashSet<&str> = pages_manifest
+            .pages
+            .get("/_app")
+            .iter()
+            .map(|s| s.as_str())
+            .collect();
+        let mut pages = HashMap::new();
 
-                if !dependencies.is_empty() {
-                    pages.insert(page.to_string(), dependencies);
-                }
+        for page in &sorted_pages {
+            if page == "_app" {
+                continue;
             }
 
-            let client_manifest = ClientBuildManifest {
-                rewrites,
-                sorted_pages: &sorted_pages,
-                pages,
-            };
+            let dependencies = build_manifest
+                .pages
+                .get(page)
+                .unwrap()
+                .iter()
+                .map(|dep| dep.as_str())
+                .filter(|dep| !app_dependencies.contains(*dep))
+                .collect::<Vec<_>>();
 
-            let client_manifest_path = format!("static/{build_id}/_buildManifest.js");
+            if !dependencies.is_empty() {
+                pages.insert(page.to_string(), dependencies);
+            }
+        }
 
-            let client_manifest_fs_path = node_root.join(&client_manifest_path);
-            client_manifest_fs_path
-                .write(
-                    FileContent::Content(
-                        format!(
-                            "self.__BUILD_MANIFEST={};self.__BUILD_MANIFEST_CB && \
-                             self.__BUILD_MANIFEST_CB()",
-                            StringifyJs(&client_manifest)
-                        )
-                        .into(),
+        let client_manifest = ClientBuildManifest {
+            rewrites: &rewrites,
+            sorted_pages: &sorted_pages,
+            pages,
+        };
+
+        let client_manifest_path = format!("static/{build_id}/_buildManifest.js");
+
+        let client_manifest_fs_path = node_root.join(&client_manifest_path);
+        client_manifest_fs_path
+            .write(
+                FileContent::Content(
+                    format!(
+                        "self.__BUILD_MANIFEST={};self.__BUILD_MANIFEST_CB && \
+                         self.__BUILD_MANIFEST_CB()",
+                        StringifyJs(&client_manifest)
                     )
-                    .cell(),
+                    .into(),
                 )
-                .await?;
+                .cell(),
+            )
+            .await?;
 
-            build_manifest.low_priority_files.push(client_manifest_path);
-        }
+        build_manifest.low_priority_files.push(client_manifest_path);
 
         // TODO(alexkirsz) These manifests should be assets.
         let build_manifest_contents = serde_json::to_string_pretty(&build_manifest)?;
         let pages_manifest_contents = serde_json::to_string_pretty(&pages_manifest)?;
+        let next_font_manifest_contents = serde_json::to_string_pretty(&next_font_manifest)?;
+
+        let custom_server_chunk = get_custom_server_chunk(
+            pages_structure,
+            project_root,
+            execution_context,
+            node_root,
+            env,
+            next_config,
+            &pages_manifest_contents,
+        );
+        handle_issues(custom_server_chunk, issue_reporter).await?;
+        if let Some(custom_server_chunk) = *custom_server_chunk.await? {
+            all_assets_from_entry(custom_server_chunk)
+                .await?
+                .iter()
+                .map(|asset| async move {
+                    if asset.ident().path().await?.is_inside(&*node_root.await?) {
+                        emit(*asset).await?;
+                    }
+                    Ok(())
+                })
+                .try_join()
+                .await?;
+        }
 
         build_manifest_path
             .write(FileContent::Content(build_manifest_contents.into()).cell())
@@ -462,11 +635,53 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         pages_manifest_path
             .write(FileContent::Content(pages_manifest_contents.into()).cell())
             .await?;
+        next_font_manifest_path
+            .write(FileContent::Content(next_font_manifest_contents.into()).cell())
+            .await?;
+
+        // Next.js writes its own, with the config as it resolved it.
+        if options.build_context.is_none() {
+            let required_server_files = RequiredServerFilesManifest::new(
+                serde_json::to_value(&*next_config.await?)?,
+                project_dir.clone(),
+                project_relative.clone(),
+                ".next",
+            );
+            node_root
+                .join("required-server-files.json")
+                .write(
+                    FileContent::Content(
+                        serde_json::to_string_pretty(&required_server_files)?.into(),
+                    )
+                    .cell(),
+                )
+                .await?;
+        }
     }
 
     Ok(CompletionVc::immutable())
 }
 
+/// Computes the build id when the build isn't driven by Next.js, which passes
+/// its own in the [BuildContext]. The value returned by `generateBuildId` takes
+/// precedence, otherwise the id is derived from the manifests so that builds
+/// with the same output agree on it.
+async fn get_build_id(
+    next_config: NextConfigVc,
+    build_manifest: &BuildManifest,
+    pages_manifest: &PagesManifest,
+) -> Result<String> {
+    if let Some(build_id) = &*next_config.generate_build_id().await? {
+        return Ok(build_id.clone());
+    }
+
+    let client_pages: BTreeMap<_, _> = build_manifest.pages.iter().collect();
+    let server_pages: BTreeMap<_, _> = pages_manifest.pages.iter().collect();
+    let content = serde_json::to_string(&(client_pages, server_pages))?;
+
+    Ok(format!("{:016x}", hash_xxh3_hash64(content.as_bytes())))
+}
+
 #[turbo_tasks::function]
 fn emit(asset: AssetVc) -> CompletionVc {
     asset.content().write(asset.ident().path())
@@ -1,6 +1,7 @@
 use anyhow::{bail, Result};
 use next_core::{
     create_page_loader_entry_asset,
+    next_config::NextConfigVc,
     turbopack::core::{asset::AssetsVc, chunk::EvaluatableAssetsVc},
 };
 use turbopack_binding::{
@@ -22,6 +23,7 @@ pub(crate) struct PagesBuildClientContext {
     client_root: FileSystemPathVc,
     client_asset_context: AssetContextVc,
     client_runtime_entries: EvaluatableAssetsVc,
+    next_config: NextConfigVc,
 }
 
 #[turbo_tasks::value_impl]
@@ -32,12 +34,14 @@ impl PagesBuildClientContextVc {
         client_root: FileSystemPathVc,
         client_asset_context: AssetContextVc,
         client_runtime_entries: EvaluatableAssetsVc,
+        next_config: NextConfigVc,
     ) -> PagesBuildClientContextVc {
         PagesBuildClientContext {
             project_root,
             client_root,
             client_asset_context,
             client_runtime_entries,
+            next_config,
         }
         .cell()
     }
@@ -53,6 +57,7 @@ impl PagesBuildClientContextVc {
             this.client_root.join("static/media"),
             this.client_asset_context.compile_time_info().environment(),
         )
+        .reference_chunk_source_maps(!*this.next_config.hidden_source_maps().await?)
         .build())
     }
 
@@ -0,0 +1,120 @@
+use anyhow::Result;
+use next_core::{
+    mode::NextMode,
+    next_config::NextConfigVc,
+    next_server::{
+        get_server_compile_time_info, get_server_module_options_context,
+        get_server_resolve_options_context, ServerContextType,
+    },
+    pages_structure::PagesStructureVc,
+};
+use turbopack_binding::{
+    turbo::{
+        tasks::Value,
+        tasks_env::ProcessEnvVc,
+        tasks_fs::{File, FileSystemEntryType, FileSystemPathVc},
+    },
+    turbopack::{
+        core::{
+            asset::AssetVc,
+            context::{AssetContext, AssetContextVc},
+            environment::ServerAddrVc,
+            file_source::FileSourceVc,
+            reference_type::{InnerAssetsVc, ReferenceType},
+            virtual_source::VirtualSourceVc,
+        },
+        node::execution_context::ExecutionContextVc,
+        turbopack::{transition::TransitionsByNameVc, ModuleAssetContextVc},
+    },
+};
+
+use super::{
+    node_context::PagesBuildNodeContextVc,
+    page_chunks::{get_node_runtime_entries, get_pages_dir},
+};
+
+/// The files at the project root that are compiled as the custom server, in
+/// order of precedence.
+const CUSTOM_SERVER_FILES: &[&str] = &["server.ts", "server.js"];
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionCustomServerChunk(Option<AssetVc>);
+
+/// Returns the entry chunk of the custom server at the project root, if there
+/// is one. The server is compiled with the Node.js server context and can
+/// import the pages manifest from `PAGES_MANIFEST`.
+#[turbo_tasks::function]
+pub async fn get_custom_server_chunk(
+    pages_structure: PagesStructureVc,
+    project_root: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    node_root: FileSystemPathVc,
+    env: ProcessEnvVc,
+    next_config: NextConfigVc,
+    pages_manifest: &str,
+) -> Result<OptionCustomServerChunkVc> {
+    let mut custom_server = None;
+    for file in CUSTOM_SERVER_FILES {
+        let path = project_root.join(file);
+        if *path.get_type().await? == FileSystemEntryType::File {
+            custom_server = Some(path);
+            break;
+        }
+    }
+    let Some(custom_server) = custom_server else {
+        return Ok(OptionCustomServerChunkVc::cell(None));
+    };
+
+    let mode = NextMode::Build;
+    let node_ty = Value::new(ServerContextType::Pages {
+        pages_dir: get_pages_dir(pages_structure, project_root),
+    });
+
+    let node_asset_context: AssetContextVc = ModuleAssetContextVc::new(
+        TransitionsByNameVc::cell(Default::default()),
+        get_server_compile_time_info(mode, env, ServerAddrVc::empty()),
+        get_server_module_options_context(
+            project_root,
+            execution_context,
+            node_ty,
+            mode,
+            next_config,
+        ),
+        get_server_resolve_options_context(
+            project_root,
+            node_ty,
+            mode,
+            next_config,
+            execution_context,
+        ),
+    )
+    .into();
+
+    let pages_manifest = node_asset_context.process(
+        VirtualSourceVc::new(
+            project_root.join("pages-manifest.js"),
+            File::from(format!("export default {pages_manifest};")).into(),
+        )
+        .into(),
+        Value::new(ReferenceType::Internal(InnerAssetsVc::empty())),
+    );
+
+    let node_build_context = PagesBuildNodeContextVc::new(
+        project_root,
+        node_root,
+        node_asset_context,
+        get_node_runtime_entries(project_root, env, next_config),
+        next_config,
+    );
+
+    Ok(OptionCustomServerChunkVc::cell(Some(
+        node_build_context.custom_server_chunk(
+            FileSourceVc::new(custom_server).into(),
+            InnerAssetsVc::cell(
+                [("PAGES_MANIFEST".to_string(), pages_manifest.into())]
+                    .into_iter()
+                    .collect(),
+            ),
+        ),
+    )))
+}
@@ -1,3 +1,4 @@
 pub(crate) mod client_context;
+pub(crate) mod custom_server;
 pub(crate) mod node_context;
 pub(crate) mod page_chunks;
@@ -1,14 +1,17 @@
 use anyhow::{bail, Result};
-use next_core::{next_client::RuntimeEntriesVc, turbopack::core::chunk::EvaluatableAssetsVc};
-use turbo_tasks::primitives::StringVc;
+use next_core::{
+    link_prefetch::link_targets, next_client::RuntimeEntriesVc, next_config::NextConfigVc,
+    turbopack::core::chunk::EvaluatableAssetsVc,
+};
+use turbo_tasks::primitives::{StringVc, StringsVc};
 use turbopack_binding::{
     turbo::{tasks::Value, tasks_fs::FileSystemPathVc},
     turbopack::{
-        build::BuildChunkingContextVc,
+        build::{BuildChunkingContextVc, MinifyType},
         core::{
             asset::AssetVc,
             context::{AssetContext, AssetContextVc},
-            reference_type::{EntryReferenceSubType, ReferenceType},
+            reference_type::{EntryReferenceSubType, InnerAssetsVc, ReferenceType},
             resolve::{parse::RequestVc, pattern::QueryMapVc},
             source::SourceVc,
         },
@@ -22,6 +25,7 @@ pub(crate) struct PagesBuildNodeContext {
     node_root: FileSystemPathVc,
     node_asset_context: AssetContextVc,
     node_runtime_entries: EvaluatableAssetsVc,
+    next_config: NextConfigVc,
 }
 
 #[turbo_tasks::value_impl]
@@ -32,12 +36,14 @@ impl PagesBuildNodeContextVc {
         node_root: FileSystemPathVc,
         node_asset_context: AssetContextVc,
         node_runtime_entries: RuntimeEntriesVc,
+        next_config: NextConfigVc,
     ) -> PagesBuildNodeContextVc {
         PagesBuildNodeContext {
             project_root,
             node_root,
             node_asset_context,
             node_runtime_entries: node_runtime_entries.resolve_entries(node_asset_context),
+            next_config,
         }
         .cell()
     }
@@ -86,9 +92,30 @@ impl PagesBuildNodeContextVc {
             this.node_root.join("server/assets"),
             this.node_asset_context.compile_time_info().environment(),
         )
+        .minify_type(if *this.next_config.server_minification().await? {
+            MinifyType::Minify
+        } else {
+            MinifyType::NoMinify
+        })
         .build())
     }
 
+    /// Returns the paths that the page in `source` links to.
+    #[turbo_tasks::function]
+    pub async fn link_targets(
+        self,
+        source: SourceVc,
+        reference_type: Value<ReferenceType>,
+    ) -> Result<StringsVc> {
+        let this = self.await?;
+        Ok(link_targets(
+            this.node_asset_context
+                .process(source, reference_type)
+                .into(),
+            this.project_root,
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn node_chunk(
         self,
@@ -116,4 +143,31 @@ impl PagesBuildNodeContextVc {
             this.node_runtime_entries,
         ))
     }
+
+    /// Returns the entry chunk of the custom server in `source`, which can
+    /// import the `inner_assets`.
+    #[turbo_tasks::function]
+    pub async fn custom_server_chunk(
+        self,
+        source: SourceVc,
+        inner_assets: InnerAssetsVc,
+    ) -> Result<AssetVc> {
+        let this = self.await?;
+
+        let node_asset = this
+            .node_asset_context
+            .process(source, Value::new(ReferenceType::Internal(inner_assets)));
+
+        let Some(node_module_asset) = EcmascriptChunkPlaceableVc::resolve_from(node_asset).await?
+        else {
+            bail!("Expected an EcmaScript module asset");
+        };
+
+        let chunking_context = self.node_chunking_context();
+        Ok(chunking_context.generate_entry_chunk(
+            this.node_root.join("server/custom-server.js"),
+            node_module_asset,
+            this.node_runtime_entries,
+        ))
+    }
 }
@@ -24,13 +24,16 @@ use next_core::{
 use turbo_tasks::ValueToString;
 use turbopack_binding::{
     turbo::{
-        tasks::{primitives::StringVc, Value},
+        tasks::{
+            primitives::{StringVc, StringsVc},
+            Value,
+        },
         tasks_env::ProcessEnvVc,
         tasks_fs::FileSystemPathVc,
     },
     turbopack::{
         core::{
-            asset::AssetVc,
+            asset::{Asset, AssetVc},
             context::AssetContextVc,
             environment::ServerAddrVc,
             file_source::FileSourceVc,
@@ -70,11 +73,7 @@ pub async fn get_page_chunks(
     next_config: NextConfigVc,
     node_addr: ServerAddrVc,
 ) -> Result<PageChunksVc> {
-    let pages_dir = if let Some(pages) = pages_structure.await?.pages {
-        pages.project_path().resolve().await?
-    } else {
-        project_root.join("pages")
-    };
+    let pages_dir = get_pages_dir(pages_structure, project_root);
 
     let mode = NextMode::Build;
 
@@ -167,12 +166,14 @@ pub async fn get_page_chunks(
         node_root,
         node_asset_context,
         node_runtime_entries,
+        next_config,
     );
     let client_build_context = PagesBuildClientContextVc::new(
         project_root,
         client_root,
         client_asset_context,
         client_runtime_entries,
+        next_config,
     );
 
     Ok(get_page_chunks_for_root_directory(
@@ -318,10 +319,14 @@ async fn get_page_chunks_for_directory(
 pub struct PageChunk {
     /// The pathname of the page.
     pub pathname: StringVc,
+    /// The source file of the page.
+    pub source_path: FileSystemPathVc,
     /// The Node.js chunk.
     pub node_chunk: AssetVc,
     /// The client chunks.
     pub client_chunks: AssetsVc,
+    /// The paths the page links to, whose chunks can be prefetched.
+    pub link_targets: StringsVc,
 }
 
 #[turbo_tasks::function]
@@ -340,8 +345,10 @@ async fn get_page_chunk_for_file(
 
     Ok(PageChunk {
         pathname,
+        source_path: page_asset.ident().path(),
         node_chunk: node_build_context.node_chunk(page_asset, original_path, reference_type),
         client_chunks: client_build_context.client_chunk(page_asset, pathname),
+        link_targets: node_build_context.link_targets(page_asset, reference_type),
     }
     .cell())
 }
@@ -365,8 +372,21 @@ pub async fn get_original_path(
     Ok(StringVc::cell(path.to_string()))
 }
 
+/// Returns the pages directory, which is either `pages` or `src/pages`.
+#[turbo_tasks::function]
+pub(crate) async fn get_pages_dir(
+    pages_structure: PagesStructureVc,
+    project_root: FileSystemPathVc,
+) -> Result<FileSystemPathVc> {
+    Ok(if let Some(pages) = pages_structure.await?.pages {
+        pages.project_path().resolve().await?
+    } else {
+        project_root.join("pages")
+    })
+}
+
 #[turbo_tasks::function]
-fn get_node_runtime_entries(
+pub(crate) fn get_node_runtime_entries(
     project_root: FileSystemPathVc,
     env: ProcessEnvVc,
     next_config: NextConfigVc,
@@ -0,0 +1,281 @@
+//! A `stats.json` for bundle analysis tools, in a subset of the format of
+//! webpack's stats.
+//!
+//! Every client chunk file is a chunk of its own. The modules of a chunk are
+//! read from the sections of its source map, and their size is the size of
+//! their original source.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileContent, FileSystemPath},
+    turbopack::core::{
+        asset::{Asset, AssetVc, AssetsVc},
+        reference::AssetReference,
+    },
+};
+
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleStats {
+    pub assets: Vec<StatsAsset>,
+    pub chunks: Vec<StatsChunk>,
+    pub entrypoints: BTreeMap<String, StatsChunkGroup>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsAsset {
+    pub name: String,
+    pub size: usize,
+    pub chunks: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsChunk {
+    pub id: String,
+    pub files: Vec<String>,
+    pub auxiliary_files: Vec<String>,
+    pub size: usize,
+    pub initial: bool,
+    pub parents: BTreeSet<String>,
+    pub children: BTreeSet<String>,
+    pub modules: Vec<StatsModule>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsModule {
+    pub name: String,
+    pub size: usize,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsChunkGroup {
+    pub name: String,
+    pub chunks: Vec<String>,
+    pub assets: Vec<StatsChunkGroupAsset>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsChunkGroupAsset {
+    pub name: String,
+    pub size: usize,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct SourceMapJson {
+    #[serde(default)]
+    sources: Vec<String>,
+    #[serde(default)]
+    sources_content: Vec<Option<String>>,
+    #[serde(default)]
+    sections: Vec<SourceMapSection>,
+}
+
+#[derive(Deserialize)]
+struct SourceMapSection {
+    map: SourceMapJson,
+}
+
+impl BundleStats {
+    /// Computes the stats of the client `assets`, which are keyed by their
+    /// path. `entrypoints` are the pages with their client chunks.
+    pub async fn new(
+        client_root: &FileSystemPath,
+        entrypoints: &[(String, AssetsVc)],
+        assets: &HashMap<String, AssetVc>,
+    ) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        for asset in assets.values() {
+            let path = asset.ident().path().await?;
+            let Some(name) = client_root.get_path_to(&path) else {
+                continue;
+            };
+            let FileContent::Content(file) = &*asset.content().file_content().await? else {
+                continue;
+            };
+            files.insert(
+                name.to_string(),
+                (*asset, file.content().to_bytes()?.into_owned()),
+            );
+        }
+
+        let mut chunks = BTreeMap::new();
+        for (name, (asset, content)) in &files {
+            if !is_chunk_file(name) {
+                continue;
+            }
+
+            let source_map_name = format!("{name}.map");
+            let auxiliary_files = if files.contains_key(&source_map_name) {
+                vec![source_map_name]
+            } else {
+                Vec::new()
+            };
+            let modules = match auxiliary_files.first().and_then(|name| files.get(name)) {
+                Some((_, source_map)) => modules_from_source_map(source_map)?,
+                None => Vec::new(),
+            };
+
+            let mut children = BTreeSet::new();
+            for reference in asset.references().await?.iter() {
+                for referenced in reference.resolve_reference().primary_assets().await?.iter() {
+                    let path = referenced.ident().path().await?;
+                    if let Some(child) = client_root.get_path_to(&path) {
+                        if child != name && is_chunk_file(child) {
+                            children.insert(child.to_string());
+                        }
+                    }
+                }
+            }
+
+            chunks.insert(
+                name.clone(),
+                StatsChunk {
+                    id: name.clone(),
+                    files: vec![name.clone()],
+                    auxiliary_files,
+                    size: content.len(),
+                    initial: false,
+                    parents: BTreeSet::new(),
+                    children,
+                    modules,
+                },
+            );
+        }
+
+        let parents = chunks
+            .values()
+            .flat_map(|chunk| {
+                chunk
+                    .children
+                    .iter()
+                    .map(|child| (child.clone(), chunk.id.clone()))
+            })
+            .collect::<Vec<_>>();
+        for (child, parent) in parents {
+            if let Some(chunk) = chunks.get_mut(&child) {
+                chunk.parents.insert(parent);
+            }
+        }
+
+        let mut stats = BundleStats::default();
+        for (pathname, client_chunks) in entrypoints {
+            let mut group = StatsChunkGroup {
+                name: pathname.clone(),
+                chunks: Vec::new(),
+                assets: Vec::new(),
+            };
+            for chunk in client_chunks.await?.iter() {
+                let path = chunk.ident().path().await?;
+                let Some(name) = client_root.get_path_to(&path) else {
+                    continue;
+                };
+                if let Some(chunk) = chunks.get_mut(name) {
+                    chunk.initial = true;
+                    group.chunks.push(chunk.id.clone());
+                }
+                if let Some((_, content)) = files.get(name) {
+                    group.assets.push(StatsChunkGroupAsset {
+                        name: name.to_string(),
+                        size: content.len(),
+                    });
+                }
+            }
+            stats.entrypoints.insert(pathname.clone(), group);
+        }
+
+        for (name, (_, content)) in &files {
+            let chunk = name.strip_suffix(".map").unwrap_or(name);
+            stats.assets.push(StatsAsset {
+                name: name.clone(),
+                size: content.len(),
+                chunks: if chunks.contains_key(chunk) {
+                    vec![chunk.to_string()]
+                } else {
+                    Vec::new()
+                },
+            });
+        }
+        stats.chunks = chunks.into_values().collect();
+
+        Ok(stats)
+    }
+}
+
+fn is_chunk_file(name: &str) -> bool {
+    name.ends_with(".js") || name.ends_with(".css")
+}
+
+/// Lists the modules of a chunk from its source map, where every module has
+/// its own section.
+fn modules_from_source_map(source_map: &[u8]) -> Result<Vec<StatsModule>> {
+    let source_map: SourceMapJson = serde_json::from_slice(source_map)?;
+
+    let mut modules = BTreeMap::new();
+    let maps = std::iter::once(&source_map).chain(source_map.sections.iter().map(|s| &s.map));
+    for map in maps {
+        for (index, source) in map.sources.iter().enumerate() {
+            let size = map
+                .sources_content
+                .get(index)
+                .and_then(|content| content.as_ref())
+                .map_or(0, |content| content.len());
+            modules.entry(source.clone()).or_insert(size);
+        }
+    }
+
+    Ok(modules
+        .into_iter()
+        .map(|(name, size)| StatsModule { name, size })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::modules_from_source_map;
+
+    #[test]
+    fn test_modules_from_source_map() {
+        let source_map = serde_json::json!({
+            "version": 3,
+            "sections": [
+                {
+                    "offset": { "line": 0, "column": 0 },
+                    "map": {
+                        "version": 3,
+                        "sources": ["pages/index.js"],
+                        "sourcesContent": ["export default 1"],
+                    },
+                },
+                {
+                    "offset": { "line": 4, "column": 0 },
+                    "map": {
+                        "version": 3,
+                        "sources": ["node_modules/react/index.js", "pages/index.js"],
+                        "sourcesContent": [null, "export default 2"],
+                    },
+                },
+            ],
+        });
+
+        let modules = modules_from_source_map(source_map.to_string().as_bytes()).unwrap();
+        let modules: Vec<_> = modules
+            .iter()
+            .map(|module| (module.name.as_str(), module.size))
+            .collect();
+        // Modules without a source content have no known size, and a module
+        // that appears in several sections is only counted once.
+        assert_eq!(
+            modules,
+            vec![("node_modules/react/index.js", 0), ("pages/index.js", 16)]
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Type checking for builds that aren't driven by Next.js, which type checks
+//! the project itself. Turbopack only strips types, so the project's own
+//! TypeScript compiler is run over the project and the errors it reports are
+//! emitted as issues, which fail the build.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use next_core::next_config::NextConfigVc;
+use tokio::process::Command;
+use turbo_tasks::primitives::{BoolVc, StringVc};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileContent, FileSystemPathVc},
+    turbopack::core::{
+        file_source::FileSourceVc,
+        issue::{
+            Issue, IssueSeverity, IssueSeverityVc, IssueSourceVc, IssueVc, OptionIssueSourceVc,
+        },
+    },
+};
+
+/// Runs `tsc` over the project in `project_dir`, the directory of
+/// `project_path` on disk, and emits a [TypeScriptIssue] for every type
+/// error. Returns whether the build should fail. Projects without a tsconfig
+/// are not type checked, and neither are projects that set
+/// `typescript.ignoreBuildErrors`.
+#[turbo_tasks::function]
+pub(crate) async fn type_check(
+    project_path: FileSystemPathVc,
+    project_dir: &str,
+    next_config: NextConfigVc,
+) -> Result<BoolVc> {
+    if *next_config.typescript_ignore_build_errors().await? {
+        return Ok(BoolVc::cell(false));
+    }
+
+    let tsconfig = next_config
+        .typescript_tsconfig_path()
+        .await?
+        .clone()
+        .unwrap_or_else(|| "tsconfig.json".to_string());
+    let project_dir = Path::new(project_dir);
+    if !project_dir.join(&tsconfig).exists() {
+        return Ok(BoolVc::cell(false));
+    }
+
+    let Some(tsc) = project_dir
+        .ancestors()
+        .map(|dir| dir.join("node_modules/typescript/bin/tsc"))
+        .find(|tsc| tsc.exists())
+    else {
+        TypeScriptIssue {
+            path: project_path.join(&tsconfig),
+            title: "TypeScript is not installed".to_string(),
+            description: format!(
+                "The project is configured with {tsconfig}, but the typescript package can't be \
+                 found to type check it. Install it, or set `typescript.ignoreBuildErrors` to \
+                 skip type checking."
+            ),
+            source: None,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+        return Ok(BoolVc::cell(true));
+    };
+
+    let output = Command::new("node")
+        .arg(&tsc)
+        .args(["--noEmit", "--pretty", "false", "--project", &tsconfig])
+        .current_dir(project_dir)
+        .output()
+        .await
+        .context("failed to run tsc")?;
+
+    let diagnostics = parse_diagnostics(&String::from_utf8_lossy(&output.stdout));
+    let has_errors = !diagnostics.is_empty();
+    for diagnostic in diagnostics {
+        let (path, source) = match &diagnostic.file {
+            Some(file) => {
+                let path = project_path.join(file);
+                let source = match &*path.read().await? {
+                    FileContent::Content(content) => byte_offset(
+                        &content.content().to_str()?,
+                        diagnostic.line,
+                        diagnostic.column,
+                    )
+                    .map(|offset| {
+                        IssueSourceVc::from_byte_offset(
+                            FileSourceVc::new(path).into(),
+                            offset,
+                            offset,
+                        )
+                    }),
+                    FileContent::NotFound => None,
+                };
+                (path, source)
+            }
+            None => (project_path.join(&tsconfig), None),
+        };
+        TypeScriptIssue {
+            path,
+            title: format!("Type error TS{}", diagnostic.code),
+            description: diagnostic.message,
+            source,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+    }
+
+    Ok(BoolVc::cell(has_errors))
+}
+
+/// An error reported by `tsc`.
+#[derive(Debug, PartialEq, Eq)]
+struct Diagnostic {
+    /// The path of the file relative to the project, if the error is in a
+    /// file.
+    file: Option<String>,
+    /// 1-based, like `column`.
+    line: usize,
+    column: usize,
+    code: u32,
+    message: String,
+}
+
+/// Parses the output of `tsc --pretty false`, where every error starts with a
+/// line like `pages/index.tsx(3,7): error TS2322: Message`. Errors that aren't
+/// in a file, like an invalid tsconfig, have no location. The lines of a
+/// message after the first are indented.
+fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for line in output.lines() {
+        if line.starts_with(' ') {
+            if let Some(diagnostic) = diagnostics.last_mut() {
+                diagnostic.message.push('\n');
+                diagnostic.message.push_str(line);
+            }
+        } else if let Some(diagnostic) = parse_diagnostic(line) {
+            diagnostics.push(diagnostic);
+        }
+    }
+    diagnostics
+}
+
+fn parse_diagnostic(line: &str) -> Option<Diagnostic> {
+    let (location, rest) = match line.split_once(": error TS") {
+        Some((location, rest)) => (Some(location), rest),
+        None => (None, line.strip_prefix("error TS")?),
+    };
+    let (code, message) = rest.split_once(": ")?;
+    let (file, line, column) = match location {
+        Some(location) => {
+            let (file, position) = location.strip_suffix(')')?.rsplit_once('(')?;
+            let (line, column) = position.split_once(',')?;
+            (
+                Some(file.to_string()),
+                line.parse().ok()?,
+                column.parse().ok()?,
+            )
+        }
+        None => (None, 0, 0),
+    };
+    Some(Diagnostic {
+        file,
+        line,
+        column,
+        code: code.parse().ok()?,
+        message: message.to_string(),
+    })
+}
+
+/// Converts a 1-based line and column into a byte offset into `content`.
+pub(crate) fn byte_offset(content: &str, line: usize, column: usize) -> Option<usize> {
+    let line_start = match line {
+        0 => return None,
+        1 => 0,
+        _ => content.match_indices('\n').nth(line - 2)?.0 + 1,
+    };
+    let line_content = content[line_start..].split('\n').next()?;
+    let column_offset = line_content
+        .char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line_content.len(), |(offset, _)| offset);
+    Some(line_start + column_offset)
+}
+
+#[turbo_tasks::value(shared)]
+struct TypeScriptIssue {
+    path: FileSystemPathVc,
+    title: String,
+    description: String,
+    source: Option<IssueSourceVc>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for TypeScriptIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(self.title.clone())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("type checking".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(self.description.clone())
+    }
+
+    #[turbo_tasks::function]
+    fn source(&self) -> OptionIssueSourceVc {
+        OptionIssueSourceVc::cell(self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{byte_offset, parse_diagnostics, Diagnostic};
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let output = "pages/index.tsx(3,7): error TS2322: Type 'string' is not assignable to type \
+                      'number'.\nlib/a.ts(10,1): error TS2345: Argument of type 'A' is not \
+                      assignable.\n  Property 'b' is missing in type 'A'.\nerror TS5023: Unknown \
+                      compiler option 'foo'.\n";
+        assert_eq!(
+            parse_diagnostics(output),
+            vec![
+                Diagnostic {
+                    file: Some("pages/index.tsx".to_string()),
+                    line: 3,
+                    column: 7,
+                    code: 2322,
+                    message: "Type 'string' is not assignable to type 'number'.".to_string(),
+                },
+                Diagnostic {
+                    file: Some("lib/a.ts".to_string()),
+                    line: 10,
+                    column: 1,
+                    code: 2345,
+                    message: "Argument of type 'A' is not assignable.\n  Property 'b' is missing \
+                              in type 'A'."
+                        .to_string(),
+                },
+                Diagnostic {
+                    file: None,
+                    line: 0,
+                    column: 0,
+                    code: 5023,
+                    message: "Unknown compiler option 'foo'.".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_byte_offset() {
+        let content = "const a = 1\nconst b: number = 'b'\n";
+        assert_eq!(byte_offset(content, 1, 1), Some(0));
+        assert_eq!(byte_offset(content, 2, 7), Some(18));
+        assert_eq!(byte_offset(content, 2, 100), Some(33));
+        assert_eq!(byte_offset(content, 4, 1), None);
+    }
+}
@@ -0,0 +1,174 @@
+//! Type definitions for `experimental.typedRoutes`, written to
+//! `.next/types/link.d.ts`. They restrict the `href` of `next/link` to the
+//! routes of the project, like the definitions generated by webpack's
+//! `NextTypesPlugin`.
+
+use std::collections::BTreeSet;
+
+/// Returns the types of the paths that match a route. Routes that can't be
+/// linked to, like API routes and `_app`, have none.
+///
+/// Static routes are string literals. Dynamic segments are `${string}`, so
+/// that `/blog/[slug]` becomes `` `/blog/${string}` ``. An optional catch-all
+/// also matches the route without the segment.
+fn route_types(route: &str) -> Vec<String> {
+    if route.starts_with("/_") || route == "/api" || route.starts_with("/api/") {
+        return Vec::new();
+    }
+
+    let mut prefixes = vec![String::new()];
+    for segment in route.split('/').filter(|segment| !segment.is_empty()) {
+        if segment.starts_with("[[...") && segment.ends_with("]]") {
+            let without_segment = prefixes.clone();
+            for prefix in &mut prefixes {
+                prefix.push_str("/${string}");
+            }
+            prefixes.extend(without_segment);
+        } else if segment.starts_with('[') && segment.ends_with(']') {
+            for prefix in &mut prefixes {
+                prefix.push_str("/${string}");
+            }
+        } else {
+            for prefix in &mut prefixes {
+                prefix.push('/');
+                prefix.push_str(segment);
+            }
+        }
+    }
+
+    prefixes
+        .into_iter()
+        .map(|path| {
+            let path = if path.is_empty() {
+                "/".to_string()
+            } else {
+                path
+            };
+            if path.contains("${") {
+                format!("`{path}`")
+            } else {
+                format!("'{path}'")
+            }
+        })
+        .collect()
+}
+
+/// Generates the contents of `link.d.ts` for the given routes of the pages
+/// and app directories, e.g. `/blog/[slug]`.
+pub fn link_types<'a>(routes: impl IntoIterator<Item = &'a str>) -> String {
+    let mut static_routes = BTreeSet::new();
+    let mut dynamic_routes = BTreeSet::new();
+    for route in routes {
+        for route_type in route_types(route) {
+            if route_type.starts_with('`') {
+                dynamic_routes.insert(route_type);
+            } else {
+                static_routes.insert(route_type);
+            }
+        }
+    }
+
+    let union = |types: &BTreeSet<String>| {
+        if types.is_empty() {
+            " never".to_string()
+        } else {
+            types
+                .iter()
+                .map(|route_type| format!("\n    | {route_type}"))
+                .collect()
+        }
+    };
+
+    format!(
+        r#"// Type definitions for Next.js routes
+
+/**
+ * Internal types used by the Next.js router and Link component.
+ * These types are not meant to be used directly.
+ * @internal
+ */
+declare namespace __next_route_internal_types__ {{
+  type SearchOrHash = `?${{string}}` | `#${{string}}`
+  type WithProtocol = `${{string}}:${{string}}`
+
+  type StaticRoutes ={static_routes}
+  type DynamicRoutes ={dynamic_routes}
+
+  type RouteImpl =
+    | StaticRoutes
+    | DynamicRoutes
+    | SearchOrHash
+    | WithProtocol
+    | `${{StaticRoutes | DynamicRoutes}}${{SearchOrHash}}`
+}}
+
+declare module 'next' {{
+  export {{ default }} from 'next/types'
+  export * from 'next/types'
+
+  export type Route = __next_route_internal_types__.RouteImpl
+}}
+
+declare module 'next/link' {{
+  import type {{ LinkProps as OriginalLinkProps }} from 'next/dist/client/link'
+  import type {{ AnchorHTMLAttributes, DetailedHTMLProps }} from 'react'
+  import type {{ UrlObject }} from 'url'
+
+  type LinkRestProps = Omit<
+    Omit<
+      DetailedHTMLProps<
+        AnchorHTMLAttributes<HTMLAnchorElement>,
+        HTMLAnchorElement
+      >,
+      keyof OriginalLinkProps
+    > &
+      OriginalLinkProps,
+    'href'
+  >
+
+  export type LinkProps = LinkRestProps & {{
+    /**
+     * The path or URL to navigate to. This is the only required prop. It can also be an object.
+     * @see https://nextjs.org/docs/api-reference/next/link
+     */
+    href: __next_route_internal_types__.RouteImpl | UrlObject
+  }}
+
+  export default function Link(props: LinkProps): JSX.Element
+}}
+"#,
+        static_routes = union(&static_routes),
+        dynamic_routes = union(&dynamic_routes),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{link_types, route_types};
+
+    #[test]
+    fn test_route_types() {
+        assert_eq!(route_types("/"), vec!["'/'"]);
+        assert_eq!(route_types("/about"), vec!["'/about'"]);
+        assert_eq!(route_types("/blog/[slug]"), vec!["`/blog/${string}`"]);
+        assert_eq!(route_types("/docs/[...path]"), vec!["`/docs/${string}`"]);
+        assert_eq!(
+            route_types("/shop/[[...path]]"),
+            vec!["`/shop/${string}`", "'/shop'"]
+        );
+        assert!(route_types("/_app").is_empty());
+        assert!(route_types("/api/users").is_empty());
+    }
+
+    #[test]
+    fn test_link_types() {
+        let types = link_types(["/", "/_app", "/blog", "/blog/[slug]", "/api/hello"]);
+        assert!(types.contains("type StaticRoutes =\n    | '/'\n    | '/blog'\n"));
+        assert!(types.contains("type DynamicRoutes =\n    | `/blog/${string}`\n"));
+        assert!(!types.contains("/api/hello"));
+        assert!(!types.contains(" \n"));
+
+        let types = link_types(["/"]);
+        assert!(types.contains("type DynamicRoutes = never\n"));
+    }
+}
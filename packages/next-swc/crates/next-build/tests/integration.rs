@@ -0,0 +1,177 @@
+#![cfg(test)]
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use dunce::canonicalize;
+use next_build::BuildOptions;
+
+/// Builds the project in `tests/integration/<name>` and returns its dist
+/// directory. The project is copied into the workspace first, so that `next`
+/// resolves the same way as for a real project.
+async fn build_fixture(name: &str) -> PathBuf {
+    try_build_fixture(name).await.expect("build failed")
+}
+
+/// Like [build_fixture], but returns the error of a failed build instead of
+/// panicking.
+async fn try_build_fixture(name: &str) -> Result<PathBuf> {
+    let package_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let tests_dir = package_root.join("tests");
+    let project_dir = tests_dir.join("temp").join(name);
+
+    // We don't care about errors when removing the previous temp directory.
+    // It can still exist if we crashed during a previous test run.
+    let _ = std::fs::remove_dir_all(&project_dir);
+    std::fs::create_dir_all(&project_dir).expect("failed to create temporary directory");
+    copy_recursive(&tests_dir.join("integration").join(name), &project_dir)
+        .expect("failed to copy test files to temporary directory");
+
+    let workspace_root = canonicalize(&package_root)
+        .unwrap()
+        .ancestors()
+        .nth(4)
+        .unwrap()
+        .to_path_buf();
+
+    next_build::build(BuildOptions {
+        root: Some(workspace_root),
+        dir: Some(project_dir.clone()),
+        memory_limit: None,
+        log_level: None,
+        show_all: false,
+        log_detail: false,
+        full_stats: false,
+        bundle_stats: false,
+        build_context: None,
+    })
+    .await?;
+
+    Ok(project_dir.join(".next"))
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    for entry in from.read_dir()? {
+        let path = entry?.path();
+        let to_path = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            std::fs::create_dir_all(&to_path)?;
+            copy_recursive(&path, &to_path)?;
+        } else {
+            std::fs::copy(&path, &to_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads all JavaScript files within `dir`.
+fn read_js_files(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut files = vec![];
+    for entry in dir.read_dir().unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(read_js_files(&path));
+        } else if path.extension().map_or(false, |ext| ext == "js") {
+            let content = std::fs::read_to_string(&path).unwrap();
+            files.push((path, content));
+        }
+    }
+    files
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_production_chunks_drop_development_branches() {
+    let dist_dir = build_fixture("node-env").await;
+
+    for (dir, name) in [("static/chunks", "page"), ("server/edge", "middleware")] {
+        let files = read_js_files(&dist_dir.join(dir));
+        assert!(
+            files
+                .iter()
+                .any(|(_, content)| content.contains(&format!("production-only {name}"))),
+            "no chunk in {dir} contains the {name}"
+        );
+        for (path, content) in files {
+            assert!(
+                !content.contains(&format!("development-only {name}")),
+                "{} contains development-only code",
+                path.display()
+            );
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_middleware_manifest() {
+    let dist_dir = build_fixture("middleware").await;
+
+    let manifest: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(dist_dir.join("server/middleware-manifest.json")).unwrap(),
+    )
+    .unwrap();
+    let middleware = &manifest["middleware"]["/"];
+
+    let files = middleware["files"].as_array().unwrap();
+    assert!(!files.is_empty());
+    for file in files {
+        let file = file.as_str().unwrap();
+        assert!(
+            file.starts_with("server/edge/"),
+            "{file} is not an edge chunk"
+        );
+        assert!(dist_dir.join(file).exists(), "{file} wasn't emitted");
+    }
+    assert_eq!(
+        middleware["matchers"][0]["originalSource"],
+        serde_json::json!("/api/:path*")
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_custom_server() {
+    let dist_dir = build_fixture("custom-server").await;
+
+    assert!(dist_dir.join("server/custom-server.js").exists());
+    let files = read_js_files(&dist_dir.join("server"));
+    assert!(
+        files
+            .iter()
+            .any(|(_, content)| content.contains("custom server")),
+        "server.ts wasn't compiled"
+    );
+    for (path, content) in files {
+        assert!(
+            !content.contains("port: number"),
+            "{} contains TypeScript",
+            path.display()
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_generate_build_id() {
+    let dist_dir = build_fixture("build-id").await;
+
+    assert_eq!(
+        std::fs::read_to_string(dist_dir.join("BUILD_ID")).unwrap(),
+        "custom-build-id"
+    );
+    assert!(
+        dist_dir
+            .join("static/custom-build-id/_buildManifest.js")
+            .exists(),
+        "_buildManifest.js wasn't emitted for the custom build id"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lint_errors_fail_the_build() {
+    let error = try_build_fixture("lint-error")
+        .await
+        .expect_err("the build succeeded despite a lint error");
+
+    assert!(
+        format!("{error:?}").contains("Lint errors occurred"),
+        "unexpected error: {error:?}"
+    );
+}
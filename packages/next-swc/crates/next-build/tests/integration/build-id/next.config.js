@@ -0,0 +1,3 @@
+module.exports = {
+  generateBuildId: async () => 'custom-build-id',
+}
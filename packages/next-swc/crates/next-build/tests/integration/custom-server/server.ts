@@ -0,0 +1,5 @@
+import pagesManifest from 'PAGES_MANIFEST'
+
+const port: number = parseInt(process.env.PORT ?? '3000', 10)
+
+console.log('custom server', port, Object.keys(pagesManifest))
@@ -0,0 +1,4 @@
+export default function Page() {
+  debugger
+  return null
+}
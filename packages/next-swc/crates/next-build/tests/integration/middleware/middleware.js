@@ -0,0 +1,9 @@
+import { NextResponse } from 'next/server'
+
+export function middleware() {
+  return NextResponse.next()
+}
+
+export const config = {
+  matcher: ['/api/:path*'],
+}
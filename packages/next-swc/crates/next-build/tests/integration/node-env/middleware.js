@@ -0,0 +1,11 @@
+import { NextResponse } from 'next/server'
+
+export function middleware() {
+  if (process.env.NODE_ENV !== 'production') {
+    console.log('development-only middleware')
+  }
+  if (process.env.NODE_ENV === 'production') {
+    console.log('production-only middleware')
+  }
+  return NextResponse.next()
+}
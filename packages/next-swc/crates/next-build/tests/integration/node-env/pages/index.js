@@ -0,0 +1,9 @@
+export default function Page() {
+  if (process.env.NODE_ENV !== 'production') {
+    console.log('development-only page')
+  }
+  if (process.env.NODE_ENV === 'production') {
+    console.log('production-only page')
+  }
+  return null
+}
@@ -14,10 +14,15 @@ import type { RenderData } from 'types/turbopack'
 
 import chunkGroup from 'INNER_EDGE_CHUNK_GROUP'
 import { attachRequestMeta } from '../internal/next-request-helpers'
+import { withRenderTimeout } from '../internal/render-timeout'
 import { Readable } from 'stream'
 
 startOperationStreamHandler(async (renderData: RenderData, respond) => {
-  const { response } = await runOperation(renderData)
+  const { response } = await withRenderTimeout(
+    runOperation(renderData),
+    renderData.data?.renderTimeout,
+    renderData.path
+  )
 
   if (response == null) {
     throw new Error('no html returned')
@@ -11,13 +11,18 @@ import type { RenderOpts } from 'next/dist/server/app-render/types'
 
 import { renderToHTMLOrFlight } from 'next/dist/server/app-render/app-render'
 import { RSC_VARY_HEADER } from 'next/dist/client/components/app-router-headers'
-import { headersFromEntries, initProxiedHeaders } from '../internal/headers'
+import {
+  headersFromEntries,
+  initProxiedHeaders,
+  limitLinkHeader,
+} from '../internal/headers'
 import { parse, ParsedUrlQuery } from 'node:querystring'
 import { PassThrough } from 'node:stream'
 ;('TURBOPACK { chunking-type: isolatedParallel }')
 import entry from 'APP_ENTRY'
 import BOOTSTRAP from 'APP_BOOTSTRAP'
 import { createServerResponse } from '../internal/http'
+import { withRenderTimeout } from '../internal/render-timeout'
 import { createManifests, installRequireAndChunkLoad } from './app/manifest'
 import { join } from 'node:path'
 import { nodeFs } from 'next/dist/server/lib/node-fs-methods'
@@ -30,7 +35,11 @@ process.env.__NEXT_NEW_LINK_BEHAVIOR = 'true'
 const MIME_TEXT_HTML_UTF8 = 'text/html; charset=utf-8'
 
 startOperationStreamHandler(async (renderData: RenderData, respond) => {
-  const result = await runOperation(renderData)
+  const result = await withRenderTimeout(
+    runOperation(renderData),
+    renderData.data?.renderTimeout,
+    renderData.path
+  )
 
   if (result == null) {
     throw new Error('no html returned')
@@ -112,7 +121,7 @@ async function runOperation(renderData: RenderData) {
         version: 4,
         routes: {},
         dynamicRoutes: {},
-        preview: {
+        preview: renderData.data?.previewProps ?? {
           previewModeEncryptionKey: '',
           previewModeId: '',
           previewModeSigningKey: '',
@@ -128,6 +137,7 @@ async function runOperation(renderData: RenderData) {
     pageConfig: {},
     reactLoadableManifest: {},
     nextConfigOutput: renderData.data?.nextConfigOutput,
+    previewProps: renderData.data?.previewProps,
   }
   const result = await renderToHTMLOrFlight(
     req,
@@ -145,12 +155,31 @@ async function runOperation(renderData: RenderData) {
   } else {
     body.write(result.toUnchunkedString())
   }
+  const headers: [string, string][] = [
+    ['Content-Type', result.contentType ?? MIME_TEXT_HTML_UTF8],
+    ['Vary', RSC_VARY_HEADER],
+  ]
+  const link = limitLinkHeader(
+    res.getHeader('Link'),
+    renderData.data?.reactMaxHeadersLength,
+    renderData.path
+  )
+  if (link) {
+    headers.push(['Link', link])
+  }
+  // `redirect()` during rendering responds with a 307 to its Location, along
+  // with the cookies that were set before.
+  const location = res.getHeader('Location')
+  if (typeof location === 'string') {
+    headers.push(['Location', location])
+  }
+  const setCookie = res.getHeader('Set-Cookie')
+  for (const cookie of Array.isArray(setCookie) ? setCookie : []) {
+    headers.push(['Set-Cookie', cookie])
+  }
   return {
     statusCode: res.statusCode,
-    headers: [
-      ['Content-Type', result.contentType ?? MIME_TEXT_HTML_UTF8],
-      ['Vary', RSC_VARY_HEADER],
-    ] as [string, string][],
+    headers,
     body,
   }
 }
@@ -9,6 +9,7 @@ import { linkGc } from 'next/dist/client/app-link-gc'
 import { HeadManagerContext } from 'next/dist/shared/lib/head-manager-context'
 
 import { initializeHMR } from '@vercel/turbopack-next/dev/client'
+import { withChunkLoadRetry } from '../../internal/chunk-load-retry'
 
 initializeHMR({
   assetPrefix: '',
@@ -23,7 +24,7 @@ globalThis.__next_require__ = (data) => {
   const [client_id] = JSON.parse(data)
   return __turbopack_require__(client_id)
 }
-globalThis.__next_chunk_load__ = __turbopack_load__
+globalThis.__next_chunk_load__ = withChunkLoadRetry(__turbopack_load__)
 
 process.env.__NEXT_NEW_LINK_BEHAVIOR = 'true'
 
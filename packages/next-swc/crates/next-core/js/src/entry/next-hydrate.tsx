@@ -9,19 +9,22 @@ import {
 import { formatWithValidation } from 'next/dist/shared/lib/router/utils/format-url'
 import { initializeHMR } from '../dev/client'
 import { subscribeToUpdate } from '@vercel/turbopack-ecmascript-runtime/dev/client/hmr-client'
+import { withChunkLoadRetry } from '../internal/chunk-load-retry'
+
+const loadChunk = withChunkLoadRetry(__turbopack_load__)
 
 async function loadPageChunk(assetPrefix: string, chunkData: ChunkData) {
   if (typeof chunkData === 'string') {
     const fullPath = assetPrefix + chunkData
 
-    await __turbopack_load__(fullPath)
+    await loadChunk(fullPath)
   } else {
     let fullChunkData = {
       ...chunkData,
       path: assetPrefix + chunkData.path,
     }
 
-    await __turbopack_load__(fullChunkData)
+    await loadChunk(fullChunkData)
   }
 }
 
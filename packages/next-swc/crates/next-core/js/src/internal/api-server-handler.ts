@@ -9,6 +9,7 @@ import { Buffer } from 'node:buffer'
 import type { RenderData } from 'types/turbopack'
 import { createServer, makeRequest } from '../internal/server'
 import { toPairs } from '../internal/headers'
+import { withRenderTimeout } from '../internal/render-timeout'
 
 type Handler = (data: {
   request: IncomingMessage
@@ -16,6 +17,7 @@ type Handler = (data: {
   query: string
   params: Record<string, string | string[]>
   path: string
+  previewProps: NonNullable<RenderData['data']>['previewProps']
 }) => Promise<void>
 
 type Operation = {
@@ -54,13 +56,18 @@ export default function startHandler(handler: Handler): void {
           clientRequest,
           server,
           clientResponsePromise,
-          apiOperation: handler({
-            request: serverRequest,
-            response: serverResponse,
-            query: renderData.rawQuery,
-            params: renderData.params,
-            path: renderData.path,
-          }),
+          apiOperation: withRenderTimeout(
+            handler({
+              request: serverRequest,
+              response: serverResponse,
+              query: renderData.rawQuery,
+              params: renderData.params,
+              path: renderData.path,
+              previewProps: renderData.data?.previewProps,
+            }),
+            renderData.data?.renderTimeout,
+            renderData.path
+          ),
         }
       })()
 
@@ -0,0 +1,57 @@
+// Set from `experimental.chunkLoadRetry` by the client runtime entry.
+const RETRIES = Number(process.env.__NEXT_CHUNK_LOAD_RETRIES ?? 0)
+const TIMEOUT = Number(process.env.__NEXT_CHUNK_LOAD_TIMEOUT ?? 0)
+
+// The delay before the first retry, doubled for every following one.
+const BACKOFF = 500
+
+function withTimeout<T>(promise: Promise<T>, timeout: number): Promise<T> {
+  if (timeout <= 0) {
+    return promise
+  }
+
+  return new Promise((resolve, reject) => {
+    const timer = setTimeout(
+      () => reject(new Error(`Loading chunk timed out after ${timeout}ms`)),
+      timeout
+    )
+    promise.then(
+      (value) => {
+        clearTimeout(timer)
+        resolve(value)
+      },
+      (err) => {
+        clearTimeout(timer)
+        reject(err)
+      }
+    )
+  })
+}
+
+/**
+ * Wraps a chunk loading function so that a failed or timed out load is
+ * retried with an exponential backoff, as configured by
+ * `experimental.chunkLoadRetry`. Without that config, chunks are loaded once.
+ */
+export function withChunkLoadRetry<A extends unknown[], T>(
+  load: (...args: A) => Promise<T>
+): (...args: A) => Promise<T> {
+  if (RETRIES <= 0 && TIMEOUT <= 0) {
+    return load
+  }
+
+  return async (...args) => {
+    for (let attempt = 0; ; attempt++) {
+      try {
+        return await withTimeout(load(...args), TIMEOUT)
+      } catch (err) {
+        if (attempt >= RETRIES) {
+          throw err
+        }
+        await new Promise((resolve) =>
+          setTimeout(resolve, BACKOFF * 2 ** attempt)
+        )
+      }
+    }
+  }
+}
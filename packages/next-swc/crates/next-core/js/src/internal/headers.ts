@@ -59,3 +59,67 @@ export function initProxiedHeaders(
   headers['x-forwarded-proto'] = proxiedFor?.protocol || 'http'
   return headers
 }
+
+/**
+ * Splits a `Link` header into its links, e.g.
+ * `</a.css>; rel=preload; as=style, </b.js>; rel=preload; as=script`.
+ */
+export function splitLinkHeader(header: string): string[] {
+  const links: string[] = []
+  let start = 0
+  let inUrl = false
+  let inQuotes = false
+  for (let i = 0; i < header.length; i++) {
+    const char = header[i]
+    if (inQuotes) {
+      inQuotes = char !== '"'
+    } else if (inUrl) {
+      inUrl = char !== '>'
+    } else if (char === '"') {
+      inQuotes = true
+    } else if (char === '<') {
+      inUrl = true
+    } else if (char === ',') {
+      links.push(header.slice(start, i).trim())
+      start = i + 1
+    }
+  }
+  links.push(header.slice(start).trim())
+  return links.filter(Boolean)
+}
+
+/**
+ * Limits the `Link` header set during a render to `maxLength` characters, as
+ * proxies reject responses with large headers. The links are in order of
+ * priority, so the last ones are dropped first, with a warning.
+ */
+export function limitLinkHeader(
+  value: number | string | ReadonlyArray<string> | undefined,
+  maxLength: number | undefined,
+  path: string
+): string | undefined {
+  if (value == null) {
+    return undefined
+  }
+
+  const values: ReadonlyArray<string> =
+    typeof value === 'object' ? value : [String(value)]
+  const links = values.flatMap(splitLinkHeader)
+  const dropped: string[] = []
+  while (
+    maxLength != null &&
+    links.length > 0 &&
+    links.join(', ').length > maxLength
+  ) {
+    dropped.unshift(links.pop()!)
+  }
+
+  if (dropped.length > 0) {
+    const list = dropped.map((link) => `  ${link}`).join('\n')
+    console.warn(
+      `Warning: the Link header of "${path}" exceeds \`reactMaxHeadersLength\` (${maxLength}), the following links were dropped:\n${list}`
+    )
+  }
+
+  return links.length > 0 ? links.join(', ') : undefined
+}
@@ -19,7 +19,7 @@ import { attachRequestMeta } from './next-request-helpers'
 import type { RouteModule } from 'next/dist/server/future/route-modules/route-module'
 
 export default (routeModule: RouteModule) => {
-  startHandler(async ({ request, response, params }) => {
+  startHandler(async ({ request, response, params, previewProps }) => {
     const req = new NodeNextRequest(request)
     const res = new NodeNextResponse(response)
 
@@ -33,9 +33,11 @@ export default (routeModule: RouteModule) => {
         routes: {},
         dynamicRoutes: {},
         notFoundRoutes: [],
-        preview: {
-          previewModeId: 'development-id',
-        } as any,
+        preview:
+          previewProps ??
+          ({
+            previewModeId: 'development-id',
+          } as any),
       },
       staticGenerationContext: {
         supportsDynamicHTML: true,
@@ -11,11 +11,17 @@ import { renderToHTML, RenderOpts } from 'next/dist/server/render'
 import { getRedirectStatus } from 'next/dist/lib/redirect-status'
 import { PERMANENT_REDIRECT_STATUS } from 'next/dist/shared/lib/constants'
 import { buildStaticPaths } from 'next/dist/build/utils'
+import { setConfig } from 'next/dist/shared/lib/runtime-config'
 import type { BuildManifest } from 'next/dist/server/get-page-files'
 import type { ReactLoadableManifest } from 'next/dist/server/load-components'
 
-import { headersFromEntries, initProxiedHeaders } from './headers'
+import {
+  headersFromEntries,
+  initProxiedHeaders,
+  limitLinkHeader,
+} from './headers'
 import { createServerResponse } from './http'
+import { withRenderTimeout } from './render-timeout'
 import type { Ipc } from '@vercel/turbopack-node/ipc/index'
 import type { RenderData } from 'types/turbopack'
 import type { ChunkGroup } from 'types/next'
@@ -75,9 +81,20 @@ export default function startHandler({
         }
       }
 
+      // Pages can call `getConfig()` from `next/config` at module scope, so the
+      // runtime config has to be set before the page module is loaded.
+      setConfig({
+        serverRuntimeConfig: renderData.data?.serverRuntimeConfig ?? {},
+        publicRuntimeConfig: renderData.data?.publicRuntimeConfig ?? {},
+      })
+
       const { Component, namespace } = await mod()
 
-      const res = await runOperation(renderData, Component, namespace)
+      const res = await withRenderTimeout(
+        runOperation(renderData, Component, namespace),
+        renderData.data?.renderTimeout,
+        renderData.path
+      )
 
       ipc.send(res)
     }
@@ -169,10 +186,10 @@ export default function startHandler({
 
       /* RenderOptsPartial */
       isDataReq,
-      runtimeConfig: {},
+      runtimeConfig: renderData.data?.publicRuntimeConfig ?? {},
       assetPrefix: '',
       canonicalBase: '',
-      previewProps: {
+      previewProps: renderData.data?.previewProps ?? {
         previewModeId: '',
         previewModeEncryptionKey: '',
         previewModeSigningKey: '',
@@ -182,6 +199,7 @@ export default function startHandler({
       // TODO(WEB-583) this isn't correct, instead it should set `dev: true`
       nextExport: true,
       nextConfigOutput: renderData.data?.nextConfigOutput,
+      largePageDataBytes: renderData.data?.largePageDataBytes,
       resolvedUrl: renderData.url,
       optimizeFonts: false,
       optimizeCss: false,
@@ -314,12 +332,22 @@ export default function startHandler({
     // TODO: handle revalidate
     // const sprRevalidate = renderResult.metadata.revalidate;
 
+    const headers: Array<[string, string]> = [
+      ['Content-Type', renderResult.contentType ?? MIME_TEXT_HTML_UTF8],
+    ]
+    const link = limitLinkHeader(
+      res.getHeader('Link'),
+      renderData.data?.reactMaxHeadersLength,
+      renderData.path
+    )
+    if (link) {
+      headers.push(['Link', link])
+    }
+
     return {
       type: 'response',
       statusCode: res.statusCode,
-      headers: [
-        ['Content-Type', renderResult.contentType ?? MIME_TEXT_HTML_UTF8],
-      ],
+      headers,
       body,
     }
   }
@@ -0,0 +1,33 @@
+/**
+ * Rejects when `promise` doesn't settle within `timeout` milliseconds, e.g.
+ * because a render waits for a fetch that never resolves.
+ *
+ * The rejection is reported to Turbopack with `ipc.sendError`, which responds
+ * with a 500 and exits this worker, so that the pending render doesn't keep
+ * it busy. A synchronous infinite loop blocks the timer as well and can't be
+ * interrupted from within the worker.
+ */
+export function withRenderTimeout<T>(
+  promise: Promise<T>,
+  timeout: number | undefined,
+  path: string
+): Promise<T> {
+  if (!timeout) {
+    return promise
+  }
+
+  let timer: NodeJS.Timeout | undefined
+  const timeoutPromise = new Promise<never>((_, reject) => {
+    timer = setTimeout(() => {
+      reject(
+        new Error(
+          `Rendering "${path}" timed out after ${timeout}ms. Check for requests that never resolve, or increase \`experimental.renderTimeout\` in next.config.js.`
+        )
+      )
+    }, timeout)
+  })
+
+  return Promise.race([promise, timeoutPromise]).finally(() => {
+    clearTimeout(timer)
+  })
+}
@@ -15,5 +15,15 @@ export type RenderData = {
     allowedRevalidateHeaderKeys?: string[]
     fetchCacheKeyPrefix?: string
     isrMemoryCacheSize?: number
+    previewProps?: {
+      previewModeId: string
+      previewModeSigningKey: string
+      previewModeEncryptionKey: string
+    }
+    publicRuntimeConfig?: Record<string, any>
+    serverRuntimeConfig?: Record<string, any>
+    largePageDataBytes?: number
+    renderTimeout?: number
+    reactMaxHeadersLength?: number
   }
 }
@@ -0,0 +1,124 @@
+//! Validation of the error boundaries of the app directory, `error.tsx` and
+//! `global-error.tsx`. React only calls error boundaries on the client, so
+//! they need to be client components.
+
+use anyhow::Result;
+use turbo_tasks::{primitives::StringVc, CompletionVc};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileContent, FileSystemPathVc},
+    turbopack::core::issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+};
+
+/// Emits a [ServerErrorBoundaryIssue] when the error boundary at `path` has
+/// no `"use client"` directive.
+#[turbo_tasks::function]
+pub async fn validate_error_boundary(path: FileSystemPathVc) -> Result<CompletionVc> {
+    let FileContent::Content(file) = &*path.read().await? else {
+        return Ok(CompletionVc::new());
+    };
+    if !directives(&file.content().to_str()?).contains(&"use client") {
+        ServerErrorBoundaryIssue { path }.cell().as_issue().emit();
+    }
+    Ok(CompletionVc::new())
+}
+
+/// Returns the directives at the start of a module, like `"use client"`,
+/// skipping comments and a hashbang.
+fn directives(source: &str) -> Vec<&str> {
+    let mut directives = Vec::new();
+    let mut rest = source.trim_start_matches('\u{feff}');
+    if rest.starts_with("#!") {
+        rest = rest.split_once('\n').map_or("", |(_, rest)| rest);
+    }
+    loop {
+        rest = rest.trim_start();
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, rest)| rest);
+        } else if let Some(quote @ ('"' | '\'')) = rest.chars().next() {
+            let Some((directive, after)) = rest[1..].split_once(quote) else {
+                break;
+            };
+            let after = after.trim_start_matches([' ', '\t']);
+            // A string that is followed by more of an expression, like
+            // `'use client'.length`, is not a directive.
+            if !(after.is_empty() || after.starts_with([';', '\n', '\r'])) {
+                break;
+            }
+            directives.push(directive);
+            rest = after.strip_prefix(';').unwrap_or(after);
+        } else {
+            break;
+        }
+    }
+    directives
+}
+
+/// An `error.tsx` or `global-error.tsx` that is not a client component.
+#[turbo_tasks::value(shared)]
+pub struct ServerErrorBoundaryIssue {
+    path: FileSystemPathVc,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ServerErrorBoundaryIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("Error boundaries must be Client Components".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("next app".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(
+            "Errors are only caught by an error boundary that is a Client Component. Add the \
+             \"use client\" directive at the top of the file so that it can catch errors and \
+             receive the `reset` function."
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> StringVc {
+        StringVc::cell(
+            "https://nextjs.org/docs/app/api-reference/file-conventions/error".to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::directives;
+
+    #[test]
+    fn test_directives() {
+        assert_eq!(
+            directives("'use client'\n\nexport default 1"),
+            vec!["use client"]
+        );
+        assert_eq!(
+            directives(
+                "#!/usr/bin/env node\n// comment\n/* a\n b */ \"use strict\";\"use client\""
+            ),
+            vec!["use strict", "use client"]
+        );
+        assert!(directives("import 'x'\n'use client'").is_empty());
+        assert!(directives("'use client'.length").is_empty());
+        assert!(directives("export default function Error() {}").is_empty());
+    }
+}
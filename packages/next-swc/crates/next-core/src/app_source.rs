@@ -24,7 +24,7 @@ use turbopack_binding::{
                 EcmaScriptModulesReferenceSubType, EntryReferenceSubType, InnerAssetsVc,
                 ReferenceType,
             },
-            source::SourcesVc,
+            source::{SourceVc, SourcesVc},
             virtual_source::VirtualSourceVc,
         },
         dev::DevChunkingContextVc,
@@ -61,6 +61,7 @@ use turbopack_binding::{
 };
 
 use crate::{
+    app_error_boundary::validate_error_boundary,
     app_render::next_server_component_transition::NextServerComponentTransition,
     app_segment_config::{parse_segment_config_from_loader_tree, parse_segment_config_from_source},
     app_structure::{
@@ -68,6 +69,8 @@ use crate::{
         LoaderTreeVc, Metadata, MetadataItem, MetadataWithAltItem, OptionAppDirVc,
     },
     bootstrap::{route_bootstrap, BootstrapConfigVc},
+    cache_query_params_source::CacheQueryParamsContentSourceVc,
+    conditional_source::ConditionalContentSourceVc,
     embed_js::{next_asset, next_js_file_path},
     env::env_for_js,
     fallback::get_fallback_page,
@@ -87,7 +90,9 @@ use crate::{
     },
     next_config::NextConfigVc,
     next_edge::{
-        context::{get_edge_compile_time_info, get_edge_resolve_options_context},
+        context::{
+            get_edge_compile_time_info, get_edge_resolve_options_context, get_edge_runtime_entries,
+        },
         page_transition::NextEdgePageTransition,
         route_transition::NextEdgeRouteTransition,
     },
@@ -97,6 +102,7 @@ use crate::{
         get_server_compile_time_info, get_server_module_options_context,
         get_server_resolve_options_context, ServerContextType,
     },
+    tenant_source::TenantHeaderContentSourceVc,
     util::{render_data, NextRuntime},
 };
 
@@ -130,6 +136,18 @@ fn pathname_to_segments(pathname: &str) -> Result<(Vec<BaseSegment>, RouteType)>
     Ok((segments, RouteType::Exact))
 }
 
+/// The `type` of an icon link for the extension of an icon file, see
+/// `STATIC_LOCAL_METADATA` in `app_structure`.
+fn image_mime_type(extension: &str) -> Option<&'static str> {
+    Some(match extension {
+        "ico" => "image/x-icon",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        _ => return None,
+    })
+}
+
 #[turbo_tasks::function]
 async fn next_client_transition(
     project_path: FileSystemPathVc,
@@ -243,9 +261,9 @@ fn next_edge_server_component_transition(
 ) -> TransitionVc {
     let ty = Value::new(ServerContextType::AppRSC { app_dir });
     let mode = NextMode::Development;
-    let rsc_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let rsc_compile_time_info = get_edge_compile_time_info(project_path, mode, server_addr);
     let rsc_resolve_options_context =
-        get_edge_resolve_options_context(project_path, ty, next_config, execution_context);
+        get_edge_resolve_options_context(project_path, ty, mode, next_config, execution_context);
     let rsc_module_options_context =
         get_server_module_options_context(project_path, execution_context, ty, mode, next_config);
 
@@ -264,14 +282,16 @@ fn next_edge_route_transition(
     project_path: FileSystemPathVc,
     app_dir: FileSystemPathVc,
     server_root: FileSystemPathVc,
+    env: ProcessEnvVc,
     next_config: NextConfigVc,
     server_addr: ServerAddrVc,
     output_path: FileSystemPathVc,
     execution_context: ExecutionContextVc,
 ) -> TransitionVc {
+    let mode = NextMode::Development;
     let server_ty = Value::new(ServerContextType::AppRoute { app_dir });
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info = get_edge_compile_time_info(project_path, mode, server_addr);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -282,14 +302,20 @@ fn next_edge_route_transition(
     )
     .reference_chunk_source_maps(should_debug("app_source"))
     .build();
-    let edge_resolve_options_context =
-        get_edge_resolve_options_context(project_path, server_ty, next_config, execution_context);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_path,
+        server_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
 
     NextEdgeRouteTransition {
         edge_compile_time_info,
         edge_chunking_context,
         edge_module_options_context: None,
         edge_resolve_options_context,
+        runtime_entries: get_edge_runtime_entries(project_path, env, next_config),
         output_path,
         base_path: app_dir,
         bootstrap_asset: next_asset("entry/app/edge-route-bootstrap.ts"),
@@ -304,14 +330,16 @@ fn next_edge_page_transition(
     project_path: FileSystemPathVc,
     app_dir: FileSystemPathVc,
     server_root: FileSystemPathVc,
+    env: ProcessEnvVc,
     next_config: NextConfigVc,
     server_addr: ServerAddrVc,
     output_path: FileSystemPathVc,
     execution_context: ExecutionContextVc,
 ) -> TransitionVc {
+    let mode = NextMode::Development;
     let server_ty = Value::new(ServerContextType::AppRoute { app_dir });
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info = get_edge_compile_time_info(project_path, mode, server_addr);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -323,14 +351,20 @@ fn next_edge_page_transition(
     .layer("ssr")
     .reference_chunk_source_maps(should_debug("app_source"))
     .build();
-    let edge_resolve_options_context =
-        get_edge_resolve_options_context(project_path, server_ty, next_config, execution_context);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_path,
+        server_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
 
     NextEdgePageTransition {
         edge_compile_time_info,
         edge_chunking_context,
         edge_module_options_context: None,
         edge_resolve_options_context,
+        runtime_entries: get_edge_runtime_entries(project_path, env, next_config),
         output_path,
         bootstrap_asset: next_asset("entry/app/edge-page-bootstrap.ts"),
     }
@@ -362,6 +396,7 @@ fn app_context(
             project_path,
             app_dir,
             server_root,
+            env,
             next_config,
             server_addr,
             output_path,
@@ -374,6 +409,7 @@ fn app_context(
             project_path,
             app_dir,
             server_root,
+            env,
             next_config,
             server_addr,
             output_path,
@@ -530,7 +566,7 @@ pub async fn create_app_source(
         client_compile_time_info,
         next_config,
     );
-    let render_data = render_data(next_config, server_addr);
+    let render_data = render_data(next_config, env, server_addr);
 
     let entrypoints = entrypoints.await?;
     let mut sources: Vec<_> = entrypoints
@@ -549,10 +585,11 @@ pub async fn create_app_source(
                 fallback_page,
                 output_path,
                 render_data,
+                next_config,
             ),
             Entrypoint::AppRoute { path } => create_app_route_source_for_route(
                 pathname,
-                path,
+                FileSourceVc::new(path).into(),
                 context_ssr,
                 project_path,
                 app_dir,
@@ -570,6 +607,27 @@ pub async fn create_app_source(
         )))
         .collect();
 
+    if let Some(MetadataItem::Dynamic { path }) = metadata.await?.manifest {
+        sources.push(create_app_route_source_for_route(
+            "/manifest.webmanifest",
+            metadata_route_entry(
+                app_dir,
+                path,
+                "manifest.webmanifest",
+                "manifest",
+                "application/manifest+json",
+            ),
+            context_ssr,
+            project_path,
+            app_dir,
+            env,
+            server_root,
+            server_runtime_entries,
+            output_path,
+            render_data,
+        ));
+    }
+
     if let Some(&Entrypoint::AppPage { loader_tree }) = entrypoints.get("/") {
         if loader_tree.await?.components.await?.not_found.is_some() {
             // Only add a source for the app 404 page if a top-level not-found page is
@@ -586,6 +644,7 @@ pub async fn create_app_source(
                 fallback_page,
                 output_path,
                 render_data,
+                next_config,
             );
             sources.push(not_found_page_source);
         }
@@ -651,6 +710,7 @@ async fn create_app_page_source_for_route(
     fallback_page: DevHtmlAssetVc,
     intermediate_output_path_root: FileSystemPathVc,
     render_data: JsonValueVc,
+    next_config: NextConfigVc,
 ) -> Result<ContentSourceVc> {
     let pathname_vc = StringVc::cell(pathname.to_string());
 
@@ -661,8 +721,8 @@ async fn create_app_page_source_for_route(
     let source = create_node_rendered_source(
         project_path,
         env,
-        base_segments,
-        route_type,
+        base_segments.clone(),
+        route_type.clone(),
         server_root,
         params_matcher.into(),
         pathname_vc,
@@ -675,6 +735,7 @@ async fn create_app_page_source_for_route(
             project_path,
             intermediate_output_path: intermediate_output_path_root,
             loader_tree,
+            next_config,
         }
         .cell()
         .into(),
@@ -682,6 +743,21 @@ async fn create_app_page_source_for_route(
         render_data,
         should_debug("app_source"),
     );
+    let source = TenantHeaderContentSourceVc::new(
+        source,
+        base_segments.clone(),
+        route_type.clone(),
+        next_config,
+    );
+    let source = CacheQueryParamsContentSourceVc::new(
+        source.into(),
+        base_segments.clone(),
+        route_type.clone(),
+        next_config,
+    );
+    let source: ContentSourceVc =
+        ConditionalContentSourceVc::new(source.into(), base_segments, route_type, next_config)
+            .into();
 
     Ok(source.issue_context(app_dir, &format!("Next.js App Page Route {pathname}")))
 }
@@ -700,6 +776,7 @@ async fn create_app_not_found_page_source(
     fallback_page: DevHtmlAssetVc,
     intermediate_output_path_root: FileSystemPathVc,
     render_data: JsonValueVc,
+    next_config: NextConfigVc,
 ) -> Result<ContentSourceVc> {
     let pathname_vc = StringVc::cell("/404".to_string());
 
@@ -720,6 +797,7 @@ async fn create_app_not_found_page_source(
             project_path,
             intermediate_output_path: intermediate_output_path_root,
             loader_tree,
+            next_config,
         }
         .cell()
         .into(),
@@ -727,15 +805,59 @@ async fn create_app_not_found_page_source(
         render_data,
         should_debug("app_source"),
     );
+    let source: ContentSourceVc =
+        CacheQueryParamsContentSourceVc::new(source, Vec::new(), RouteType::NotFound, next_config)
+            .into();
 
     Ok(source.issue_context(app_dir, "Next.js App Page Route /404"))
 }
 
+/// Wraps a dynamic metadata file in the root of the app directory, like
+/// `manifest.ts`, in a route handler that responds with the serialized data
+/// returned by its default export, like `next-metadata-route-loader` does.
+#[turbo_tasks::function]
+async fn metadata_route_entry(
+    app_dir: FileSystemPathVc,
+    path: FileSystemPathVc,
+    server_path: &str,
+    file_type: &str,
+    content_type: &str,
+) -> Result<SourceVc> {
+    let path = path.await?;
+    let code = format!(
+        indoc! {r#"
+            import {{ NextResponse }} from "next/server";
+            import {{ resolveRouteData }} from "next/dist/build/webpack/loaders/metadata/resolve-route-data";
+            import handler from {import};
+
+            export async function GET() {{
+              const data = await handler();
+              const content = resolveRouteData(data, {file_type});
+
+              return new NextResponse(content, {{
+                headers: {{
+                  "Content-Type": {content_type},
+                  "Cache-Control": "public, max-age=0, must-revalidate",
+                }},
+              }});
+            }}
+        "#},
+        import = StringifyJs(&format!("../{}", path.file_name())),
+        file_type = StringifyJs(file_type),
+        content_type = StringifyJs(content_type),
+    );
+    Ok(VirtualSourceVc::new(
+        app_dir.join(&format!("{server_path}/route.ts")),
+        File::from(code).into(),
+    )
+    .into())
+}
+
 #[allow(clippy::too_many_arguments)]
 #[turbo_tasks::function]
 async fn create_app_route_source_for_route(
     pathname: &str,
-    entry_path: FileSystemPathVc,
+    entry: SourceVc,
     context_ssr: ModuleAssetContextVc,
     project_path: FileSystemPathVc,
     app_dir: FileSystemPathVc,
@@ -763,7 +885,7 @@ async fn create_app_route_source_for_route(
             context: context_ssr,
             runtime_entries,
             server_root,
-            entry_path,
+            entry,
             project_path,
             intermediate_output_path: intermediate_output_path_root,
             output_root: intermediate_output_path_root,
@@ -789,6 +911,7 @@ struct AppRenderer {
     server_root: FileSystemPathVc,
     intermediate_output_path: FileSystemPathVc,
     loader_tree: LoaderTreeVc,
+    next_config: NextConfigVc,
 }
 
 #[turbo_tasks::value_impl]
@@ -804,6 +927,7 @@ impl AppRendererVc {
             server_root,
             intermediate_output_path,
             loader_tree,
+            next_config,
         } = *self.await?;
 
         let (context, intermediate_output_path) = if is_rsc {
@@ -826,8 +950,13 @@ impl AppRendererVc {
             imports: Vec<String>,
             loader_tree_code: String,
             context: ModuleAssetContextVc,
+            app_dir: FileSystemPathVc,
             unsupported_metadata: Vec<FileSystemPathVc>,
             rsc_transition: &'static str,
+            max_route_depth: u32,
+            segments: Vec<String>,
+            /// The segments of the first loader tree that was too deep.
+            too_deep: Option<String>,
         }
 
         impl State {
@@ -844,8 +973,12 @@ impl AppRendererVc {
             imports: Vec::new(),
             loader_tree_code: String::new(),
             context,
+            app_dir,
             unsupported_metadata: Vec::new(),
             rsc_transition,
+            max_route_depth: *next_config.max_route_depth().await?,
+            segments: Vec::new(),
+            too_deep: None,
         };
 
         fn write_component(
@@ -888,7 +1021,7 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
             Ok(())
         }
 
-        fn write_metadata(state: &mut State, metadata: &Metadata) -> Result<()> {
+        async fn write_metadata(state: &mut State, metadata: &Metadata) -> Result<()> {
             if metadata.is_empty() {
                 return Ok(());
             }
@@ -901,16 +1034,16 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
                 manifest,
             } = metadata;
             state.loader_tree_code += "  metadata: {";
-            write_metadata_items(state, "icon", favicon.iter().chain(icon.iter()))?;
-            write_metadata_items(state, "apple", apple.iter())?;
-            write_metadata_items(state, "twitter", twitter.iter())?;
-            write_metadata_items(state, "openGraph", open_graph.iter())?;
-            write_metadata_manifest(state, *manifest)?;
+            write_metadata_items(state, "icon", favicon.iter().chain(icon.iter())).await?;
+            write_metadata_items(state, "apple", apple.iter()).await?;
+            write_metadata_items(state, "twitter", twitter.iter()).await?;
+            write_metadata_items(state, "openGraph", open_graph.iter()).await?;
+            write_metadata_manifest(state, *manifest).await?;
             state.loader_tree_code += "  },";
             Ok(())
         }
 
-        fn write_metadata_manifest(
+        async fn write_metadata_manifest(
             state: &mut State,
             manifest: Option<MetadataItem>,
         ) -> Result<()> {
@@ -937,32 +1070,42 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
                     writeln!(state.loader_tree_code, "    manifest: {identifier},")?;
                 }
                 MetadataItem::Dynamic { path } => {
-                    state.unsupported_metadata.push(path);
+                    use std::fmt::Write;
+                    // Only a manifest in the root of the app directory is served, at
+                    // `/manifest.webmanifest`.
+                    if *path.parent().await? == *state.app_dir.await? {
+                        writeln!(
+                            state.loader_tree_code,
+                            "    manifest: \"/manifest.webmanifest\","
+                        )?;
+                    } else {
+                        state.unsupported_metadata.push(path);
+                    }
                 }
             }
 
             Ok(())
         }
 
-        fn write_metadata_items<'a>(
+        async fn write_metadata_items<'a>(
             state: &mut State,
             name: &str,
             it: impl Iterator<Item = &'a MetadataWithAltItem>,
         ) -> Result<()> {
             use std::fmt::Write;
-            let mut it = it.peekable();
-            if it.peek().is_none() {
+            let items = it.collect::<Vec<_>>();
+            if items.is_empty() {
                 return Ok(());
             }
             writeln!(state.loader_tree_code, "    {name}: [")?;
-            for item in it {
-                write_metadata_item(state, name, item)?;
+            for item in items {
+                write_metadata_item(state, name, item).await?;
             }
             writeln!(state.loader_tree_code, "    ],")?;
             Ok(())
         }
 
-        fn write_metadata_item(
+        async fn write_metadata_item(
             state: &mut State,
             name: &str,
             item: &MetadataWithAltItem,
@@ -997,6 +1140,13 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
                             state.loader_tree_code,
                             "{s}  sizes: `${{{identifier}.width}}x${{{identifier}.height}}`,"
                         )?;
+                        if let Some(mime_type) = image_mime_type(&path.extension().await?) {
+                            writeln!(
+                                state.loader_tree_code,
+                                "{s}  type: {},",
+                                StringifyJs(mime_type)
+                            )?;
+                        }
                     }
                     if let Some(alt_path) = alt_path {
                         let identifier = magic_identifier::mangle(&format!("{name} alt text #{i}"));
@@ -1038,6 +1188,22 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
                 components,
             } = &*loader_tree.await?;
 
+            state.segments.push(segment.clone());
+            // Stop at the maximum depth and render an empty segment instead, so
+            // that a malformed app directory can't recurse without bounds.
+            if state.segments.len() > state.max_route_depth as usize {
+                if state.too_deep.is_none() {
+                    state.too_deep = Some(state.segments.join("/"));
+                }
+                write!(
+                    state.loader_tree_code,
+                    "[{segment}, {{}}, {{}}]",
+                    segment = StringifyJs(segment)
+                )?;
+                state.segments.pop();
+                return Ok(());
+            }
+
             writeln!(
                 state.loader_tree_code,
                 "[{segment}, {{",
@@ -1061,7 +1227,11 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
                 not_found,
                 metadata,
                 route: _,
+                global_error: _,
             } = &*components.await?;
+            if let Some(error) = *error {
+                validate_error_boundary(error).await?;
+            }
             write_component(state, "page", *page)?;
             write_component(state, "defaultPage", *default)?;
             write_component(state, "error", *error)?;
@@ -1069,21 +1239,64 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
             write_component(state, "loading", *loading)?;
             write_component(state, "template", *template)?;
             write_component(state, "not-found", *not_found)?;
-            write_metadata(state, metadata)?;
+            write_metadata(state, metadata).await?;
             write!(state.loader_tree_code, "}}]")?;
+            state.segments.pop();
             Ok(())
         }
 
         walk_tree(&mut state, loader_tree).await?;
 
+        if let Some(global_error) = loader_tree.await?.components.await?.global_error {
+            validate_error_boundary(global_error).await?;
+            state
+                .imports
+                .push("import GlobalError from \"GLOBAL_ERROR\";".to_string());
+            state.inner_assets.insert(
+                "GLOBAL_ERROR".to_string(),
+                state
+                    .context
+                    .with_transition(state.rsc_transition)
+                    .process(
+                        FileSourceVc::new(global_error).into(),
+                        Value::new(ReferenceType::EcmaScriptModules(
+                            EcmaScriptModulesReferenceSubType::Undefined,
+                        )),
+                    )
+                    .into(),
+            );
+        } else {
+            state.imports.push(
+                indoc! {"
+                    \"TURBOPACK { chunking-type: isolatedParallel; transition: next-edge-server-component }\";
+                    import GlobalErrorMod from \"next/dist/client/components/error-boundary\"
+                    const { GlobalError } = GlobalErrorMod;
+                "}
+                .to_string(),
+            );
+        }
+
         let State {
             inner_assets,
             imports,
             loader_tree_code,
             unsupported_metadata,
+            max_route_depth,
+            too_deep,
             ..
         } = state;
 
+        if let Some(segments) = too_deep {
+            RouteTooDeepIssue {
+                app_dir,
+                segments,
+                max_route_depth,
+            }
+            .cell()
+            .as_issue()
+            .emit();
+        }
+
         if !unsupported_metadata.is_empty() {
             UnsupportedDynamicMetadataIssue {
                 app_dir,
@@ -1095,9 +1308,6 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
         }
 
         let mut result = RopeBuilder::from(indoc! {"
-                \"TURBOPACK { chunking-type: isolatedParallel; transition: next-edge-server-component }\";
-                import GlobalErrorMod from \"next/dist/client/components/error-boundary\"
-                const { GlobalError } = GlobalErrorMod;
                 \"TURBOPACK { chunking-type: isolatedParallel; transition: next-edge-server-component }\";
                 import base from \"next/dist/server/app-render/entry-base\"\n
             "});
@@ -1189,7 +1399,9 @@ impl NodeEntry for AppRenderer {
         } else {
             false
         };
-        // Call with only is_rsc as key
+        // Call with only is_rsc as key. The remaining request headers (including
+        // cookies) are passed to the renderer as render data, where they back
+        // `headers()` and `cookies()` from `next/headers`.
         self_vc.entry(is_rsc)
     }
 }
@@ -1199,7 +1411,7 @@ impl NodeEntry for AppRenderer {
 struct AppRoute {
     runtime_entries: SourcesVc,
     context: ModuleAssetContextVc,
-    entry_path: FileSystemPathVc,
+    entry: SourceVc,
     intermediate_output_path: FileSystemPathVc,
     project_path: FileSystemPathVc,
     server_root: FileSystemPathVc,
@@ -1224,9 +1436,8 @@ impl AppRouteVc {
         .reference_chunk_source_maps(should_debug("app_source"))
         .build();
 
-        let entry_file_source = FileSourceVc::new(this.entry_path);
         let entry_asset = this.context.process(
-            entry_file_source.into(),
+            this.entry,
             Value::new(ReferenceType::Entry(EntryReferenceSubType::AppRoute)),
         );
 
@@ -1247,7 +1458,7 @@ impl AppRouteVc {
                 let internal_asset = next_asset("entry/app/edge-route.ts");
 
                 let entry = this.context.with_transition("next-edge-route").process(
-                    entry_file_source.into(),
+                    this.entry,
                     Value::new(ReferenceType::Entry(EntryReferenceSubType::AppRoute)),
                 );
 
@@ -1293,6 +1504,48 @@ impl NodeEntry for AppRoute {
     }
 }
 
+/// A route whose loader tree is nested deeper than
+/// `experimental.maxRouteDepth`.
+#[turbo_tasks::value(shared)]
+struct RouteTooDeepIssue {
+    app_dir: FileSystemPathVc,
+    segments: String,
+    max_route_depth: u32,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for RouteTooDeepIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("next app".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.app_dir
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("Route is nested too deeply".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(format!(
+            "The segments and parallel routes of this route are nested deeper than {} levels at \
+             {}. The deeper segments are not rendered. Increase `experimental.maxRouteDepth` if \
+             this nesting is intended.",
+            self.max_route_depth, self.segments
+        ))
+    }
+}
+
 #[turbo_tasks::value]
 struct UnsupportedDynamicMetadataIssue {
     app_dir: FileSystemPathVc,
@@ -41,6 +41,9 @@ pub struct Components {
     pub default: Option<FileSystemPathVc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub route: Option<FileSystemPathVc>,
+    /// Only used in the root of the app directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_error: Option<FileSystemPathVc>,
     #[serde(skip_serializing_if = "Metadata::is_empty")]
     pub metadata: Metadata,
 }
@@ -56,6 +59,7 @@ impl Components {
             not_found: self.not_found,
             default: None,
             route: None,
+            global_error: self.global_error,
             metadata: self.metadata.clone(),
         }
     }
@@ -70,6 +74,7 @@ impl Components {
             not_found: a.not_found.or(b.not_found),
             default: a.default.or(b.default),
             route: a.route.or(b.route),
+            global_error: a.global_error.or(b.global_error),
             metadata: Metadata::merge(&a.metadata, &b.metadata),
         }
     }
@@ -167,6 +172,8 @@ pub struct GlobalMetadata {
     pub robots: Option<MetadataItem>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sitemap: Option<MetadataItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<MetadataItem>,
 }
 
 impl GlobalMetadata {
@@ -175,8 +182,9 @@ impl GlobalMetadata {
             favicon,
             robots,
             sitemap,
+            manifest,
         } = self;
-        favicon.is_none() && robots.is_none() && sitemap.is_none()
+        favicon.is_none() && robots.is_none() && sitemap.is_none() && manifest.is_none()
     }
 }
 
@@ -276,6 +284,7 @@ static STATIC_GLOBAL_METADATA: Lazy<HashMap<&'static str, &'static [&'static str
             ("favicon", &["ico"] as &'static [&'static str]),
             ("robots", &["txt"]),
             ("sitemap", &["xml"]),
+            ("manifest", &["webmanifest", "json"]),
         ])
     });
 
@@ -328,6 +337,7 @@ async fn get_directory_tree(
                             "not-found" => components.not_found = Some(file),
                             "default" => components.default = Some(file),
                             "route" => components.route = Some(file),
+                            "global-error" => components.global_error = Some(file),
                             "manifest" => {
                                 components.metadata.manifest =
                                     Some(MetadataItem::Dynamic { path: file });
@@ -729,6 +739,7 @@ pub async fn get_global_metadata(
                     "favicon" => Some(&mut metadata.favicon),
                     "sitemap" => Some(&mut metadata.sitemap),
                     "robots" => Some(&mut metadata.robots),
+                    "manifest" => Some(&mut metadata.manifest),
                     _ => None,
                 };
                 if let Some(list) = list {
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use indexmap::IndexSet;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::turbopack::{
+    core::introspect::{Introspectable, IntrospectableChildrenVc, IntrospectableVc},
+    dev_server::source::{
+        route_tree::{BaseSegment, RouteTreeVc, RouteType},
+        ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+        ContentSourceVc, GetContentSourceContent, GetContentSourceContentVc, HeaderListVc,
+        StaticContent,
+    },
+};
+
+/// The pathname under which the chunks and assets of pages are served.
+const STATIC_PATHNAME: &str = "_next/static";
+
+/// The `Cache-Control` header of assets whose filename contains a hash of
+/// their content, which never change under the same URL.
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// The `Cache-Control` header of all other static assets, which are
+/// revalidated on every request.
+const REVALIDATE_CACHE_CONTROL: &str = "public, max-age=0, must-revalidate";
+
+/// Adds a `Cache-Control` header to the static responses of an inner source
+/// that serves the assets in `_next/static`, like the chunks and media of a
+/// page loader. Content-hashed assets, like the images and fonts emitted to
+/// `_next/static/media`, are cached forever, while all other assets are
+/// revalidated, so changes show up on the next request.
+///
+/// As assets only vary on their path, so does this source.
+#[turbo_tasks::value(shared)]
+pub struct CacheControlContentSource {
+    inner: ContentSourceVc,
+}
+
+#[turbo_tasks::value_impl]
+impl CacheControlContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(inner: ContentSourceVc) -> CacheControlContentSourceVc {
+        CacheControlContentSource { inner }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for CacheControlContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self_vc: CacheControlContentSourceVc) -> RouteTreeVc {
+        RouteTreeVc::new_route(
+            BaseSegment::from_static_pathname(STATIC_PATHNAME).collect(),
+            RouteType::CatchAll,
+            self_vc.into(),
+        )
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for CacheControlContentSource {
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let mut content = ContentSourceContent::Next.cell();
+        for get_content in self.inner.get_routes().get(path).await?.iter() {
+            let inner_content = get_content.get(path, data.clone());
+            if !matches!(*inner_content.await?, ContentSourceContent::Next) {
+                content = inner_content;
+                break;
+            }
+        }
+
+        let ContentSourceContent::Static(static_content) = &*content.await? else {
+            return Ok(content);
+        };
+        let static_content = static_content.await?;
+        if static_content.status_code != 200 {
+            return Ok(content);
+        }
+        let mut headers = static_content.headers.await?.clone_value();
+        if headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("cache-control"))
+        {
+            return Ok(content);
+        }
+
+        headers.push(("cache-control".to_string(), cache_control(path).to_string()));
+        Ok(ContentSourceContent::Static(
+            StaticContent {
+                content: static_content.content,
+                status_code: static_content.status_code,
+                headers: HeaderListVc::new(headers),
+            }
+            .cell(),
+        )
+        .cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for CacheControlContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("cache control source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell(
+            "adds Cache-Control headers to static responses, which are immutable for \
+             content-hashed assets."
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<IntrospectableChildrenVc> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = IntrospectableVc::resolve_from(self.inner).await? {
+            children.insert((StringVc::cell("inner".to_string()), inner));
+        }
+        Ok(IntrospectableChildrenVc::cell(children))
+    }
+}
+
+pub(crate) fn cache_control(path: &str) -> &'static str {
+    if is_content_hashed(path) {
+        IMMUTABLE_CACHE_CONTROL
+    } else {
+        REVALIDATE_CACHE_CONTROL
+    }
+}
+
+/// Whether the filename contains a content hash between its name and its
+/// extension, e.g. `logo.4a7e2b9c.png`.
+fn is_content_hashed(path: &str) -> bool {
+    let filename = path.rsplit('/').next().unwrap_or(path);
+    let segments: Vec<_> = filename.split('.').collect();
+    segments.len() > 2
+        && segments[1..segments.len() - 1]
+            .iter()
+            .any(|segment| segment.len() >= 8 && segment.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cache_control, IMMUTABLE_CACHE_CONTROL, REVALIDATE_CACHE_CONTROL};
+
+    #[test]
+    fn test_cache_control() {
+        assert_eq!(
+            cache_control("_next/static/media/logo.4a7e2b9c.png"),
+            IMMUTABLE_CACHE_CONTROL
+        );
+        assert_eq!(
+            cache_control("_next/static/media/inter.latin.0f3d5e1a9b2c7d48.woff2"),
+            IMMUTABLE_CACHE_CONTROL
+        );
+        assert_eq!(
+            cache_control("_next/static/chunks/pages_index_4a7e2b.js"),
+            REVALIDATE_CACHE_CONTROL
+        );
+        assert_eq!(cache_control("favicon.ico"), REVALIDATE_CACHE_CONTROL);
+        assert_eq!(cache_control("a.b.c"), REVALIDATE_CACHE_CONTROL);
+        assert_eq!(cache_control("deadbeef00.js"), REVALIDATE_CACHE_CONTROL);
+        assert_eq!(cache_control("cafe.babe.js"), REVALIDATE_CACHE_CONTROL);
+    }
+}
@@ -0,0 +1,162 @@
+use anyhow::Result;
+use indexmap::IndexSet;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::turbopack::{
+    core::introspect::{Introspectable, IntrospectableChildrenVc, IntrospectableVc},
+    dev_server::source::{
+        route_tree::{BaseSegment, RouteTreeVc, RouteType},
+        ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+        ContentSourceDataVary, ContentSourceDataVaryVc, ContentSourceVc, GetContentSourceContent,
+        GetContentSourceContentVc,
+    },
+};
+
+use crate::next_config::{CacheQueryParamsConfig, NextConfigVc};
+
+/// Drops the query params that aren't part of the cache key, as configured by
+/// `experimental.turbo.cacheQueryParams`, before a request reaches the inner
+/// rendered source. Requests that only differ in those params then share the
+/// same rendered result.
+///
+/// `base_segments` and `route_type` are the ones of the inner source, so the
+/// page keeps its place in the route tree.
+#[turbo_tasks::value(shared)]
+pub struct CacheQueryParamsContentSource {
+    inner: ContentSourceVc,
+    base_segments: Vec<BaseSegment>,
+    route_type: RouteType,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl CacheQueryParamsContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: ContentSourceVc,
+        base_segments: Vec<BaseSegment>,
+        route_type: RouteType,
+        next_config: NextConfigVc,
+    ) -> CacheQueryParamsContentSourceVc {
+        CacheQueryParamsContentSource {
+            inner,
+            base_segments,
+            route_type,
+            next_config,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for CacheQueryParamsContentSource {
+    #[turbo_tasks::function]
+    async fn get_routes(self_vc: CacheQueryParamsContentSourceVc) -> Result<RouteTreeVc> {
+        let this = self_vc.await?;
+        if this.next_config.cache_query_params().await?.includes_all() {
+            return Ok(this.inner.get_routes());
+        }
+
+        Ok(RouteTreeVc::new_route(
+            this.base_segments.clone(),
+            this.route_type.clone(),
+            self_vc.into(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for CacheQueryParamsContentSource {
+    /// The request data a rendered source varies on.
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            method: true,
+            url: true,
+            original_url: true,
+            raw_headers: true,
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let config = self.next_config.cache_query_params().await?;
+        let mut data = data.into_value();
+        data.url = data.url.map(|url| filter_url(&config, &url));
+        data.original_url = data.original_url.map(|url| filter_url(&config, &url));
+        data.raw_query = data.raw_query.map(|query| config.filter_query(&query));
+        let data = Value::new(data);
+
+        for get_content in self.inner.get_routes().get(path).await?.iter() {
+            let content = get_content.get(path, data.clone());
+            if !matches!(*content.await?, ContentSourceContent::Next) {
+                return Ok(content);
+            }
+        }
+        Ok(ContentSourceContent::Next.cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for CacheQueryParamsContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("cache query params source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell(
+            "drops the query params that aren't part of the cache key before rendering."
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<IntrospectableChildrenVc> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = IntrospectableVc::resolve_from(self.inner).await? {
+            children.insert((StringVc::cell("inner".to_string()), inner));
+        }
+        Ok(IntrospectableChildrenVc::cell(children))
+    }
+}
+
+/// Filters the query of a url, dropping the `?` when no param is left.
+fn filter_url(config: &CacheQueryParamsConfig, url: &str) -> String {
+    let Some((pathname, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let query = config.filter_query(query);
+    if query.is_empty() {
+        pathname.to_string()
+    } else {
+        format!("{pathname}?{query}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::filter_url;
+    use crate::next_config::CacheQueryParamsConfig;
+
+    #[test]
+    fn test_filter_url() {
+        let config = CacheQueryParamsConfig {
+            include: None,
+            exclude: Some(vec!["utm_*".to_string()]),
+        };
+        assert_eq!(
+            filter_url(&config, "/count?page=1&utm_source=a"),
+            "/count?page=1"
+        );
+        assert_eq!(filter_url(&config, "/count?utm_source=a"), "/count");
+        assert_eq!(filter_url(&config, "/count"), "/count");
+    }
+}
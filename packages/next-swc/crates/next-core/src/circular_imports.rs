@@ -0,0 +1,232 @@
+//! Detection of import cycles between the modules of a project, enabled with
+//! `experimental.circularImportWarnings`.
+//!
+//! A cycle makes the evaluation order of its modules depend on which one is
+//! imported first, so an export can be read before it is initialized
+//! ("Cannot access 'x' before initialization").
+
+use std::hash::Hash;
+
+use anyhow::Result;
+use indexmap::{IndexMap, IndexSet};
+use turbo_tasks::{primitives::StringVc, CompletionVc};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileSystemPath, FileSystemPathVc},
+    turbopack::core::{
+        asset::{Asset, AssetVc},
+        issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+        module::ModuleVc,
+        reference::AssetReference,
+    },
+};
+
+/// Emits a [CircularImportIssue] for every cycle between the project's own
+/// modules that is reachable from `entry`. Modules outside of the project and
+/// in `node_modules` are not followed.
+#[turbo_tasks::function]
+pub async fn emit_circular_import_issues(
+    entry: AssetVc,
+    project_path: FileSystemPathVc,
+) -> Result<CompletionVc> {
+    let project_path_value = project_path.await?;
+
+    let mut graph = IndexMap::new();
+    let mut queue = vec![entry.resolve().await?];
+    while let Some(asset) = queue.pop() {
+        if graph.contains_key(&asset) {
+            continue;
+        }
+        let referenced = referenced_project_modules(asset, &project_path_value).await?;
+        queue.extend(referenced.iter().copied());
+        graph.insert(asset, referenced);
+    }
+
+    for cycle in find_cycles(&graph) {
+        let mut paths = Vec::with_capacity(cycle.len());
+        for asset in &cycle {
+            let path = asset.ident().path().await?;
+            paths.push(
+                project_path_value
+                    .get_path_to(&path)
+                    .unwrap_or(&path.path)
+                    .to_string(),
+            );
+        }
+        CircularImportIssue {
+            path: cycle[0].ident().path(),
+            paths,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+    }
+
+    Ok(CompletionVc::new())
+}
+
+pub(crate) async fn referenced_project_modules(
+    asset: AssetVc,
+    project_path: &FileSystemPath,
+) -> Result<Vec<AssetVc>> {
+    let mut modules = Vec::new();
+    for reference in asset.references().await?.iter() {
+        for &referenced in reference.resolve_reference().primary_assets().await?.iter() {
+            if ModuleVc::resolve_from(referenced).await?.is_none() {
+                continue;
+            }
+            let path = referenced.ident().path().await?;
+            let Some(relative) = project_path.get_path_to(&path) else {
+                continue;
+            };
+            if relative.split('/').any(|segment| segment == "node_modules") {
+                continue;
+            }
+            modules.push(referenced.resolve().await?);
+        }
+    }
+    Ok(modules)
+}
+
+/// Returns the strongly connected components of `graph` that contain a cycle,
+/// i.e. more than one node or a node that references itself. The nodes of a
+/// component are in the order in which they were discovered.
+fn find_cycles<N>(graph: &IndexMap<N, Vec<N>>) -> Vec<Vec<N>>
+where
+    N: Copy + Eq + Hash,
+{
+    // An iterative version of Tarjan's algorithm, as module graphs can be
+    // deeper than the stack allows.
+    let mut index: IndexMap<N, (usize, usize)> = IndexMap::new();
+    let mut stack: IndexSet<N> = IndexSet::new();
+    let mut cycles = Vec::new();
+
+    for &root in graph.keys() {
+        if index.contains_key(&root) {
+            continue;
+        }
+
+        let mut work = vec![(root, 0)];
+        while let Some((node, child)) = work.pop() {
+            if child == 0 {
+                let next_index = index.len();
+                index.insert(node, (next_index, next_index));
+                stack.insert(node);
+            }
+
+            let edges = graph.get(&node).map(Vec::as_slice).unwrap_or_default();
+            if let Some(&next) = edges.get(child) {
+                work.push((node, child + 1));
+                match index.get(&next) {
+                    None => work.push((next, 0)),
+                    Some(&(next_index, _)) if stack.contains(&next) => {
+                        let low_link = &mut index.get_mut(&node).unwrap().1;
+                        *low_link = (*low_link).min(next_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            let (node_index, node_low_link) = index[&node];
+            if let Some(&(parent, _)) = work.last() {
+                let parent_low_link = &mut index.get_mut(&parent).unwrap().1;
+                *parent_low_link = (*parent_low_link).min(node_low_link);
+            }
+
+            if node_low_link == node_index {
+                let position = stack.get_index_of(&node).unwrap();
+                let component = stack.drain(position..).collect::<Vec<_>>();
+                if component.len() > 1 || edges.contains(&node) {
+                    cycles.push(component);
+                }
+            }
+        }
+    }
+
+    cycles
+}
+
+/// Modules that import each other, directly or through other modules.
+#[turbo_tasks::value(shared)]
+pub struct CircularImportIssue {
+    path: FileSystemPathVc,
+    paths: Vec<String>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for CircularImportIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("Circular import".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("module".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        let mut description = "These modules import each other, so some of their exports can be \
+                               undefined or uninitialized while they are evaluated:\n"
+            .to_string();
+        for path in &self.paths {
+            description.push_str(&format!("\n- {path}"));
+        }
+        StringVc::cell(description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::indexmap;
+
+    use super::find_cycles;
+
+    #[test]
+    fn test_two_module_cycle() {
+        let graph = indexmap! {
+            "page" => vec!["a"],
+            "a" => vec!["b"],
+            "b" => vec!["a", "c"],
+            "c" => vec![],
+        };
+
+        assert_eq!(find_cycles(&graph), vec![vec!["a", "b"]]);
+    }
+
+    #[test]
+    fn test_no_cycle() {
+        let graph = indexmap! {
+            "page" => vec!["a", "b"],
+            "a" => vec!["c"],
+            "b" => vec!["c"],
+            "c" => vec![],
+        };
+
+        assert!(find_cycles(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_self_import_and_separate_cycles() {
+        let graph = indexmap! {
+            "page" => vec!["a", "c"],
+            "a" => vec!["a"],
+            "c" => vec!["d"],
+            "d" => vec!["e"],
+            "e" => vec!["c"],
+        };
+
+        assert_eq!(find_cycles(&graph), vec![vec!["a"], vec!["c", "d", "e"]]);
+    }
+}
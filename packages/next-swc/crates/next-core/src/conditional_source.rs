@@ -0,0 +1,379 @@
+use anyhow::Result;
+use futures::TryStreamExt;
+use indexmap::IndexSet;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::{
+    turbo::{
+        tasks_fs::FileContent,
+        tasks_hash::{encode_hex, hash_xxh3_hash64},
+    },
+    turbopack::{
+        core::introspect::{Introspectable, IntrospectableChildrenVc, IntrospectableVc},
+        dev_server::source::{
+            route_tree::{BaseSegment, RouteTreeVc, RouteType},
+            ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+            ContentSourceDataVary, ContentSourceDataVaryVc, ContentSourceVc,
+            GetContentSourceContent, GetContentSourceContentVc, HeaderListVc, ProxyResult,
+            StaticContent,
+        },
+    },
+};
+
+use crate::next_config::NextConfigVc;
+
+/// Adds an `ETag` header with a hash of the body to the rendered HTML of the
+/// inner source, as `generateEtags` configures, and answers requests whose
+/// `If-None-Match` header matches it with a `304 Not Modified`.
+///
+/// `base_segments` and `route_type` are the ones of the inner source, so the
+/// page keeps its place in the route tree.
+#[turbo_tasks::value(shared)]
+pub struct ConditionalContentSource {
+    inner: ContentSourceVc,
+    base_segments: Vec<BaseSegment>,
+    route_type: RouteType,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl ConditionalContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: ContentSourceVc,
+        base_segments: Vec<BaseSegment>,
+        route_type: RouteType,
+        next_config: NextConfigVc,
+    ) -> ConditionalContentSourceVc {
+        ConditionalContentSource {
+            inner,
+            base_segments,
+            route_type,
+            next_config,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for ConditionalContentSource {
+    #[turbo_tasks::function]
+    async fn get_routes(self_vc: ConditionalContentSourceVc) -> Result<RouteTreeVc> {
+        let this = self_vc.await?;
+        if !*this.next_config.generate_etags().await? {
+            return Ok(this.inner.get_routes());
+        }
+
+        Ok(RouteTreeVc::new_route(
+            this.base_segments.clone(),
+            this.route_type.clone(),
+            self_vc.into(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for ConditionalContentSource {
+    /// The request data a rendered source varies on.
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            method: true,
+            url: true,
+            original_url: true,
+            raw_headers: true,
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let mut content = ContentSourceContent::Next.cell();
+        for get_content in self.inner.get_routes().get(path).await?.iter() {
+            let inner_content = get_content.get(path, data.clone());
+            if !matches!(*inner_content.await?, ContentSourceContent::Next) {
+                content = inner_content;
+                break;
+            }
+        }
+
+        if !matches!(data.method.as_deref(), Some("GET" | "HEAD")) {
+            return Ok(content);
+        }
+        let if_none_match = request_header(&data, "if-none-match");
+
+        match &*content.await? {
+            ContentSourceContent::Static(static_content) => {
+                let static_content = static_content.await?;
+                if static_content.status_code != 200 {
+                    return Ok(content);
+                }
+                let FileContent::Content(file) = &*static_content.content.file_content().await?
+                else {
+                    return Ok(content);
+                };
+
+                let etag = etag(&file.content().to_bytes()?);
+                let validators = validators(Some(&etag), None);
+                if is_not_modified(if_none_match, None, Some(&etag), None) {
+                    return Ok(not_modified(validators));
+                }
+
+                let mut headers = static_content.headers.await?.clone_value();
+                headers.extend(validators);
+                Ok(ContentSourceContent::Static(
+                    StaticContent {
+                        content: static_content.content,
+                        status_code: static_content.status_code,
+                        headers: HeaderListVc::new(headers),
+                    }
+                    .cell(),
+                )
+                .cell())
+            }
+            // Streamed HTML, like the one of app pages, is buffered to hash it.
+            ContentSourceContent::HttpProxy(proxy_result) => {
+                let proxy_result = proxy_result.await?;
+                if proxy_result.status != 200 || !is_html(&proxy_result.headers) {
+                    return Ok(content);
+                }
+
+                let mut body_stream = proxy_result.body.read();
+                let mut body = Vec::new();
+                while let Some(data) = body_stream.try_next().await? {
+                    body.extend_from_slice(&data);
+                }
+
+                let etag = etag(&body);
+                let validators = validators(Some(&etag), None);
+                if is_not_modified(if_none_match, None, Some(&etag), None) {
+                    return Ok(not_modified(validators));
+                }
+
+                let mut headers = proxy_result.headers.clone();
+                headers.extend(validators);
+                Ok(ContentSourceContent::HttpProxy(
+                    ProxyResult {
+                        status: proxy_result.status,
+                        headers,
+                        body: body.into(),
+                    }
+                    .cell(),
+                )
+                .cell())
+            }
+            _ => Ok(content),
+        }
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for ConditionalContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("conditional request source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell(
+            "adds ETag headers to rendered pages and answers conditional requests.".to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<IntrospectableChildrenVc> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = IntrospectableVc::resolve_from(self.inner).await? {
+            children.insert((StringVc::cell("inner".to_string()), inner));
+        }
+        Ok(IntrospectableChildrenVc::cell(children))
+    }
+}
+
+/// The value of a request header, which is matched case-insensitively.
+pub(crate) fn request_header<'a>(data: &'a ContentSourceData, header: &str) -> Option<&'a str> {
+    data.raw_headers
+        .as_ref()?
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(header))
+        .map(|(_, value)| value.as_str())
+}
+
+fn is_html(headers: &[(String, String)]) -> bool {
+    headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("content-type") && value.starts_with("text/html")
+    })
+}
+
+/// The `ETag` and `Last-Modified` headers of a response.
+pub(crate) fn validators(etag: Option<&str>, last_modified: Option<u64>) -> Vec<(String, String)> {
+    let mut validators = Vec::new();
+    if let Some(etag) = etag {
+        validators.push(("etag".to_string(), etag.to_string()));
+    }
+    if let Some(last_modified) = last_modified {
+        validators.push(("last-modified".to_string(), http_date(last_modified)));
+    }
+    validators
+}
+
+pub(crate) fn etag(body: &[u8]) -> String {
+    format!("\"{}\"", encode_hex(hash_xxh3_hash64(body)))
+}
+
+pub(crate) fn not_modified(validators: Vec<(String, String)>) -> ContentSourceContentVc {
+    ContentSourceContent::HttpProxy(
+        ProxyResult {
+            status: 304,
+            headers: validators,
+            body: "".into(),
+        }
+        .cell(),
+    )
+    .cell()
+}
+
+/// Whether a conditional request can be answered with a `304 Not Modified`.
+/// As RFC 9110 requires, `If-Modified-Since` is ignored when the request has
+/// an `If-None-Match` header.
+pub(crate) fn is_not_modified(
+    if_none_match: Option<&str>,
+    if_modified_since: Option<&str>,
+    etag: Option<&str>,
+    last_modified: Option<u64>,
+) -> bool {
+    if let Some(if_none_match) = if_none_match {
+        return etag.map_or(false, |etag| if_none_match_matches(if_none_match, etag));
+    }
+    match (if_modified_since.and_then(parse_http_date), last_modified) {
+        (Some(since), Some(last_modified)) => last_modified <= since,
+        _ => false,
+    }
+}
+
+/// Whether an `If-None-Match` header, a list of entity tags or `*`, matches
+/// `etag`. Weak tags are compared like strong ones, as RFC 9110 requires for
+/// `If-None-Match`.
+fn if_none_match_matches(header: &str, etag: &str) -> bool {
+    header
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Formats seconds since the Unix epoch as an HTTP date, e.g.
+/// `Wed, 21 Oct 2015 07:28:00 GMT`.
+fn http_date(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let time = secs % 86400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[((days + 4) % 7) as usize],
+        day,
+        MONTHS[month as usize - 1],
+        year,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+/// Parses an HTTP date in the preferred format of RFC 9110 into seconds since
+/// the Unix epoch.
+fn parse_http_date(date: &str) -> Option<u64> {
+    let [_, day, month, year, time, "GMT"] = date.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let day: i64 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|m| *m == month)? as i64 + 1;
+    let year: i64 = year.parse().ok()?;
+    let [hours, minutes, seconds] = time
+        .split(':')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?[..]
+    else {
+        return None;
+    };
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    Some(days * 86400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+// See http://howardhinnant.github.io/date_algorithms.html
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{http_date, if_none_match_matches, is_not_modified, parse_http_date};
+
+    #[test]
+    fn test_if_none_match_matches() {
+        assert!(if_none_match_matches("\"abc\"", "\"abc\""));
+        assert!(if_none_match_matches("\"xyz\", W/\"abc\"", "\"abc\""));
+        assert!(if_none_match_matches("*", "\"abc\""));
+        assert!(!if_none_match_matches("\"abcd\"", "\"abc\""));
+        assert!(!if_none_match_matches("", "\"abc\""));
+    }
+
+    #[test]
+    fn test_is_not_modified() {
+        let date = "Wed, 21 Oct 2015 07:28:00 GMT";
+        assert!(is_not_modified(None, Some(date), None, Some(1445412480)));
+        assert!(is_not_modified(None, Some(date), None, Some(1445412000)));
+        assert!(!is_not_modified(None, Some(date), None, Some(1445412481)));
+        assert!(!is_not_modified(None, Some(date), None, None));
+        // `If-None-Match` takes precedence.
+        assert!(!is_not_modified(
+            Some("\"xyz\""),
+            Some(date),
+            Some("\"abc\""),
+            Some(1445412480)
+        ));
+    }
+
+    #[test]
+    fn test_http_date() {
+        assert_eq!(http_date(1445412480), "Wed, 21 Oct 2015 07:28:00 GMT");
+        assert_eq!(http_date(0), "Thu, 01 Jan 1970 00:00:00 GMT");
+        assert_eq!(http_date(951782400), "Tue, 29 Feb 2000 00:00:00 GMT");
+        assert_eq!(
+            parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(1445412480)
+        );
+        assert_eq!(parse_http_date("Wednesday, 21-Oct-15 07:28:00 GMT"), None);
+        for secs in [0, 86399, 1_000_000_000, 1_700_000_000, 4_102_444_800] {
+            assert_eq!(parse_http_date(&http_date(secs)), Some(secs));
+        }
+    }
+}
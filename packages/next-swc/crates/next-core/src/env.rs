@@ -61,6 +61,35 @@ pub async fn env_for_js(
         map.insert("__NEXT_STRICT_MODE_APP".to_string(), "true".to_string());
     }
 
+    if client {
+        if next_config.experimental.scroll_restoration.unwrap_or(false) {
+            map.insert("__NEXT_SCROLL_RESTORATION".to_string(), "true".to_string());
+        }
+
+        if let Some(web_vitals_attribution) = &next_config.experimental.web_vitals_attribution {
+            if !web_vitals_attribution.is_empty() {
+                map.insert(
+                    "__NEXT_HAS_WEB_VITALS_ATTRIBUTION".to_string(),
+                    "true".to_string(),
+                );
+                map.insert(
+                    "__NEXT_WEB_VITALS_ATTRIBUTION".to_string(),
+                    serde_json::to_string(web_vitals_attribution)?,
+                );
+            }
+        }
+
+        if let Some(chunk_load_retry) = &next_config.experimental.chunk_load_retry {
+            map.insert(
+                "__NEXT_CHUNK_LOAD_RETRIES".to_string(),
+                chunk_load_retry.retries.to_string(),
+            );
+            if let Some(timeout) = chunk_load_retry.timeout {
+                map.insert("__NEXT_CHUNK_LOAD_TIMEOUT".to_string(), timeout.to_string());
+            }
+        }
+    }
+
     if !test_mode.is_empty() {
         map.insert("__NEXT_TEST_MODE".to_string(), "true".to_string());
     }
@@ -0,0 +1,208 @@
+use anyhow::Result;
+use indexmap::IndexSet;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileContent, FileSystemPathVc},
+    turbopack::{
+        core::{
+            introspect::{Introspectable, IntrospectableChildrenVc, IntrospectableVc},
+            issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+        },
+        dev_server::source::{
+            route_tree::{BaseSegment, RouteTreeVc, RouteType},
+            ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+            ContentSourceDataVary, ContentSourceDataVaryVc, ContentSourceVc,
+            GetContentSourceContent, GetContentSourceContentVc,
+        },
+    },
+};
+
+use crate::{next_config::NextConfigVc, util::DEFAULT_LARGE_PAGE_DATA_BYTES};
+
+/// Warns when the page data that the inner source renders for a client-side
+/// navigation exceeds `experimental.largePageDataBytes`. The page data inlined
+/// in the HTML is already checked by `next/document`.
+///
+/// `base_segments` and `route_type` are the ones of the inner source, so the
+/// page keeps its place in the route tree.
+#[turbo_tasks::value(shared)]
+pub struct LargePageDataContentSource {
+    inner: ContentSourceVc,
+    base_segments: Vec<BaseSegment>,
+    route_type: RouteType,
+    page_path: FileSystemPathVc,
+    pathname: StringVc,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl LargePageDataContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: ContentSourceVc,
+        base_segments: Vec<BaseSegment>,
+        route_type: RouteType,
+        page_path: FileSystemPathVc,
+        pathname: StringVc,
+        next_config: NextConfigVc,
+    ) -> LargePageDataContentSourceVc {
+        LargePageDataContentSource {
+            inner,
+            base_segments,
+            route_type,
+            page_path,
+            pathname,
+            next_config,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for LargePageDataContentSource {
+    #[turbo_tasks::function]
+    async fn get_routes(self_vc: LargePageDataContentSourceVc) -> Result<RouteTreeVc> {
+        let this = self_vc.await?;
+        Ok(RouteTreeVc::new_route(
+            this.base_segments.clone(),
+            this.route_type.clone(),
+            self_vc.into(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for LargePageDataContentSource {
+    /// The request data a rendered source varies on.
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            method: true,
+            url: true,
+            original_url: true,
+            raw_headers: true,
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let mut content = ContentSourceContent::Next.cell();
+        for get_content in self.inner.get_routes().get(path).await?.iter() {
+            let inner_content = get_content.get(path, data.clone());
+            if !matches!(*inner_content.await?, ContentSourceContent::Next) {
+                content = inner_content;
+                break;
+            }
+        }
+
+        let ContentSourceContent::Static(static_content) = &*content.await? else {
+            return Ok(content);
+        };
+        let static_content = static_content.await?;
+        if static_content.status_code != 200 {
+            return Ok(content);
+        }
+        let FileContent::Content(file) = &*static_content.content.file_content().await? else {
+            return Ok(content);
+        };
+
+        let threshold = self
+            .next_config
+            .await?
+            .experimental
+            .large_page_data_bytes
+            .unwrap_or(DEFAULT_LARGE_PAGE_DATA_BYTES) as usize;
+        let size = file.content().len();
+        if size > threshold {
+            LargePageDataIssue {
+                page_path: self.page_path,
+                pathname: self.pathname.await?.clone_value(),
+                size,
+                threshold,
+            }
+            .cell()
+            .as_issue()
+            .emit();
+        }
+
+        Ok(content)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for LargePageDataContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("large page data source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell(
+            "warns when the page data of a client-side navigation exceeds \
+             experimental.largePageDataBytes."
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<IntrospectableChildrenVc> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = IntrospectableVc::resolve_from(self.inner).await? {
+            children.insert((StringVc::cell("inner".to_string()), inner));
+        }
+        Ok(IntrospectableChildrenVc::cell(children))
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct LargePageDataIssue {
+    page_path: FileSystemPathVc,
+    pathname: String,
+    size: usize,
+    threshold: usize,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for LargePageDataIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("Large page data".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("next pages".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.page_path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(format!(
+            "The data for page \"{}\" is {} bytes, which exceeds the threshold of {} bytes set by \
+             `experimental.largePageDataBytes`. This amount of data can reduce performance.",
+            self.pathname, self.size, self.threshold
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn documentation_link(&self) -> StringVc {
+        StringVc::cell("https://nextjs.org/docs/messages/large-page-data".to_string())
+    }
+}
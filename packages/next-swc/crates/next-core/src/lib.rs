@@ -4,16 +4,24 @@
 #![feature(min_specialization)]
 #![feature(str_split_remainder)]
 
+mod app_error_boundary;
 mod app_render;
 mod app_segment_config;
 mod app_source;
 pub mod app_structure;
 mod babel;
 mod bootstrap;
+pub mod cache_control_source;
+pub mod cache_query_params_source;
+mod circular_imports;
+pub mod conditional_source;
 mod embed_js;
 pub mod env;
 mod fallback;
+pub mod large_page_data_source;
+pub mod link_prefetch;
 pub mod manifest;
+pub mod middleware;
 pub mod mode;
 mod next_build;
 pub mod next_client;
@@ -30,10 +38,17 @@ pub mod next_shared;
 mod page_loader;
 mod page_source;
 pub mod pages_structure;
+pub mod proxy_fallback_source;
+pub mod public_assets_source;
+pub mod redirect_chains;
+pub mod request_log_source;
+pub mod route_conflicts;
 pub mod router;
 pub mod router_source;
 mod runtime;
 mod sass;
+pub mod source_priority;
+pub mod tenant_source;
 mod transform_options;
 pub mod url_node;
 mod util;
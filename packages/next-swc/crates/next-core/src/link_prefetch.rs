@@ -0,0 +1,313 @@
+//! Discovery of the routes a page links to with `next/link`, so that the
+//! chunks of those routes can be prefetched. Only links whose `href` is a
+//! string literal are found.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use indexmap::IndexSet;
+use swc_core::ecma::{
+    ast::{
+        Bool, CallExpr, Expr, ExprOrSpread, Id, ImportSpecifier, JSXAttrName, JSXAttrOrSpread,
+        JSXAttrValue, JSXElementName, JSXExpr, JSXExprContainer, JSXOpeningElement, KeyValueProp,
+        Lit, ModuleExportName, ParenExpr, Program, Prop, PropName, PropOrSpread,
+    },
+    visit::{Visit, VisitWith},
+};
+use turbo_tasks::primitives::StringsVc;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::{
+        core::asset::AssetVc,
+        ecmascript::{parse::ParseResult, EcmascriptModuleAssetVc},
+    },
+};
+
+use crate::circular_imports::referenced_project_modules;
+
+/// Returns the paths that the project's own modules reachable from `entry`
+/// link to, like `/about` for `<Link href="/about">`.
+#[turbo_tasks::function]
+pub async fn link_targets(entry: AssetVc, project_path: FileSystemPathVc) -> Result<StringsVc> {
+    let project_path_value = project_path.await?;
+
+    let mut visited = IndexSet::new();
+    let mut targets = IndexSet::new();
+    let mut queue = vec![entry.resolve().await?];
+    while let Some(asset) = queue.pop() {
+        if !visited.insert(asset) {
+            continue;
+        }
+        if let Some(module) = EcmascriptModuleAssetVc::resolve_from(asset).await? {
+            if let ParseResult::Ok { program, .. } = &*module.parse().await? {
+                targets.extend(static_link_hrefs(program));
+            }
+        }
+        queue.extend(referenced_project_modules(asset, &project_path_value).await?);
+    }
+
+    Ok(StringsVc::cell(targets.into_iter().collect()))
+}
+
+/// Returns the paths of the `next/link` elements in `program` whose `href` is
+/// a string literal pointing into the app, without their query and hash.
+/// Links with `prefetch={false}` or spread props are skipped.
+///
+/// Elements are found both as JSX and as the calls the JSX transform turns
+/// them into, like `jsx(Link, { href: "/about" })`.
+fn static_link_hrefs(program: &Program) -> Vec<String> {
+    let link_ids = link_import_ids(program);
+    if link_ids.is_empty() {
+        return Vec::new();
+    }
+    let mut visitor = LinkHrefVisitor {
+        link_ids,
+        hrefs: Vec::new(),
+    };
+    program.visit_with(&mut visitor);
+    visitor.hrefs
+}
+
+/// Returns the local bindings of the default export of `next/link`, e.g.
+/// `Link` for `import Link from 'next/link'`.
+fn link_import_ids(program: &Program) -> HashSet<Id> {
+    let Program::Module(module) = program else {
+        return HashSet::new();
+    };
+    module
+        .body
+        .iter()
+        .filter_map(|item| item.as_module_decl()?.as_import())
+        .filter(|import| !import.type_only && &*import.src.value == "next/link")
+        .flat_map(|import| import.specifiers.iter())
+        .filter_map(|specifier| match specifier {
+            ImportSpecifier::Default(default) => Some(default.local.to_id()),
+            ImportSpecifier::Named(named) if !named.is_type_only => {
+                let is_default = match &named.imported {
+                    Some(ModuleExportName::Ident(ident)) => &*ident.sym == "default",
+                    Some(ModuleExportName::Str(str)) => &*str.value == "default",
+                    None => false,
+                };
+                is_default.then(|| named.local.to_id())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+struct LinkHrefVisitor {
+    link_ids: HashSet<Id>,
+    hrefs: Vec<String>,
+}
+
+impl Visit for LinkHrefVisitor {
+    fn visit_jsx_opening_element(&mut self, element: &JSXOpeningElement) {
+        element.visit_children_with(self);
+
+        let JSXElementName::Ident(name) = &element.name else {
+            return;
+        };
+        if !self.link_ids.contains(&name.to_id()) {
+            return;
+        }
+
+        let mut props = LinkProps::default();
+        for attr in &element.attrs {
+            let JSXAttrOrSpread::JSXAttr(attr) = attr else {
+                props.spread = true;
+                continue;
+            };
+            let JSXAttrName::Ident(name) = &attr.name else {
+                continue;
+            };
+            let lit;
+            let value = match &attr.value {
+                Some(JSXAttrValue::Lit(value)) => {
+                    lit = Expr::Lit(value.clone());
+                    Some(&lit)
+                }
+                Some(JSXAttrValue::JSXExprContainer(JSXExprContainer {
+                    expr: JSXExpr::Expr(expr),
+                    ..
+                })) => Some(&**expr),
+                _ => None,
+            };
+            props.set(&name.sym, value);
+        }
+        self.hrefs.extend(props.static_href());
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        call.visit_children_with(self);
+
+        let [ExprOrSpread {
+            spread: None,
+            expr: component,
+        }, ExprOrSpread {
+            spread: None,
+            expr: props,
+        }, ..] = &call.args[..]
+        else {
+            return;
+        };
+        let Expr::Ident(component) = &**component else {
+            return;
+        };
+        if !self.link_ids.contains(&component.to_id()) {
+            return;
+        }
+        let Expr::Object(object) = &**props else {
+            return;
+        };
+
+        let mut props = LinkProps::default();
+        for prop in &object.props {
+            let PropOrSpread::Prop(prop) = prop else {
+                props.spread = true;
+                continue;
+            };
+            let Prop::KeyValue(KeyValueProp { key, value }) = &**prop else {
+                continue;
+            };
+            let name = match key {
+                PropName::Ident(ident) => &*ident.sym,
+                PropName::Str(str) => &*str.value,
+                _ => continue,
+            };
+            props.set(name, Some(value));
+        }
+        self.hrefs.extend(props.static_href());
+    }
+}
+
+/// The props of a `next/link` element that decide whether it's prefetched.
+#[derive(Default)]
+struct LinkProps {
+    href: Option<String>,
+    no_prefetch: bool,
+    spread: bool,
+}
+
+impl LinkProps {
+    fn set(&mut self, name: &str, value: Option<&Expr>) {
+        match name {
+            "href" => self.href = value.and_then(static_string),
+            "prefetch" => {
+                self.no_prefetch =
+                    matches!(value, Some(Expr::Lit(Lit::Bool(Bool { value: false, .. }))))
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the path the link points to, if it's prefetched and points into
+    /// the app.
+    fn static_href(self) -> Option<String> {
+        if self.no_prefetch || self.spread {
+            return None;
+        }
+        let href = self.href?;
+        if !href.starts_with('/') || href.starts_with("//") {
+            return None;
+        }
+        let path = href.split(['?', '#']).next().unwrap_or(&href);
+        Some(path.to_string())
+    }
+}
+
+/// Returns the value of a string literal or of a template literal without
+/// expressions.
+fn static_string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(str)) => Some(str.value.to_string()),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+            Some(tpl.quasis.first()?.cooked.as_ref()?.to_string())
+        }
+        Expr::Paren(ParenExpr { expr, .. }) => static_string(expr),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::{FileName, Globals, Mark, SourceMap, GLOBALS},
+        ecma::{
+            ast::Program,
+            parser::{parse_file_as_module, EsConfig, Syntax},
+            transforms::base::resolver,
+            visit::FoldWith,
+        },
+    };
+
+    use super::static_link_hrefs;
+
+    fn static_link_hrefs_of(source: &str) -> Vec<String> {
+        GLOBALS.set(&Globals::new(), || {
+            let source_map = SourceMap::default();
+            let file = source_map.new_source_file(FileName::Anon, source.to_string());
+            let module = parse_file_as_module(
+                &file,
+                Syntax::Es(EsConfig {
+                    jsx: true,
+                    ..Default::default()
+                }),
+                Default::default(),
+                None,
+                &mut vec![],
+            )
+            .unwrap();
+            let program =
+                Program::Module(module).fold_with(&mut resolver(Mark::new(), Mark::new(), false));
+            static_link_hrefs(&program)
+        })
+    }
+
+    #[test]
+    fn test_static_link_hrefs() {
+        assert_eq!(
+            static_link_hrefs_of(
+                r#"import Link from 'next/link'
+
+export default function Page() {
+  return (
+    <nav>
+      <Link href="/about">About</Link>
+      <Link className="a" href={'/blog?page=2'} onClick={() => a > b}>
+        Blog
+      </Link>
+      <Link href={`/docs#intro`} />
+    </nav>
+  )
+}"#
+            ),
+            vec!["/about", "/blog", "/docs"]
+        );
+        assert_eq!(
+            static_link_hrefs_of(
+                r#"import { default as NextLink } from "next/link"
+// <NextLink href="/commented" />
+const html = '<NextLink href="/string" />'
+export const a = <NextLink href="/a" />
+export const b = <NextLink href={`/posts/${id}`} />
+export const c = <NextLink href={path} />
+export const d = <NextLink href="/d" prefetch={false} />
+export const e = <NextLink href="https://nextjs.org" />
+export const f = <NextLink href="/f" {...props} />
+export const g = <NextLinkButton href="/g" />"#
+            ),
+            vec!["/a"]
+        );
+        assert_eq!(
+            static_link_hrefs_of(
+                r#"import { jsx as _jsx } from "react/jsx-runtime"
+import Link from "next/link"
+export const a = _jsx(Link, { href: "/compiled", children: "About" })
+export const b = _jsx(Link, { ...props, href: "/spread" })
+export const c = (Link) => _jsx(Link, { href: "/shadowed" })"#
+            ),
+            vec!["/compiled"]
+        );
+        assert!(static_link_hrefs_of(r#"export const a = <Link href="/about" />"#).is_empty());
+    }
+}
@@ -0,0 +1,486 @@
+//! Helpers for compiling the root middleware and describing it to the Next.js
+//! runtime.
+//!
+//! The matcher conversion below is a port of `getMiddlewareMatchers` in
+//! `get-page-static-info.ts` together with the subset of `path-to-regexp`
+//! that it relies on, so that the emitted regexes are byte-for-byte what the
+//! webpack build would write into `middleware-manifest.json`.
+
+use anyhow::{bail, Result};
+use indexmap::indexmap;
+use turbo_tasks::Value;
+use turbopack_binding::{
+    turbo::{tasks_env::ProcessEnvVc, tasks_fs::FileSystemPathVc},
+    turbopack::{
+        core::{
+            asset::AssetsVc,
+            chunk::{ChunkableModule, ChunkingContext},
+            context::{AssetContext, AssetContextVc},
+            environment::ServerAddrVc,
+            file_source::FileSourceVc,
+            reference_type::{EcmaScriptModulesReferenceSubType, ReferenceType},
+            resolve::{find_context_file, FindContextFileResult},
+        },
+        dev::DevChunkingContextVc,
+        node::execution_context::ExecutionContextVc,
+        turbopack::{transition::TransitionsByNameVc, ModuleAssetContextVc},
+    },
+};
+
+use crate::{
+    bootstrap::{route_bootstrap, BootstrapConfigVc},
+    embed_js::next_asset,
+    mode::NextMode,
+    next_config::NextConfigVc,
+    next_edge::context::{
+        get_edge_compile_time_info, get_edge_resolve_options_context, get_edge_runtime_entries,
+    },
+    next_server::context::{get_server_module_options_context, ServerContextType},
+    router::middleware_files,
+    util::parse_config_from_source,
+};
+
+/// The `originalSource` used when a middleware doesn't export a
+/// `config.matcher`.
+pub const DEFAULT_MIDDLEWARE_MATCHER_SOURCE: &str = "/:path*";
+
+/// The regex used when a middleware doesn't export a `config.matcher`.
+pub const DEFAULT_MIDDLEWARE_MATCHER_REGEXP: &str = "^/.*$";
+
+const DELIMITER_RE: &str = r"[\/#\?]";
+const DEFAULT_PATTERN: &str = r"[^\/#\?]+?";
+const PREFIXES: &[char] = &['.', '/'];
+
+/// Converts a middleware `config.matcher` source (e.g. `/api/:path*`) into the
+/// regex string expected by the runtime router.
+///
+/// The source is wrapped so that it also matches `/_next/data/<build id>/...`
+/// requests and their `.json` suffix.
+pub fn get_middleware_matcher_regexp(source: &str) -> Result<String> {
+    if !source.starts_with('/') {
+        bail!("source must start with / (got \"{}\")", source);
+    }
+    let source = if source == "/" {
+        r"/:nextData(_next/data/[^/]{1,})?/(/?index|/?index\.json)?".to_string()
+    } else {
+        format!("/:nextData(_next/data/[^/]{{1,}})?{source}(.json)?")
+    };
+    let tokens = parse(&lex(&source)?)?;
+    Ok(escape_forward_slashes(&tokens_to_regexp(&tokens)))
+}
+
+/// The root middleware compiled for the edge runtime.
+#[turbo_tasks::value(shared)]
+pub struct MiddlewareChunks {
+    /// The chunks of the middleware, in the order they need to be loaded.
+    pub chunks: AssetsVc,
+    /// The `config.matcher` sources exported by the middleware.
+    pub matchers: Option<Vec<String>>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionMiddlewareChunks(Option<MiddlewareChunksVc>);
+
+/// Compiles the root middleware (`middleware.*` or `src/middleware.*`) of the
+/// project for the edge runtime, with its chunks written to `output_path`.
+/// Returns `None` when the project has no middleware.
+#[turbo_tasks::function]
+pub async fn get_middleware_chunks(
+    project_path: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    output_path: FileSystemPathVc,
+    env: ProcessEnvVc,
+    mode: NextMode,
+    next_config: NextConfigVc,
+) -> Result<OptionMiddlewareChunksVc> {
+    let FindContextFileResult::Found(middleware_path, _) = *find_context_file(
+        project_path,
+        middleware_files(next_config.page_extensions()),
+    )
+    .await?
+    else {
+        return Ok(OptionMiddlewareChunksVc::cell(None));
+    };
+
+    let ty = Value::new(ServerContextType::Middleware);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(project_path, mode, ServerAddrVc::empty());
+    let context: AssetContextVc = ModuleAssetContextVc::new(
+        TransitionsByNameVc::cell(Default::default()),
+        edge_compile_time_info,
+        get_server_module_options_context(project_path, execution_context, ty, mode, next_config),
+        get_edge_resolve_options_context(project_path, ty, mode, next_config, execution_context),
+    )
+    .into();
+
+    let module = context.process(
+        FileSourceVc::new(middleware_path).into(),
+        Value::new(ReferenceType::EcmaScriptModules(
+            EcmaScriptModulesReferenceSubType::Undefined,
+        )),
+    );
+    let config = parse_config_from_source(module).await?;
+
+    let entry = route_bootstrap(
+        module.into(),
+        context,
+        project_path,
+        next_asset("entry/edge-bootstrap.ts"),
+        BootstrapConfigVc::cell(indexmap! {
+            "NAME".to_string() => "middleware".to_string(),
+        }),
+    );
+
+    let chunking_context = DevChunkingContextVc::builder(
+        project_path,
+        output_path,
+        output_path.join("chunks"),
+        output_path.join("assets"),
+        edge_compile_time_info.environment(),
+    )
+    .build();
+
+    let chunks = chunking_context.evaluated_chunk_group(
+        entry.as_root_chunk(chunking_context),
+        get_edge_runtime_entries(project_path, env, next_config)
+            .resolve_entries(context)
+            .with_entry(entry),
+    );
+
+    Ok(OptionMiddlewareChunksVc::cell(Some(
+        MiddlewareChunks {
+            chunks,
+            matchers: config.matcher.clone(),
+        }
+        .cell(),
+    )))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum LexToken {
+    Open,
+    Close,
+    Pattern(String),
+    Name(String),
+    Char(char),
+    EscapedChar(char),
+    Modifier(char),
+}
+
+fn lex(source: &str) -> Result<Vec<LexToken>> {
+    let chars = source.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            c @ ('*' | '+' | '?') => {
+                tokens.push(LexToken::Modifier(c));
+                i += 1;
+            }
+            '\\' => {
+                let Some(c) = chars.get(i + 1) else {
+                    bail!("Unexpected end of input at {} in \"{}\"", i, source);
+                };
+                tokens.push(LexToken::EscapedChar(*c));
+                i += 2;
+            }
+            '{' => {
+                tokens.push(LexToken::Open);
+                i += 1;
+            }
+            '}' => {
+                tokens.push(LexToken::Close);
+                i += 1;
+            }
+            ':' => {
+                let name = chars[i + 1..]
+                    .iter()
+                    .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                    .collect::<String>();
+                if name.is_empty() {
+                    bail!("Missing parameter name at {} in \"{}\"", i, source);
+                }
+                i += 1 + name.len();
+                tokens.push(LexToken::Name(name));
+            }
+            '(' => {
+                let start = i;
+                let mut count = 1;
+                let mut pattern = String::new();
+                let mut j = i + 1;
+
+                if chars.get(j) == Some(&'?') {
+                    bail!("Pattern cannot start with \"?\" at {} in \"{}\"", j, source);
+                }
+
+                while j < chars.len() {
+                    match chars[j] {
+                        '\\' => {
+                            pattern.push('\\');
+                            if let Some(c) = chars.get(j + 1) {
+                                pattern.push(*c);
+                            }
+                            j += 2;
+                            continue;
+                        }
+                        ')' => {
+                            count -= 1;
+                            if count == 0 {
+                                j += 1;
+                                break;
+                            }
+                        }
+                        '(' => {
+                            count += 1;
+                            if chars.get(j + 1) != Some(&'?') {
+                                bail!(
+                                    "Capturing groups are not allowed at {} in \"{}\"",
+                                    j,
+                                    source
+                                );
+                            }
+                        }
+                        _ => {}
+                    }
+                    pattern.push(chars[j]);
+                    j += 1;
+                }
+
+                if count != 0 {
+                    bail!("Unbalanced pattern at {} in \"{}\"", start, source);
+                }
+                if pattern.is_empty() {
+                    bail!("Missing pattern at {} in \"{}\"", start, source);
+                }
+
+                tokens.push(LexToken::Pattern(pattern));
+                i = j;
+            }
+            c => {
+                tokens.push(LexToken::Char(c));
+                i += 1;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Path(String),
+    Key {
+        prefix: String,
+        suffix: String,
+        pattern: String,
+        modifier: Option<char>,
+    },
+}
+
+fn parse(tokens: &[LexToken]) -> Result<Vec<Token>> {
+    let mut result = vec![];
+    let mut path = String::new();
+    let mut iter = tokens.iter().peekable();
+
+    macro_rules! try_consume {
+        ($variant:ident) => {
+            match iter.peek() {
+                Some(LexToken::$variant(value)) => {
+                    iter.next();
+                    Some(value.clone())
+                }
+                _ => None,
+            }
+        };
+    }
+
+    while iter.peek().is_some() {
+        let char = try_consume!(Char);
+        let name = try_consume!(Name);
+        let pattern = try_consume!(Pattern);
+
+        if name.is_some() || pattern.is_some() {
+            let mut prefix = char.map(String::from).unwrap_or_default();
+            if !prefix.is_empty() && !prefix.chars().all(|c| PREFIXES.contains(&c)) {
+                path.push_str(&prefix);
+                prefix = String::new();
+            }
+            if !path.is_empty() {
+                result.push(Token::Path(std::mem::take(&mut path)));
+            }
+            result.push(Token::Key {
+                prefix,
+                suffix: String::new(),
+                pattern: pattern.unwrap_or_else(|| DEFAULT_PATTERN.to_string()),
+                modifier: try_consume!(Modifier),
+            });
+            continue;
+        }
+
+        if let Some(value) = char.or_else(|| try_consume!(EscapedChar)) {
+            path.push(value);
+            continue;
+        }
+
+        if !path.is_empty() {
+            result.push(Token::Path(std::mem::take(&mut path)));
+        }
+
+        if matches!(iter.peek(), Some(LexToken::Open)) {
+            iter.next();
+            let mut prefix = String::new();
+            while let Some(c) = try_consume!(Char).or_else(|| try_consume!(EscapedChar)) {
+                prefix.push(c);
+            }
+            let name = try_consume!(Name);
+            let pattern = try_consume!(Pattern);
+            let mut suffix = String::new();
+            while let Some(c) = try_consume!(Char).or_else(|| try_consume!(EscapedChar)) {
+                suffix.push(c);
+            }
+            if !matches!(iter.next(), Some(LexToken::Close)) {
+                bail!("Expected \"}}\" in matcher");
+            }
+            result.push(Token::Key {
+                prefix,
+                suffix,
+                pattern: match (name, pattern) {
+                    (_, Some(pattern)) => pattern,
+                    (Some(_), None) => DEFAULT_PATTERN.to_string(),
+                    (None, None) => String::new(),
+                },
+                modifier: try_consume!(Modifier),
+            });
+            continue;
+        }
+
+        bail!("Unexpected {:?} in matcher", iter.peek().unwrap());
+    }
+
+    if !path.is_empty() {
+        result.push(Token::Path(path));
+    }
+
+    Ok(result)
+}
+
+/// Escapes a string for use in a regex, the same way `path-to-regexp` does.
+fn escape_string(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+    for c in str.chars() {
+        if ".+*?=^!:${}()[]|/\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn tokens_to_regexp(tokens: &[Token]) -> String {
+    let mut route = "^".to_string();
+
+    for token in tokens {
+        match token {
+            Token::Path(path) => route.push_str(&escape_string(path)),
+            Token::Key {
+                prefix,
+                suffix,
+                pattern,
+                modifier,
+            } => {
+                let prefix = escape_string(prefix);
+                let suffix = escape_string(suffix);
+                let modifier = modifier.map(String::from).unwrap_or_default();
+
+                if pattern.is_empty() {
+                    route.push_str(&format!("(?:{prefix}{suffix}){modifier}"));
+                } else if !prefix.is_empty() || !suffix.is_empty() {
+                    if modifier == "+" || modifier == "*" {
+                        let modifier = if modifier == "*" { "?" } else { "" };
+                        route.push_str(&format!(
+                            "(?:{prefix}((?:{pattern})(?:{suffix}{prefix}(?:{pattern}))*\
+                             ){suffix}){modifier}"
+                        ));
+                    } else {
+                        route.push_str(&format!("(?:{prefix}({pattern}){suffix}){modifier}"));
+                    }
+                } else if modifier == "+" || modifier == "*" {
+                    route.push_str(&format!("((?:{pattern}){modifier})"));
+                } else {
+                    route.push_str(&format!("({pattern}){modifier}"));
+                }
+            }
+        }
+    }
+
+    route.push_str(DELIMITER_RE);
+    route.push_str("?$");
+    route
+}
+
+/// Escapes forward slashes outside of character classes, mirroring what
+/// `RegExp.prototype.source` returns in JavaScript.
+fn escape_forward_slashes(regex: &str) -> String {
+    let mut result = String::with_capacity(regex.len());
+    let mut escaped = false;
+    let mut in_class = false;
+
+    for c in regex.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => result.push('\\'),
+            _ => {}
+        }
+        result.push(c);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_middleware_matcher_regexp;
+
+    #[test]
+    fn test_catch_all_matcher() {
+        assert_eq!(
+            get_middleware_matcher_regexp("/api/:path*").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?\/api(?:\/((?:[^\/#\?]+?)(?:\/(?:[^\/#\?]+?))*))?(.json)?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn test_static_matcher() {
+        assert_eq!(
+            get_middleware_matcher_regexp("/about").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?\/about(.json)?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn test_param_matcher() {
+        assert_eq!(
+            get_middleware_matcher_regexp("/blog/:slug/edit").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?\/blog(?:\/([^\/#\?]+?))\/edit(.json)?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn test_root_matcher() {
+        assert_eq!(
+            get_middleware_matcher_regexp("/").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?(?:\/(\/?index|\/?index\.json))?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn test_invalid_matcher() {
+        assert!(get_middleware_matcher_regexp("api").is_err());
+        assert!(get_middleware_matcher_regexp("/api/:").is_err());
+        assert!(get_middleware_matcher_regexp("/api/(foo").is_err());
+    }
+}
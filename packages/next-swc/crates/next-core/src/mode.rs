@@ -25,4 +25,13 @@ impl NextMode {
             NextMode::Build => false,
         }
     }
+
+    /// Returns true if React Refresh should be enabled. This covers both the
+    /// refresh runtime entry and the registrations added by the JSX transform.
+    pub fn is_react_refresh_enabled(&self) -> bool {
+        match self {
+            NextMode::Development => true,
+            NextMode::Build => false,
+        }
+    }
 }
@@ -53,7 +53,10 @@ use crate::{
         get_next_client_resolved_map, mdx_import_source_file,
     },
     next_shared::{
-        resolve::UnsupportedModulesResolvePluginVc,
+        resolve::{
+            AssetQueryResolvePluginVc, NativeModulesResolvePluginVc,
+            UnsupportedModulesResolvePluginVc,
+        },
         transforms::{
             emotion::get_emotion_transform_plugin, get_relay_transform_plugin,
             styled_components::get_styled_components_transform_plugin,
@@ -140,8 +143,8 @@ pub async fn get_client_resolve_options_context(
     execution_context: ExecutionContextVc,
 ) -> Result<ResolveOptionsContextVc> {
     let next_client_import_map =
-        get_next_client_import_map(project_path, ty, next_config, execution_context);
-    let next_client_fallback_import_map = get_next_client_fallback_import_map(ty);
+        get_next_client_import_map(project_path, ty, mode, next_config, execution_context);
+    let next_client_fallback_import_map = get_next_client_fallback_import_map(ty, next_config);
     let next_client_resolved_map = get_next_client_resolved_map(project_path, project_path);
     let module_options_context = ResolveOptionsContext {
         enable_node_modules: Some(project_path.root().resolve().await?),
@@ -149,9 +152,17 @@ pub async fn get_client_resolve_options_context(
         import_map: Some(next_client_import_map),
         fallback_import_map: Some(next_client_fallback_import_map),
         resolved_map: Some(next_client_resolved_map),
+        // Enables the `browser` condition (and field). `import`/`require` are picked per
+        // reference, so dual packages resolve to their ESM or CJS browser entry accordingly.
+        // Server contexts use the `node` condition instead and never set `browser`.
         browser: true,
         module: true,
-        plugins: vec![UnsupportedModulesResolvePluginVc::new(project_path).into()],
+        enable_mjs_extension: true,
+        plugins: vec![
+            AssetQueryResolvePluginVc::new(project_path).into(),
+            UnsupportedModulesResolvePluginVc::new(project_path).into(),
+            NativeModulesResolvePluginVc::new(project_path, false).into(),
+        ],
         ..Default::default()
     };
     Ok(ResolveOptionsContext {
@@ -179,8 +190,8 @@ pub async fn get_client_module_options_context(
     let resolve_options_context =
         get_client_resolve_options_context(project_path, ty, mode, next_config, execution_context);
 
-    let tsconfig = get_typescript_transform_options(project_path);
-    let decorators_options = get_decorators_transform_options(project_path);
+    let tsconfig = get_typescript_transform_options(project_path, next_config);
+    let decorators_options = get_decorators_transform_options(project_path, next_config);
     let enable_mdx_rs = if *next_config.mdx_rs().await? {
         Some(
             MdxTransformModuleOptions {
@@ -191,8 +202,12 @@ pub async fn get_client_module_options_context(
     } else {
         None
     };
-    let jsx_runtime_options =
-        get_jsx_transform_options(project_path, mode, Some(resolve_options_context));
+    let jsx_runtime_options = get_jsx_transform_options(
+        project_path,
+        mode,
+        Some(resolve_options_context),
+        next_config,
+    );
     let webpack_rules =
         *maybe_add_babel_loader(project_path, *next_config.webpack_rules().await?).await?;
     let webpack_rules = maybe_add_sass_loader(next_config.sass_config(), webpack_rules).await?;
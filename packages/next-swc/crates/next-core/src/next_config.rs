@@ -3,7 +3,7 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use turbo_tasks::{
-    primitives::{BoolVc, JsonValueVc, StringVc, StringsVc},
+    primitives::{BoolVc, JsonValueVc, OptionStringVc, StringVc, StringsVc, U32Vc},
     trace::TraceRawVcs,
     CompletionVc, Value,
 };
@@ -45,6 +45,14 @@ use turbopack_binding::{
 
 use crate::{embed_js::next_asset, next_shared::transforms::ModularizeImportPackageConfig};
 
+/// The maximum depth of the loader tree of a route, unless
+/// `experimental.maxRouteDepth` is set.
+const DEFAULT_MAX_ROUTE_DEPTH: u32 = 50;
+
+/// The maximum number of redirects a request can be sent through, unless
+/// `experimental.maxRedirectDepth` is set.
+const DEFAULT_MAX_REDIRECT_DEPTH: u32 = 10;
+
 #[turbo_tasks::value(serialization = "custom", eq = "manual")]
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -57,10 +65,13 @@ pub struct NextConfig {
     pub images: ImageConfig,
     pub page_extensions: Vec<String>,
     pub react_strict_mode: Option<bool>,
+    pub react_production_profiling: Option<bool>,
+    pub react_max_headers_length: Option<f64>,
     pub rewrites: Rewrites,
     pub transpile_packages: Option<Vec<String>>,
     pub modularize_imports: Option<IndexMap<String, ModularizeImportPackageConfig>>,
     sass_options: Option<serde_json::Value>,
+    generate_etags: bool,
 
     // Partially supported
     pub compiler: Option<CompilerConfig>,
@@ -83,7 +94,6 @@ pub struct NextConfig {
     export_path_map: Option<serde_json::Value>,
     // this is a function in js land
     generate_build_id: Option<serde_json::Value>,
-    generate_etags: bool,
     headers: Vec<Header>,
     http_agent_options: HttpAgentConfig,
     i18n: Option<I18NConfig>,
@@ -92,9 +102,9 @@ pub struct NextConfig {
     output_file_tracing: bool,
     powered_by_header: bool,
     production_browser_source_maps: bool,
-    public_runtime_config: IndexMap<String, serde_json::Value>,
+    pub public_runtime_config: IndexMap<String, serde_json::Value>,
     redirects: Vec<Redirect>,
-    server_runtime_config: IndexMap<String, serde_json::Value>,
+    pub server_runtime_config: IndexMap<String, serde_json::Value>,
     static_page_generation_timeout: f64,
     swc_minify: bool,
     target: Option<String>,
@@ -242,6 +252,9 @@ pub struct Redirect {
     pub status: RedirectStatus,
 }
 
+#[turbo_tasks::value(transparent)]
+pub struct Redirects(Vec<Redirect>);
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct Rewrite {
@@ -270,7 +283,7 @@ pub struct Rewrites {
 #[serde(rename_all = "camelCase")]
 pub struct TypeScriptConfig {
     pub ignore_build_errors: Option<bool>,
-    pub ts_config_path: Option<String>,
+    pub tsconfig_path: Option<String>,
 }
 
 #[turbo_tasks::value(eq = "manual")]
@@ -289,6 +302,7 @@ pub struct ImageConfig {
     #[serde(rename(deserialize = "dangerouslyAllowSVG"))]
     pub dangerously_allow_svg: bool,
     pub content_security_policy: String,
+    pub content_disposition_type: ContentDispositionType,
     pub remote_patterns: Vec<RemotePattern>,
     pub unoptimized: bool,
 }
@@ -307,6 +321,7 @@ impl Default for ImageConfig {
             formats: vec![ImageFormat::Webp],
             dangerously_allow_svg: false,
             content_security_policy: "".to_string(),
+            content_disposition_type: ContentDispositionType::Inline,
             remote_patterns: vec![],
             unoptimized: false,
         }
@@ -323,6 +338,14 @@ pub enum ImageLoader {
     Custom,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentDispositionType {
+    #[default]
+    Inline,
+    Attachment,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 pub enum ImageFormat {
     #[serde(rename = "image/webp")]
@@ -357,6 +380,63 @@ pub struct ExperimentalTurboConfig {
     pub loaders: Option<JsonValue>,
     pub rules: Option<IndexMap<String, RuleConfigItem>>,
     pub resolve_alias: Option<IndexMap<String, JsonValue>>,
+    pub cache_query_params: Option<CacheQueryParamsConfig>,
+}
+
+/// Controls which query params are part of the cache key of rendered pages.
+/// Patterns match a param name exactly, or by prefix when they end with `*`
+/// (e.g. `utm_*`).
+#[turbo_tasks::value(eq = "manual")]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheQueryParamsConfig {
+    /// When set, only these params are included.
+    pub include: Option<Vec<String>>,
+    /// These params are never included.
+    pub exclude: Option<Vec<String>>,
+}
+
+impl CacheQueryParamsConfig {
+    /// Whether every param is part of the cache key, which is the default.
+    pub fn includes_all(&self) -> bool {
+        self.include.is_none() && self.exclude.is_none()
+    }
+
+    fn includes(&self, name: &str) -> bool {
+        fn matches(patterns: &[String], name: &str) -> bool {
+            patterns
+                .iter()
+                .any(|pattern| match pattern.strip_suffix('*') {
+                    Some(prefix) => name.starts_with(prefix),
+                    None => name == pattern,
+                })
+        }
+
+        self.include
+            .as_ref()
+            .map_or(true, |include| matches(include, name))
+            && !self
+                .exclude
+                .as_ref()
+                .map_or(false, |exclude| matches(exclude, name))
+    }
+
+    /// Removes the params that are not part of the cache key from a raw query
+    /// string. The remaining params are kept as is and in order.
+    pub fn filter_query(&self, raw_query: &str) -> String {
+        if self.includes_all() {
+            return raw_query.to_string();
+        }
+
+        raw_query
+            .split('&')
+            .filter(|param| {
+                let name = param.split('=').next().unwrap_or_default();
+                !param.is_empty() && self.includes(name)
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
@@ -377,6 +457,93 @@ pub enum LoaderItem {
     LoaderOptions(WebpackLoaderItem),
 }
 
+/// Routes requests by the subdomain of their `Host` header.
+#[turbo_tasks::value(eq = "manual")]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TenantRoutingConfig {
+    /// The hosts that belong to a tenant, e.g. `*.example.com`. The `*` matches
+    /// the tenant's subdomain.
+    pub host_pattern: String,
+    /// The path requests of a tenant are rewritten to, e.g.
+    /// `/_tenants/:tenant/:path*`.
+    pub rewrite: Option<String>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionTenantRoutingConfig(Option<TenantRoutingConfig>);
+
+/// Proxies requests that no route matches to an existing server.
+#[turbo_tasks::value(eq = "manual")]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyFallbackConfig {
+    /// The origin of the server, e.g. `http://localhost:4000`.
+    pub upstream: String,
+    /// The paths that are proxied, e.g. `/legacy/*`. A trailing `*` matches
+    /// any suffix. When not set, all unmatched paths are proxied.
+    pub paths: Option<Vec<String>>,
+}
+
+impl ProxyFallbackConfig {
+    pub fn includes(&self, pathname: &str) -> bool {
+        self.paths.as_ref().map_or(true, |paths| {
+            paths.iter().any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => pathname.starts_with(prefix),
+                None => pathname == pattern,
+            })
+        })
+    }
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionProxyFallbackConfig(Option<ProxyFallbackConfig>);
+
+/// Retries chunk loads that failed in the browser.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkLoadRetryConfig {
+    /// How often a failed chunk load is retried, with an exponential backoff.
+    pub retries: u32,
+    /// The time in milliseconds after which a chunk load is considered
+    /// failed. No timeout when not set.
+    pub timeout: Option<u32>,
+}
+
+/// Limits the size of the client chunks each route loads initially.
+#[turbo_tasks::value(eq = "manual")]
+#[derive(Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleBudgetConfig {
+    /// The budget in bytes of every route.
+    pub max_size: Option<u64>,
+    /// The budgets in bytes of individual routes, e.g. `/dashboard`, which
+    /// take precedence over `max_size`.
+    #[serde(default)]
+    pub routes: IndexMap<String, u64>,
+    #[serde(default)]
+    pub severity: BundleBudgetSeverity,
+}
+
+impl BundleBudgetConfig {
+    pub fn budget_for(&self, pathname: &str) -> Option<u64> {
+        self.routes.get(pathname).copied().or(self.max_size)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "kebab-case")]
+pub enum BundleBudgetSeverity {
+    /// Exceeding a budget is reported.
+    #[default]
+    Warning,
+    /// Exceeding a budget fails the build.
+    Error,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionBundleBudgetConfig(Option<BundleBudgetConfig>);
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct ExperimentalConfig {
@@ -389,6 +556,22 @@ pub struct ExperimentalConfig {
     pub isr_flush_to_disk: Option<bool>,
     mdx_rs: Option<bool>,
     pub swc_plugins: Option<Vec<(String, serde_json::Value)>>,
+    fallback_node_polyfills: Option<bool>,
+    pub tenant_routing: Option<TenantRoutingConfig>,
+    pub large_page_data_bytes: Option<f64>,
+    circular_import_warnings: Option<bool>,
+    pub render_timeout: Option<f64>,
+    typed_routes: Option<bool>,
+    max_route_depth: Option<u32>,
+    max_redirect_depth: Option<u32>,
+    esm_externals: Option<EsmExternalsConfig>,
+    pub proxy_fallback: Option<ProxyFallbackConfig>,
+    pub chunk_load_retry: Option<ChunkLoadRetryConfig>,
+    pub bundle_budget: Option<BundleBudgetConfig>,
+    pub scroll_restoration: Option<bool>,
+    pub web_vitals_attribution: Option<Vec<String>>,
+    server_minification: Option<bool>,
+    hidden_source_maps: Option<bool>,
 
     // unsupported
     adjust_font_fallbacks: Option<bool>,
@@ -400,16 +583,13 @@ pub struct ExperimentalConfig {
     disable_optimized_loading: Option<bool>,
     disable_postcss_preset_env: Option<bool>,
     enable_undici: Option<bool>,
-    esm_externals: Option<serde_json::Value>,
     external_dir: Option<bool>,
-    fallback_node_polyfills: Option<bool>,
     fetch_cache: Option<bool>,
     font_loaders: Option<serde_json::Value>,
     force_swc_transforms: Option<bool>,
     fully_specified: Option<bool>,
     gzip_size: Option<bool>,
     incremental_cache_handler_path: Option<String>,
-    large_page_data_bytes: Option<f64>,
     legacy_browsers: Option<bool>,
     manual_client_base_path: Option<bool>,
     middleware_prefetch: Option<MiddlewarePrefetchType>,
@@ -423,7 +603,6 @@ pub struct ExperimentalConfig {
     profiling: Option<bool>,
     proxy_timeout: Option<f64>,
     runtime: Option<serde_json::Value>,
-    scroll_restoration: Option<bool>,
     shared_pool: Option<bool>,
     skip_middleware_url_normalize: Option<bool>,
     skip_trailing_slash_redirect: Option<bool>,
@@ -435,10 +614,18 @@ pub struct ExperimentalConfig {
     transpile_packages: Option<Vec<String>>,
     turbotrace: Option<serde_json::Value>,
     url_imports: Option<serde_json::Value>,
-    web_vitals_attribution: Option<serde_json::Value>,
     worker_threads: Option<bool>,
 }
 
+impl ExperimentalConfig {
+    /// Server output is minified unless `experimental.serverMinification` is
+    /// explicitly disabled. The default config leaves it unset, as webpack
+    /// only minifies server output when it is enabled.
+    fn server_minification(&self) -> bool {
+        self.server_minification.unwrap_or(true)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "kebab-case")]
 enum MiddlewarePrefetchType {
@@ -446,6 +633,32 @@ enum MiddlewarePrefetchType {
     Flexible,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(untagged)]
+enum EsmExternalsConfig {
+    Boolean(bool),
+    Loose(LooseEsmExternals),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "kebab-case")]
+enum LooseEsmExternals {
+    Loose,
+}
+
+/// How external packages are loaded on the server, see
+/// `experimental.esmExternals`.
+#[turbo_tasks::value]
+#[derive(Clone, Copy, Debug)]
+pub enum EsmExternals {
+    /// `true`: packages that resolve to ESM are bundled, the others are
+    /// loaded with `require`.
+    Esm,
+    /// `false`: packages are loaded with `require` from their CommonJS entry,
+    /// even when they would resolve to ESM.
+    Cjs,
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(untagged)]
 pub enum EmotionTransformOptionsOrBoolean {
@@ -540,16 +753,76 @@ impl NextConfigVc {
         Ok(self.await?.images.clone().cell())
     }
 
+    #[turbo_tasks::function]
+    pub async fn cache_query_params(self) -> Result<CacheQueryParamsConfigVc> {
+        Ok(self
+            .await?
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|turbo| turbo.cache_query_params.clone())
+            .unwrap_or_default()
+            .cell())
+    }
+
+    #[turbo_tasks::function]
+    pub async fn tenant_routing(self) -> Result<OptionTenantRoutingConfigVc> {
+        Ok(OptionTenantRoutingConfigVc::cell(
+            self.await?.experimental.tenant_routing.clone(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn proxy_fallback(self) -> Result<OptionProxyFallbackConfigVc> {
+        Ok(OptionProxyFallbackConfigVc::cell(
+            self.await?.experimental.proxy_fallback.clone(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn bundle_budget(self) -> Result<OptionBundleBudgetConfigVc> {
+        Ok(OptionBundleBudgetConfigVc::cell(
+            self.await?.experimental.bundle_budget.clone(),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn page_extensions(self) -> Result<StringsVc> {
         Ok(StringsVc::cell(self.await?.page_extensions.clone()))
     }
 
+    /// Whether production builds should use the profiling build of
+    /// `react-dom`, so that the `<Profiler>` API keeps working.
+    #[turbo_tasks::function]
+    pub async fn react_production_profiling(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?.react_production_profiling.unwrap_or(false),
+        ))
+    }
+
+    /// The build id returned by `generateBuildId`. The config loader calls the
+    /// function, so only its result is available here.
+    #[turbo_tasks::function]
+    pub async fn generate_build_id(self) -> Result<OptionStringVc> {
+        Ok(OptionStringVc::cell(
+            self.await?
+                .generate_build_id
+                .as_ref()
+                .and_then(|build_id| build_id.as_str())
+                .map(ToOwned::to_owned),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn rewrites(self) -> Result<RewritesVc> {
         Ok(self.await?.rewrites.clone().cell())
     }
 
+    #[turbo_tasks::function]
+    pub async fn redirects(self) -> Result<RedirectsVc> {
+        Ok(RedirectsVc::cell(self.await?.redirects.clone()))
+    }
+
     #[turbo_tasks::function]
     pub async fn transpile_packages(self) -> Result<StringsVc> {
         Ok(StringsVc::cell(
@@ -627,12 +900,144 @@ impl NextConfigVc {
         ))
     }
 
+    /// Whether Node.js builtins should be polyfilled in browser bundles. Set
+    /// `experimental.fallbackNodePolyfills: false` to replace them with empty
+    /// modules instead.
+    #[turbo_tasks::function]
+    pub async fn fallback_node_polyfills(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?
+                .experimental
+                .fallback_node_polyfills
+                .unwrap_or(true),
+        ))
+    }
+
+    /// Whether import cycles between the project's modules should be reported
+    /// as warnings.
+    #[turbo_tasks::function]
+    pub async fn circular_import_warnings(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?
+                .experimental
+                .circular_import_warnings
+                .unwrap_or(false),
+        ))
+    }
+
+    /// Whether type definitions for the routes of `next/link` should be
+    /// generated.
+    #[turbo_tasks::function]
+    pub async fn typed_routes(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?.experimental.typed_routes.unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn esm_externals(self) -> Result<EsmExternalsVc> {
+        Ok(match self.await?.experimental.esm_externals {
+            // `'loose'` is reported when the config is loaded.
+            None
+            | Some(EsmExternalsConfig::Boolean(true))
+            | Some(EsmExternalsConfig::Loose(LooseEsmExternals::Loose)) => EsmExternals::Esm,
+            Some(EsmExternalsConfig::Boolean(false)) => EsmExternals::Cjs,
+        }
+        .cell())
+    }
+
+    /// The maximum depth of the loader tree of an app directory route.
+    #[turbo_tasks::function]
+    pub async fn max_route_depth(self) -> Result<U32Vc> {
+        Ok(U32Vc::cell(
+            self.await?
+                .experimental
+                .max_route_depth
+                .unwrap_or(DEFAULT_MAX_ROUTE_DEPTH),
+        ))
+    }
+
+    /// The maximum number of redirects a request can be sent through.
+    #[turbo_tasks::function]
+    pub async fn max_redirect_depth(self) -> Result<U32Vc> {
+        Ok(U32Vc::cell(
+            self.await?
+                .experimental
+                .max_redirect_depth
+                .unwrap_or(DEFAULT_MAX_REDIRECT_DEPTH),
+        ))
+    }
+
     #[turbo_tasks::function]
     pub async fn sass_config(self) -> Result<JsonValueVc> {
         Ok(JsonValueVc::cell(
             self.await?.sass_options.clone().unwrap_or_default(),
         ))
     }
+
+    #[turbo_tasks::function]
+    pub async fn generate_etags(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(self.await?.generate_etags))
+    }
+
+    /// Whether the server output of `next build` is minified.
+    #[turbo_tasks::function]
+    pub async fn server_minification(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(self.await?.experimental.server_minification()))
+    }
+
+    /// Whether the source maps of client chunks are emitted without being
+    /// referenced by the chunks.
+    #[turbo_tasks::function]
+    pub async fn hidden_source_maps(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?.experimental.hidden_source_maps.unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn dist_dir(self) -> Result<StringVc> {
+        Ok(StringVc::cell(self.await?.dist_dir.clone()))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn clean_dist_dir(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(self.await?.clean_dist_dir))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn eslint_ignore_during_builds(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?.eslint.ignore_during_builds.unwrap_or(false),
+        ))
+    }
+
+    /// The directories that are linted during builds, relative to the project.
+    #[turbo_tasks::function]
+    pub async fn eslint_dirs(self) -> Result<StringsVc> {
+        Ok(StringsVc::cell(
+            self.await?.eslint.dirs.clone().unwrap_or_else(|| {
+                ["app", "pages", "components", "lib", "src"]
+                    .into_iter()
+                    .map(ToOwned::to_owned)
+                    .collect()
+            }),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn typescript_ignore_build_errors(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?.typescript.ignore_build_errors.unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn typescript_tsconfig_path(self) -> Result<OptionStringVc> {
+        Ok(OptionStringVc::cell(
+            self.await?.typescript.tsconfig_path.clone(),
+        ))
+    }
 }
 
 fn next_configs() -> StringsVc {
@@ -730,6 +1135,15 @@ Example: loaders: { \".mdx\": [\"mdx-loader\"] } -> rules: { \"*.mdx\": [\"mdx-l
         }
     }
 
+    if let Some(EsmExternalsConfig::Loose(_)) = next_config.experimental.esm_externals {
+        LooseEsmExternalsIssue {
+            path: config_file.unwrap_or(project_path),
+        }
+        .cell()
+        .as_issue()
+        .emit()
+    }
+
     Ok(next_config.cell())
 }
 
@@ -779,3 +1193,107 @@ impl Issue for OutdatedConfigIssue {
         StringVc::cell(self.description.to_string())
     }
 }
+
+/// `experimental.esmExternals: 'loose'` lets CommonJS modules require ESM
+/// externals in Next.js. ESM packages are always bundled here, so it's treated
+/// like `true`.
+#[turbo_tasks::value]
+struct LooseEsmExternalsIssue {
+    path: FileSystemPathVc,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for LooseEsmExternalsIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("\"experimental.esmExternals: 'loose'\" is not supported".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(
+            "Turbopack bundles packages that resolve to ESM instead of loading them as externals, \
+             so they can always be required from CommonJS. The option is handled like \
+             `esmExternals: true`."
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheQueryParamsConfig, ExperimentalConfig, ProxyFallbackConfig};
+
+    #[test]
+    fn test_server_minification_default() {
+        let config: ExperimentalConfig = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(config.server_minification());
+
+        let config: ExperimentalConfig =
+            serde_json::from_value(serde_json::json!({ "serverMinification": false })).unwrap();
+        assert!(!config.server_minification());
+    }
+
+    #[test]
+    fn test_cache_query_params_default() {
+        let config = CacheQueryParamsConfig::default();
+        assert_eq!(config.filter_query("a=1&utm_source=x"), "a=1&utm_source=x");
+    }
+
+    #[test]
+    fn test_cache_query_params_exclude() {
+        let config = CacheQueryParamsConfig {
+            include: None,
+            exclude: Some(vec!["utm_*".to_string(), "fbclid".to_string()]),
+        };
+        assert_eq!(
+            config.filter_query("utm_source=x&a=1&fbclid=y&utm_medium=z&b"),
+            "a=1&b"
+        );
+    }
+
+    #[test]
+    fn test_cache_query_params_include() {
+        let config = CacheQueryParamsConfig {
+            include: Some(vec!["page".to_string(), "filter_*".to_string()]),
+            exclude: Some(vec!["filter_debug".to_string()]),
+        };
+        assert_eq!(
+            config.filter_query("page=2&sort=asc&filter_color=red&filter_debug=1"),
+            "page=2&filter_color=red"
+        );
+    }
+
+    #[test]
+    fn test_proxy_fallback_paths() {
+        let config = ProxyFallbackConfig {
+            upstream: "http://localhost:4000".to_string(),
+            paths: None,
+        };
+        assert!(config.includes("/anything"));
+
+        let config = ProxyFallbackConfig {
+            upstream: "http://localhost:4000".to_string(),
+            paths: Some(vec!["/legacy/*".to_string(), "/contact".to_string()]),
+        };
+        assert!(config.includes("/legacy/about"));
+        assert!(config.includes("/contact"));
+        assert!(!config.includes("/contact/form"));
+        assert!(!config.includes("/about"));
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use turbo_tasks::Value;
 use turbopack_binding::{
-    turbo::tasks_fs::FileSystemPathVc,
+    turbo::{tasks_env::ProcessEnvVc, tasks_fs::FileSystemPathVc},
     turbopack::{
         core::{
             compile_time_defines,
@@ -14,21 +14,29 @@ use turbopack_binding::{
             },
             free_var_references,
         },
+        env::ProcessEnvAssetVc,
         node::execution_context::ExecutionContextVc,
         turbopack::resolve_options_context::{ResolveOptionsContext, ResolveOptionsContextVc},
     },
 };
 
 use crate::{
-    next_config::NextConfigVc, next_import_map::get_next_edge_import_map,
+    env::env_for_js,
+    mode::NextMode,
+    next_client::{RuntimeEntriesVc, RuntimeEntry},
+    next_config::NextConfigVc,
+    next_import_map::{get_next_edge_fallback_import_map, get_next_edge_import_map},
     next_server::context::ServerContextType,
-    next_shared::resolve::UnsupportedModulesResolvePluginVc, util::foreign_code_context_condition,
+    next_shared::resolve::{
+        AssetQueryResolvePluginVc, NativeModulesResolvePluginVc, UnsupportedModulesResolvePluginVc,
+    },
+    util::foreign_code_context_condition,
 };
 
-fn defines() -> CompileTimeDefines {
+fn defines(mode: NextMode) -> CompileTimeDefines {
     compile_time_defines!(
         process.turbopack = true,
-        process.env.NODE_ENV = "development",
+        process.env.NODE_ENV = mode.node_env(),
         process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
         process.env.NEXT_RUNTIME = "edge"
     )
@@ -37,14 +45,14 @@ fn defines() -> CompileTimeDefines {
 }
 
 #[turbo_tasks::function]
-fn next_edge_defines() -> CompileTimeDefinesVc {
-    defines().cell()
+fn next_edge_defines(mode: NextMode) -> CompileTimeDefinesVc {
+    defines(mode).cell()
 }
 
 #[turbo_tasks::function]
-fn next_edge_free_vars(project_path: FileSystemPathVc) -> FreeVarReferencesVc {
+fn next_edge_free_vars(project_path: FileSystemPathVc, mode: NextMode) -> FreeVarReferencesVc {
     free_var_references!(
-        ..defines().into_iter(),
+        ..defines(mode).into_iter(),
         Buffer = FreeVarReference::EcmaScriptModule {
             request: "next/dist/compiled/buffer".to_string(),
             context: Some(project_path),
@@ -62,20 +70,38 @@ fn next_edge_free_vars(project_path: FileSystemPathVc) -> FreeVarReferencesVc {
 #[turbo_tasks::function]
 pub fn get_edge_compile_time_info(
     project_path: FileSystemPathVc,
+    mode: NextMode,
     server_addr: ServerAddrVc,
 ) -> CompileTimeInfoVc {
     CompileTimeInfo::builder(EnvironmentVc::new(Value::new(
         ExecutionEnvironment::EdgeWorker(EdgeWorkerEnvironment { server_addr }.into()),
     )))
-    .defines(next_edge_defines())
-    .free_var_references(next_edge_free_vars(project_path))
+    .defines(next_edge_defines(mode))
+    .free_var_references(next_edge_free_vars(project_path, mode))
     .cell()
 }
 
+/// Edge functions can't read the server's `process.env`, so the env vars that
+/// are inlined for the edge runtime (`NEXT_PUBLIC_*` and the `env` from
+/// next.config.js) are injected before the entry is evaluated, like on the
+/// client.
+#[turbo_tasks::function]
+pub fn get_edge_runtime_entries(
+    project_path: FileSystemPathVc,
+    env: ProcessEnvVc,
+    next_config: NextConfigVc,
+) -> RuntimeEntriesVc {
+    RuntimeEntriesVc::cell(vec![RuntimeEntry::Source(
+        ProcessEnvAssetVc::new(project_path, env_for_js(env, true, next_config)).into(),
+    )
+    .cell()])
+}
+
 #[turbo_tasks::function]
 pub async fn get_edge_resolve_options_context(
     project_path: FileSystemPathVc,
     ty: Value<ServerContextType>,
+    mode: NextMode,
     next_config: NextConfigVc,
     execution_context: ExecutionContextVc,
 ) -> Result<ResolveOptionsContextVc> {
@@ -88,12 +114,18 @@ pub async fn get_edge_resolve_options_context(
         custom_conditions: vec![
             "edge-light".to_string(),
             "worker".to_string(),
-            "development".to_string(),
+            mode.node_env().to_string(),
         ],
         import_map: Some(next_edge_import_map),
+        fallback_import_map: Some(get_next_edge_fallback_import_map(project_path)),
         module: true,
+        enable_mjs_extension: true,
         browser: true,
-        plugins: vec![UnsupportedModulesResolvePluginVc::new(project_path).into()],
+        plugins: vec![
+            AssetQueryResolvePluginVc::new(project_path).into(),
+            UnsupportedModulesResolvePluginVc::new(project_path).into(),
+            NativeModulesResolvePluginVc::new(project_path, true).into(),
+        ],
         ..Default::default()
     };
 
@@ -23,7 +23,7 @@ use turbopack_binding::{
     },
 };
 
-use crate::embed_js::next_js_file_path;
+use crate::{embed_js::next_js_file_path, next_client::RuntimeEntriesVc};
 
 /// Transition into edge environment to render an app directory page.
 ///
@@ -36,6 +36,7 @@ pub struct NextEdgePageTransition {
     pub edge_chunking_context: ChunkingContextVc,
     pub edge_module_options_context: Option<ModuleOptionsContextVc>,
     pub edge_resolve_options_context: ResolveOptionsContextVc,
+    pub runtime_entries: RuntimeEntriesVc,
     pub output_path: FileSystemPathVc,
     pub bootstrap_asset: SourceVc,
 }
@@ -89,11 +90,13 @@ impl Transition for NextEdgePageTransition {
             bail!("Internal module is not chunkable");
         };
 
+        let runtime_entries = self.runtime_entries.resolve_entries(context.into());
+
         let asset = ChunkGroupFilesAsset {
             module,
             client_root: self.output_path,
             chunking_context: self.edge_chunking_context,
-            runtime_entries: None,
+            runtime_entries: Some(runtime_entries),
         };
 
         Ok(asset.cell().into())
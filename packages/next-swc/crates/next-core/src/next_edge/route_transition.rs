@@ -17,7 +17,10 @@ use turbopack_binding::{
     },
 };
 
-use crate::bootstrap::{route_bootstrap, BootstrapConfigVc};
+use crate::{
+    bootstrap::{route_bootstrap, BootstrapConfigVc},
+    next_client::RuntimeEntriesVc,
+};
 
 #[turbo_tasks::value(shared)]
 pub struct NextEdgeRouteTransition {
@@ -25,6 +28,7 @@ pub struct NextEdgeRouteTransition {
     pub edge_chunking_context: ChunkingContextVc,
     pub edge_module_options_context: Option<ModuleOptionsContextVc>,
     pub edge_resolve_options_context: ResolveOptionsContextVc,
+    pub runtime_entries: RuntimeEntriesVc,
     pub output_path: FileSystemPathVc,
     pub base_path: FileSystemPathVc,
     pub bootstrap_asset: SourceVc,
@@ -73,11 +77,13 @@ impl Transition for NextEdgeRouteTransition {
             }),
         );
 
+        let runtime_entries = self.runtime_entries.resolve_entries(context.into());
+
         let asset = ChunkGroupFilesAsset {
             module: new_asset.into(),
             client_root: self.output_path,
             chunking_context: self.edge_chunking_context,
-            runtime_entries: None,
+            runtime_entries: Some(runtime_entries),
         };
 
         Ok(asset.cell().into())
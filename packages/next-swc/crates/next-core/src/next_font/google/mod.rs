@@ -1,9 +1,14 @@
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::HashMap,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::{bail, Context, Result};
 use futures::FutureExt;
 use indexmap::IndexMap;
 use indoc::formatdoc;
+use once_cell::sync::Lazy;
 use turbopack_binding::{
     turbo::{
         tasks::{
@@ -12,11 +17,12 @@ use turbopack_binding::{
         },
         tasks_bytes::stream::SingleValue,
         tasks_env::{CommandLineProcessEnvVc, ProcessEnv},
-        tasks_fetch::fetch,
+        tasks_fetch::{FetchError, FetchErrorKind, FetchErrorVc},
         tasks_fs::{
-            json::parse_json_with_source_context, DiskFileSystemVc, File, FileContent, FileSystem,
-            FileSystemPathVc,
+            json::parse_json_with_source_context, to_sys_path, DiskFileSystemVc, File, FileContent,
+            FileSystem, FileSystemPathOptionVc, FileSystemPathVc,
         },
+        tasks_hash::{encode_hex, hash_xxh3_hash64},
     },
     turbopack::{
         core::{
@@ -48,7 +54,7 @@ use self::{
     font_fallback::get_font_fallback,
     options::{options_from_request, FontDataEntry, FontWeights, NextFontGoogleOptionsVc},
     stylesheet::build_stylesheet,
-    util::{get_font_axes, get_stylesheet_url},
+    util::{get_font_axes, get_stylesheet_url, scope_font_face_families},
 };
 use super::{
     font_fallback::{FontFallback, FontFallbackVc},
@@ -57,7 +63,7 @@ use super::{
         FontCssPropertiesVc, FontFamilyType,
     },
 };
-use crate::{embed_js::next_js_file_path, util::load_next_json};
+use crate::{embed_js::next_js_file_path, next_config::NextConfigVc, util::load_next_json};
 
 pub mod font_fallback;
 pub mod options;
@@ -72,6 +78,15 @@ pub const GOOGLE_FONTS_STYLESHEET_URL: &str = "https://fonts.googleapis.com/css2
 pub const USER_AGENT_FOR_GOOGLE_FONTS: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) \
                                                AppleWebKit/537.36 (KHTML, like Gecko) \
                                                Chrome/104.0.0.0 Safari/537.36";
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+/// How often fetching a stylesheet is attempted before giving up.
+const FETCH_ATTEMPTS: u32 = 3;
+/// The delay before the first retry, which doubles with every further retry.
+const FETCH_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+/// How long a cached stylesheet is used before it is fetched again, unless
+/// `NEXT_FONT_GOOGLE_CACHE_MAX_AGE` is set (in seconds).
+const DEFAULT_STYLESHEET_CACHE_MAX_AGE_SECONDS: u64 = 24 * 60 * 60;
 
 #[turbo_tasks::value(transparent)]
 struct FontData(IndexMap<String, FontDataEntry>);
@@ -171,15 +186,21 @@ impl ImportMappingReplacement for NextFontGoogleReplacer {
 pub struct NextFontGoogleCssModuleReplacer {
     project_path: FileSystemPathVc,
     execution_context: ExecutionContextVc,
+    next_config: NextConfigVc,
 }
 
 #[turbo_tasks::value_impl]
 impl NextFontGoogleCssModuleReplacerVc {
     #[turbo_tasks::function]
-    pub fn new(project_path: FileSystemPathVc, execution_context: ExecutionContextVc) -> Self {
+    pub fn new(
+        project_path: FileSystemPathVc,
+        execution_context: ExecutionContextVc,
+        next_config: NextConfigVc,
+    ) -> Self {
         Self::cell(NextFontGoogleCssModuleReplacer {
             project_path,
             execution_context,
+            next_config,
         })
     }
 }
@@ -230,10 +251,24 @@ impl ImportMappingReplacement for NextFontGoogleCssModuleReplacer {
         // requests to Google Fonts.
         let env = CommandLineProcessEnvVc::new().as_process_env();
         let mocked_responses_path = &*env.read("NEXT_FONT_GOOGLE_MOCKED_RESPONSES").await?;
+        let cache_max_age = match &*env.read("NEXT_FONT_GOOGLE_CACHE_MAX_AGE").await? {
+            Some(max_age) => max_age
+                .parse()
+                .context("NEXT_FONT_GOOGLE_CACHE_MAX_AGE must be a number of seconds")?,
+            None => DEFAULT_STYLESHEET_CACHE_MAX_AGE_SECONDS,
+        };
         let stylesheet_str = mocked_responses_path
             .as_ref()
             .map_or_else(
-                || fetch_real_stylesheet(stylesheet_url, css_virtual_path).boxed(),
+                || {
+                    fetch_real_stylesheet(
+                        stylesheet_url,
+                        css_virtual_path,
+                        get_stylesheet_cache_dir(self.project_path, self.next_config),
+                        cache_max_age,
+                    )
+                    .boxed()
+                },
                 |p| get_mock_stylesheet(stylesheet_url, p, self.execution_context).boxed(),
             )
             .await?;
@@ -285,12 +320,11 @@ async fn update_google_stylesheet(
     options: NextFontGoogleOptionsVc,
     scoped_font_family: StringVc,
 ) -> Result<StringVc> {
-    // Update font-family definitions to the scoped name
-    // TODO: Do this more resiliently, e.g. transforming an swc ast
-    Ok(StringVc::cell(stylesheet.await?.replace(
-        &format!("font-family: '{}';", &*options.await?.font_family),
-        &format!("font-family: '{}';", &*scoped_font_family.await?),
-    )))
+    Ok(StringVc::cell(scope_font_face_families(
+        &stylesheet.await?,
+        &options.await?.font_family,
+        &scoped_font_family.await?,
+    )?))
 }
 
 #[turbo_tasks::function]
@@ -387,18 +421,106 @@ async fn font_options_from_query_map(
         .map(|o| NextFontGoogleOptionsVc::new(Value::new(o)))
 }
 
+/// A stylesheet response from Google Fonts that is stored on disk, so it
+/// doesn't have to be fetched again when the server is restarted.
+#[turbo_tasks::value(shared)]
+#[derive(Clone)]
+struct CachedStylesheet {
+    /// When the stylesheet was fetched, in seconds since the Unix epoch.
+    timestamp: u64,
+    body: String,
+}
+
+#[turbo_tasks::value(transparent)]
+struct OptionCachedStylesheet(Option<CachedStylesheet>);
+
+#[turbo_tasks::function]
+async fn read_cached_stylesheet(cache_path: FileSystemPathVc) -> Result<OptionCachedStylesheetVc> {
+    let FileContent::Content(file) = &*cache_path.read().await? else {
+        return Ok(OptionCachedStylesheetVc::cell(None));
+    };
+    // A cache entry that can't be parsed is fetched again and overwritten.
+    Ok(OptionCachedStylesheetVc::cell(
+        serde_json::from_str(&file.content().to_str()?).ok(),
+    ))
+}
+
+/// Returns `<distDir>/cache/google-fonts`, in which stylesheets are cached, if
+/// the project is on disk. The directory is a file system of its own that
+/// isn't watched, so writing a cache entry doesn't invalidate the task that
+/// read it, unlike writing to the project file system.
+#[turbo_tasks::function]
+async fn get_stylesheet_cache_dir(
+    project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
+) -> Result<FileSystemPathOptionVc> {
+    let Some(project_dir) = to_sys_path(project_path).await? else {
+        return Ok(FileSystemPathOptionVc::cell(None));
+    };
+    let cache_dir = project_dir
+        .join(&*next_config.dist_dir().await?)
+        .join("cache")
+        .join("google-fonts");
+    Ok(FileSystemPathOptionVc::cell(Some(
+        DiskFileSystemVc::new(
+            "google-fonts-cache".to_string(),
+            cache_dir.to_string_lossy().to_string(),
+        )
+        .as_file_system()
+        .root(),
+    )))
+}
+
+/// Cache entries are keyed by the stylesheet URL and the user agent, which
+/// Google Fonts varies responses on.
+#[turbo_tasks::function]
+async fn get_stylesheet_cache_path(
+    cache_dir: FileSystemPathVc,
+    stylesheet_url: StringVc,
+) -> Result<FileSystemPathVc> {
+    let key = format!("{}\n{}", USER_AGENT_FOR_GOOGLE_FONTS, stylesheet_url.await?);
+    Ok(cache_dir.join(&format!("{}.json", encode_hex(hash_xxh3_hash64(key)))))
+}
+
+/// Fetches the stylesheet from Google Fonts, unless a cached response that is
+/// younger than `cache_max_age` seconds exists. When the fetch fails, e.g.
+/// when offline, an older cached response is used instead.
 async fn fetch_real_stylesheet(
     stylesheet_url: StringVc,
     css_virtual_path: FileSystemPathVc,
+    cache_dir: FileSystemPathOptionVc,
+    cache_max_age: u64,
 ) -> Result<Option<StringVc>> {
-    let stylesheet = fetch(
-        stylesheet_url,
-        OptionStringVc::cell(Some(USER_AGENT_FOR_GOOGLE_FONTS.to_owned())),
-    )
-    .await?;
+    let cache_path = match *cache_dir.await? {
+        Some(cache_dir) => Some(get_stylesheet_cache_path(cache_dir, stylesheet_url)),
+        None => None,
+    };
+    let cached = match cache_path {
+        Some(cache_path) => read_cached_stylesheet(cache_path).await?.clone_value(),
+        None => None,
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    if let Some(cached) = &cached {
+        if now.saturating_sub(cached.timestamp) < cache_max_age {
+            return Ok(Some(StringVc::cell(cached.body.clone())));
+        }
+    }
 
-    Ok(match &*stylesheet {
-        Ok(r) => Some(r.await?.body.to_string()),
+    Ok(match fetch_with_retries(stylesheet_url).await? {
+        Ok(body) => {
+            if let Some(cache_path) = cache_path {
+                let cached = CachedStylesheet {
+                    timestamp: now,
+                    body: body.clone(),
+                };
+                cache_path
+                    .write(FileContent::Content(File::from(serde_json::to_string(&cached)?)).cell())
+                    .await?;
+            }
+            Some(StringVc::cell(body))
+        }
+        // A stale response is better than no stylesheet at all.
+        Err(_) if cached.is_some() => cached.map(|cached| StringVc::cell(cached.body)),
         Err(err) => {
             // Inform the user of the failure to retreive the stylesheet, but don't
             // propagate this error. We don't want e.g. offline connections to prevent page
@@ -416,6 +538,60 @@ async fn fetch_real_stylesheet(
     })
 }
 
+/// Fetches the stylesheet, retrying failed connections and server errors with
+/// an exponential backoff. Client errors, e.g. for an unknown font family,
+/// aren't retried.
+///
+/// The requests are sent directly rather than through the `fetch` turbo-tasks
+/// function, which would return its cached result for every retry.
+async fn fetch_with_retries(stylesheet_url: StringVc) -> Result<Result<String, FetchErrorVc>> {
+    let url = stylesheet_url.await?;
+    let mut attempt = 1;
+    loop {
+        let response = CLIENT
+            .get(url.as_str())
+            .header("User-Agent", USER_AGENT_FOR_GOOGLE_FONTS)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        let error = match response {
+            Ok(response) => match response.text().await {
+                Ok(body) => return Ok(Ok(body)),
+                Err(err) => err,
+            },
+            Err(err) => err,
+        };
+        let is_client_error = error
+            .status()
+            .map_or(false, |status| status.is_client_error());
+        if is_client_error || attempt == FETCH_ATTEMPTS {
+            return Ok(Err(fetch_error(&error, stylesheet_url)));
+        }
+        tokio::time::sleep(FETCH_RETRY_BASE_DELAY * 2u32.pow(attempt - 1)).await;
+        attempt += 1;
+    }
+}
+
+/// Converts a failed request into the error `fetch` would have returned, so
+/// it's reported the same way.
+fn fetch_error(error: &reqwest::Error, url: StringVc) -> FetchErrorVc {
+    let kind = if error.is_connect() {
+        FetchErrorKind::Connect
+    } else if error.is_timeout() {
+        FetchErrorKind::Timeout
+    } else if let Some(status) = error.status() {
+        FetchErrorKind::Status(status.as_u16())
+    } else {
+        FetchErrorKind::Other
+    };
+    FetchError {
+        url,
+        kind: kind.cell(),
+        detail: StringVc::cell(error.to_string()),
+    }
+    .cell()
+}
+
 async fn get_mock_stylesheet(
     stylesheet_url: StringVc,
     mocked_responses_path: &str,
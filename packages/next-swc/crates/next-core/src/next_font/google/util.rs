@@ -1,7 +1,15 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, ops::Range};
 
 use anyhow::{anyhow, bail, Context, Result};
 use indexmap::{indexset, IndexSet};
+use swc_core::{
+    common::{sync::Lrc, BytePos, FileName, SourceMap, Span},
+    css::{
+        ast::{AtRule, AtRuleName, ComponentValue, Declaration, DeclarationName, Stylesheet},
+        parser::{parse_file, parser::ParserConfig},
+        visit::{Visit, VisitWith},
+    },
+};
 
 use super::options::{FontData, FontWeights};
 
@@ -227,6 +235,90 @@ pub(super) fn get_stylesheet_url(
     }
 }
 
+/// Replaces the unscoped font family in the `font-family` descriptors of the
+/// `@font-face` rules of a Google Fonts stylesheet with the scoped font
+/// family. Everything else, including comments and `local()` sources that
+/// mention the font family, is kept as is.
+pub(super) fn scope_font_face_families(
+    stylesheet: &str,
+    font_family: &str,
+    scoped_font_family: &str,
+) -> Result<String> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, stylesheet.to_string());
+    let mut errors = vec![];
+    let Ok(parsed) = parse_file::<Stylesheet>(&fm, None, ParserConfig::default(), &mut errors)
+    else {
+        bail!("Failed to parse the Google Fonts stylesheet");
+    };
+
+    let mut visitor = FontFaceFamilyVisitor {
+        start_pos: fm.start_pos,
+        font_family,
+        in_font_face: false,
+        declarations: vec![],
+    };
+    parsed.visit_with(&mut visitor);
+
+    let mut scoped = String::with_capacity(stylesheet.len());
+    let mut last_end = 0;
+    for range in visitor.declarations {
+        scoped.push_str(&stylesheet[last_end..range.start]);
+        scoped.push_str(&format!("font-family: '{}'", scoped_font_family));
+        last_end = range.end;
+    }
+    scoped.push_str(&stylesheet[last_end..]);
+    Ok(scoped)
+}
+
+/// Collects the byte ranges of the `font-family` declarations in `@font-face`
+/// rules whose value is the given font family.
+struct FontFaceFamilyVisitor<'a> {
+    start_pos: BytePos,
+    font_family: &'a str,
+    in_font_face: bool,
+    declarations: Vec<Range<usize>>,
+}
+
+impl FontFaceFamilyVisitor<'_> {
+    fn range(&self, span: Span) -> Range<usize> {
+        (span.lo - self.start_pos).0 as usize..(span.hi - self.start_pos).0 as usize
+    }
+}
+
+impl Visit for FontFaceFamilyVisitor<'_> {
+    fn visit_at_rule(&mut self, at_rule: &AtRule) {
+        let is_font_face = matches!(
+            &at_rule.name,
+            AtRuleName::Ident(name) if name.value.eq_ignore_ascii_case("font-face")
+        );
+        let in_font_face = std::mem::replace(&mut self.in_font_face, is_font_face);
+        at_rule.visit_children_with(self);
+        self.in_font_face = in_font_face;
+    }
+
+    fn visit_declaration(&mut self, declaration: &Declaration) {
+        if !self.in_font_face {
+            return;
+        }
+        let DeclarationName::Ident(name) = &declaration.name else {
+            return;
+        };
+        if !name.value.eq_ignore_ascii_case("font-family") {
+            return;
+        }
+        let family = match &declaration.value[..] {
+            [ComponentValue::Str(family)] => &*family.value,
+            [ComponentValue::Ident(family)] => &*family.value,
+            _ => return,
+        };
+        if family == self.font_family {
+            let range = self.range(declaration.span);
+            self.declarations.push(range);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
@@ -236,7 +328,7 @@ mod tests {
     use super::get_font_axes;
     use crate::next_font::google::{
         options::{FontData, FontWeights},
-        util::{get_stylesheet_url, FontAxes, FontStyle},
+        util::{get_stylesheet_url, scope_font_face_families, FontAxes, FontStyle},
         GOOGLE_FONTS_STYLESHEET_URL,
     };
 
@@ -548,4 +640,48 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_scope_font_face_families() -> Result<()> {
+        let stylesheet = r#"/* latin */
+@font-face {
+  font-family: 'Inter';
+  font-style: normal;
+  src: local('Inter'), url(https://fonts.gstatic.com/s/inter/v12/a.woff2) format('woff2');
+}
+@font-face{font-family:"Inter";font-weight:700}
+@font-face {
+  font-family: Inter;
+}
+@font-face {
+  font-family: 'Inter Tight';
+}
+.inter {
+  font-family: 'Inter';
+}
+"#;
+
+        assert_eq!(
+            scope_font_face_families(stylesheet, "Inter", "__Inter_123456")?,
+            r#"/* latin */
+@font-face {
+  font-family: '__Inter_123456';
+  font-style: normal;
+  src: local('Inter'), url(https://fonts.gstatic.com/s/inter/v12/a.woff2) format('woff2');
+}
+@font-face{font-family: '__Inter_123456';font-weight:700}
+@font-face {
+  font-family: '__Inter_123456';
+}
+@font-face {
+  font-family: 'Inter Tight';
+}
+.inter {
+  font-family: 'Inter';
+}
+"#
+        );
+
+        Ok(())
+    }
 }
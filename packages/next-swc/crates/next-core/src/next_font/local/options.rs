@@ -5,8 +5,8 @@ use serde::{Deserialize, Serialize};
 use turbopack_binding::turbo::tasks::{primitives::StringVc, trace::TraceRawVcs, Value};
 
 use super::request::{
-    AdjustFontFallback, NextFontLocalRequest, NextFontLocalRequestArguments, SrcDescriptor,
-    SrcRequest,
+    AdjustFontFallback, FontFaceDeclaration, NextFontLocalRequest, NextFontLocalRequestArguments,
+    SrcDescriptor, SrcRequest,
 };
 
 /// A normalized, Vc-friendly struct derived from validating and transforming
@@ -28,6 +28,8 @@ pub(super) struct NextFontLocalOptions {
     /// An optional name for a css custom property (css variable) that applies
     /// the font family when used.
     pub variable: Option<String>,
+    /// Additional descriptors that are included in every `@font-face` rule.
+    pub declarations: Option<Vec<FontFaceDeclaration>>,
     /// The name of the variable assigned to the results of calling the
     /// `localFont` function. This is used as the font family's base name.
     pub variable_name: String,
@@ -139,6 +141,7 @@ pub(super) fn options_from_request(request: &NextFontLocalRequest) -> Result<Nex
         src,
         adjust_font_fallback,
         variable,
+        declarations,
     } = &request.arguments.0;
 
     let fonts = match src {
@@ -164,6 +167,7 @@ pub(super) fn options_from_request(request: &NextFontLocalRequest) -> Result<Nex
         fallback: fallback.to_owned(),
         adjust_font_fallback: adjust_font_fallback.to_owned(),
         variable: variable.to_owned(),
+        declarations: declarations.to_owned(),
         variable_name: request.variable_name.to_owned(),
         default_weight: weight.as_ref().and_then(|s| s.parse().ok()),
         default_style: style.to_owned(),
@@ -178,7 +182,7 @@ mod tests {
     use super::{options_from_request, NextFontLocalOptions};
     use crate::next_font::local::{
         options::{FontDescriptor, FontDescriptors, FontWeight},
-        request::{AdjustFontFallback, NextFontLocalRequest},
+        request::{AdjustFontFallback, FontFaceDeclaration, NextFontLocalRequest},
     };
 
     #[test]
@@ -212,6 +216,7 @@ mod tests {
                 fallback: None,
                 adjust_font_fallback: AdjustFontFallback::Arial,
                 variable: None,
+                declarations: None,
                 variable_name: "myFont".to_owned()
             },
         );
@@ -267,6 +272,7 @@ mod tests {
                 fallback: None,
                 adjust_font_fallback: AdjustFontFallback::Arial,
                 variable: None,
+                declarations: None,
                 variable_name: "myFont".to_owned()
             },
         );
@@ -318,7 +324,11 @@ mod tests {
                     "fallback": ["Fallback"],
                     "adjustFontFallback": "Times New Roman",
                     "display": "optional",
-                    "variable": "myvar"
+                    "variable": "myvar",
+                    "declarations": [{
+                        "prop": "ascent-override",
+                        "value": "90%"
+                    }]
                 }]
             }
         "#,
@@ -340,6 +350,10 @@ mod tests {
                 fallback: Some(vec!["Fallback".to_owned()]),
                 adjust_font_fallback: AdjustFontFallback::TimesNewRoman,
                 variable: Some("myvar".to_owned()),
+                declarations: Some(vec![FontFaceDeclaration {
+                    prop: "ascent-override".to_owned(),
+                    value: "90%".to_owned(),
+                }]),
                 variable_name: "myFont".to_owned()
             },
         );
@@ -28,6 +28,7 @@ pub(super) struct NextFontLocalRequestArguments {
     )]
     pub adjust_font_fallback: AdjustFontFallback,
     pub variable: Option<String>,
+    pub declarations: Option<Vec<FontFaceDeclaration>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -44,6 +45,16 @@ pub(super) struct SrcDescriptor {
     pub style: Option<String>,
 }
 
+/// An additional descriptor of the `@font-face` rules, e.g.
+/// `{ prop: 'ascent-override', value: '90%' }`.
+#[derive(
+    Clone, Debug, Deserialize, Hash, Ord, PartialOrd, PartialEq, Eq, Serialize, TraceRawVcs,
+)]
+pub(super) struct FontFaceDeclaration {
+    pub prop: String,
+    pub value: String,
+}
+
 /// The user's desired fallback font
 #[derive(
     Clone, Debug, Deserialize, Hash, Ord, PartialOrd, PartialEq, Eq, Serialize, TraceRawVcs,
@@ -55,7 +55,7 @@ pub(super) async fn build_font_face_definitions(
                 font-family: '{}';
                 src: url('{}') format('{}');
                 font-display: {};
-                {}{}
+                {}{}{}
             }}
         "#,
             *scoped_font_family.await?,
@@ -72,6 +72,12 @@ pub(super) async fn build_font_face_definitions(
                 .as_ref()
                 .or(options.default_style.as_ref())
                 .map_or_else(|| "".to_owned(), |s| format!("font-style: {};", s)),
+            options
+                .declarations
+                .iter()
+                .flatten()
+                .map(|declaration| format!("{}: {};", declaration.prop, declaration.value))
+                .collect::<String>(),
         ));
     }
 
@@ -1,40 +1,64 @@
 use anyhow::{bail, Result};
-use turbo_tasks::{primitives::StringVc, Value};
-use turbo_tasks_fs::FileSystem;
-use turbopack_binding::turbopack::{
-    core::{
-        asset::AssetContent,
-        ident::AssetIdentVc,
-        introspect::{Introspectable, IntrospectableVc},
-        server_fs::ServerFileSystemVc,
-        version::VersionedContent,
-    },
-    dev_server::source::{
-        query::QueryValue,
-        route_tree::{RouteTreeVc, RouteType},
-        wrapping_source::{
-            ContentSourceProcessor, ContentSourceProcessorVc, WrappedGetContentSourceContentVc,
+use regex::Regex;
+use turbo_tasks::{
+    primitives::{OptionStringVc, StringVc},
+    Value,
+};
+use turbo_tasks_fs::{rope::Rope, File, FileContent, FileSystem};
+use turbopack_binding::{
+    turbo::tasks_fetch::{fetch, FetchErrorKind},
+    turbopack::{
+        core::{
+            asset::AssetContent,
+            ident::AssetIdentVc,
+            introspect::{Introspectable, IntrospectableVc},
+            server_fs::ServerFileSystemVc,
+            version::VersionedContent,
         },
-        ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
-        ContentSourceDataFilter, ContentSourceDataVary, ContentSourceDataVaryVc, ContentSourceVc,
-        GetContentSourceContent, GetContentSourceContentVc, GetContentSourceContentsVc,
-        ProxyResult, RewriteBuilder,
+        dev_server::source::{
+            query::QueryValue,
+            route_tree::{RouteTreeVc, RouteType},
+            wrapping_source::{
+                ContentSourceProcessor, ContentSourceProcessorVc, WrappedGetContentSourceContentVc,
+            },
+            ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+            ContentSourceDataFilter, ContentSourceDataVary, ContentSourceDataVaryVc,
+            ContentSourceVc, GetContentSourceContent, GetContentSourceContentVc,
+            GetContentSourceContentsVc, HeaderListVc, ProxyResult, RewriteBuilder, StaticContent,
+        },
+        image::process::optimize,
     },
-    image::process::optimize,
+};
+use url::Url;
+
+use crate::next_config::{
+    ContentDispositionType, ImageConfig, NextConfigVc, RemotePattern, RemotePatternProtocal,
 };
 
 /// Serves, resizes, optimizes, and re-encodes images to be used with
-/// next/image.
+/// next/image. SVGs are only served, as they are, when
+/// `images.dangerouslyAllowSVG` is enabled.
+///
+/// Remote images are downloaded by the server when their URL is allowed by
+/// `images.domains` or `images.remotePatterns`.
 #[turbo_tasks::value(shared)]
 pub struct NextImageContentSource {
     asset_source: ContentSourceVc,
+    next_config: NextConfigVc,
 }
 
 #[turbo_tasks::value_impl]
 impl NextImageContentSourceVc {
     #[turbo_tasks::function]
-    pub fn new(asset_source: ContentSourceVc) -> NextImageContentSourceVc {
-        NextImageContentSource { asset_source }.cell()
+    pub fn new(
+        asset_source: ContentSourceVc,
+        next_config: NextConfigVc,
+    ) -> NextImageContentSourceVc {
+        NextImageContentSource {
+            asset_source,
+            next_config,
+        }
+        .cell()
     }
 }
 
@@ -96,16 +120,38 @@ impl GetContentSourceContent for NextImageContentSource {
             _ => bail!("missing w query argument"),
         };
 
+        let image_config = this.next_config.image_config().await?;
+        if is_svg(url) && !image_config.dangerously_allow_svg {
+            return Ok(ContentSourceContent::HttpProxy(
+                ProxyResult {
+                    status: 400,
+                    headers: vec![],
+                    body: "\"url\" parameter is valid but image type is not allowed. SVGs are \
+                           only served when images.dangerouslyAllowSVG is enabled."
+                        .into(),
+                }
+                .cell(),
+            )
+            .cell());
+        }
+
         // TODO: re-encode into next-gen formats.
 
         if let Some(path) = url.strip_prefix('/') {
+            let headers = image_headers(&image_config, path);
             let sources = this.asset_source.get_routes().get(path).await?;
             let sources = sources
                 .iter()
                 .map(|s| {
                     WrappedGetContentSourceContentVc::new(
                         *s,
-                        NextImageContentSourceProcessorVc::new(path.to_string(), w, q).into(),
+                        NextImageContentSourceProcessorVc::new(
+                            path.to_string(),
+                            w,
+                            q,
+                            headers.clone(),
+                        )
+                        .into(),
                     )
                     .into()
                 })
@@ -116,12 +162,60 @@ impl GetContentSourceContent for NextImageContentSource {
             );
         }
 
-        // TODO: This should be downloaded by the server, and resized, etc.
-        Ok(ContentSourceContent::HttpProxy(
-            ProxyResult {
-                status: 302,
-                headers: vec![("Location".to_string(), url.clone())],
-                body: "".into(),
+        let remote_url = match Url::parse(url) {
+            Ok(remote_url) if is_allowed_remote_url(&image_config, &remote_url) => remote_url,
+            _ => {
+                return Ok(error_response(400, "\"url\" parameter is not allowed"));
+            }
+        };
+
+        let response = fetch(StringVc::cell(url.clone()), OptionStringVc::cell(None)).await?;
+        let response = match &*response {
+            Ok(response) => response.await?,
+            Err(err) => {
+                let status = match *err.await?.kind.await? {
+                    FetchErrorKind::Status(status) => status,
+                    _ => 500,
+                };
+                return Ok(error_response(
+                    status,
+                    "\"url\" parameter is valid but upstream response is invalid",
+                ));
+            }
+        };
+        let body = response.body.await?;
+        let file_content = FileContent::Content(File::from(Rope::from(body.0.clone()))).cell();
+        let path = format!(
+            "{}{}",
+            remote_url.host_str().unwrap_or_default(),
+            remote_url.path()
+        );
+        // Vector images are served as they are.
+        let file_content = if is_svg(url) {
+            file_content
+        } else {
+            optimize(
+                AssetIdentVc::from_path(ServerFileSystemVc::new().root().join(&path)),
+                file_content,
+                w,
+                u32::MAX,
+                q,
+            )
+        };
+
+        let mut headers = image_headers(&image_config, remote_url.path());
+        headers.push((
+            "cache-control".to_string(),
+            format!(
+                "public, max-age={}, must-revalidate",
+                image_config.minimum_cache_ttl
+            ),
+        ));
+        Ok(ContentSourceContent::Static(
+            StaticContent {
+                content: AssetContent::File(file_content).cell().into(),
+                status_code: 200,
+                headers: HeaderListVc::new(headers),
             }
             .cell(),
         )
@@ -129,6 +223,18 @@ impl GetContentSourceContent for NextImageContentSource {
     }
 }
 
+fn error_response(status: u16, message: &str) -> ContentSourceContentVc {
+    ContentSourceContent::HttpProxy(
+        ProxyResult {
+            status,
+            headers: vec![],
+            body: message.into(),
+        }
+        .cell(),
+    )
+    .cell()
+}
+
 #[turbo_tasks::value_impl]
 impl Introspectable for NextImageContentSource {
     #[turbo_tasks::function]
@@ -147,16 +253,23 @@ struct NextImageContentSourceProcessor {
     path: String,
     width: u32,
     quality: u8,
+    headers: Vec<(String, String)>,
 }
 
 #[turbo_tasks::value_impl]
 impl NextImageContentSourceProcessorVc {
     #[turbo_tasks::function]
-    pub fn new(path: String, width: u32, quality: u8) -> NextImageContentSourceProcessorVc {
+    pub fn new(
+        path: String,
+        width: u32,
+        quality: u8,
+        headers: Vec<(String, String)>,
+    ) -> NextImageContentSourceProcessorVc {
         NextImageContentSourceProcessor {
             path,
             width,
             quality,
+            headers,
         }
         .cell()
     }
@@ -174,15 +287,221 @@ impl ContentSourceProcessor for NextImageContentSourceProcessor {
         let AssetContent::File(file_content) = *asset_content else {
             return Ok(content);
         };
-        let optimized_file_content = optimize(
-            AssetIdentVc::from_path(ServerFileSystemVc::new().root().join(&self.path)),
-            file_content,
-            self.width,
-            u32::MAX,
-            self.quality,
+        // Vector images are served as they are.
+        let file_content = if is_svg(&self.path) {
+            file_content
+        } else {
+            optimize(
+                AssetIdentVc::from_path(ServerFileSystemVc::new().root().join(&self.path)),
+                file_content,
+                self.width,
+                u32::MAX,
+                self.quality,
+            )
+        };
+        let mut headers = static_content.headers.await?.clone_value();
+        headers.extend(self.headers.iter().cloned());
+        Ok(ContentSourceContent::Static(
+            StaticContent {
+                content: AssetContent::File(file_content).cell().into(),
+                status_code: static_content.status_code,
+                headers: HeaderListVc::new(headers),
+            }
+            .cell(),
+        )
+        .cell())
+    }
+}
+
+fn is_svg(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.to_ascii_lowercase().ends_with(".svg")
+}
+
+/// Whether a remote image URL is allowed by `images.domains` or
+/// `images.remotePatterns`.
+fn is_allowed_remote_url(image_config: &ImageConfig, url: &Url) -> bool {
+    let Some(hostname) = url.host_str() else {
+        return false;
+    };
+    if !matches!(url.scheme(), "http" | "https") {
+        return false;
+    }
+    image_config.domains.iter().any(|domain| domain == hostname)
+        || image_config
+            .remote_patterns
+            .iter()
+            .any(|pattern| matches_remote_pattern(pattern, url))
+}
+
+fn matches_remote_pattern(pattern: &RemotePattern, url: &Url) -> bool {
+    if let Some(protocol) = &pattern.protocol {
+        let scheme = match protocol {
+            RemotePatternProtocal::Http => "http",
+            RemotePatternProtocal::Https => "https",
+        };
+        if url.scheme() != scheme {
+            return false;
+        }
+    }
+    if let Some(port) = &pattern.port {
+        // An empty port only matches URLs without an explicit port.
+        if *port != url.port().map(|port| port.to_string()).unwrap_or_default() {
+            return false;
+        }
+    }
+    matches_glob(&pattern.hostname, url.host_str().unwrap_or_default(), '.')
+        && matches_glob(pattern.pathname.as_deref().unwrap_or("**"), url.path(), '/')
+}
+
+/// Matches a `remotePatterns` glob, where `**` matches anything and `*`
+/// matches anything within a single segment, delimited by `separator`.
+fn matches_glob(glob: &str, value: &str, separator: char) -> bool {
+    let segment = format!("[^{}]*", regex::escape(&separator.to_string()));
+    let pattern = glob
+        .split("**")
+        .map(|part| {
+            part.split('*')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(&segment)
+        })
+        .collect::<Vec<_>>()
+        .join(".*");
+    Regex::new(&format!("^{pattern}$")).map_or(false, |regex| regex.is_match(value))
+}
+
+/// The headers Next.js sends with optimized images, from the
+/// `contentDispositionType` and `contentSecurityPolicy` image config.
+fn image_headers(image_config: &ImageConfig, path: &str) -> Vec<(String, String)> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let disposition_type = match image_config.content_disposition_type {
+        ContentDispositionType::Inline => "inline",
+        ContentDispositionType::Attachment => "attachment",
+    };
+    let mut headers = vec![(
+        "content-disposition".to_string(),
+        format!(
+            "{disposition_type}; filename=\"{}\"",
+            file_name.replace(['"', '\\'], "_")
+        ),
+    )];
+    if !image_config.content_security_policy.is_empty() {
+        headers.push((
+            "content-security-policy".to_string(),
+            image_config.content_security_policy.clone(),
+        ));
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{image_headers, is_allowed_remote_url, is_svg, matches_glob};
+    use crate::next_config::{
+        ContentDispositionType, ImageConfig, RemotePattern, RemotePatternProtocal,
+    };
+
+    #[test]
+    fn test_is_svg() {
+        assert!(is_svg("/logo.svg"));
+        assert!(is_svg("https://example.com/logo.SVG?v=1"));
+        assert!(!is_svg("/logo.png"));
+        assert!(!is_svg("/svg/logo.png"));
+    }
+
+    #[test]
+    fn test_matches_glob() {
+        assert!(matches_glob(
+            "avatars.*.example.com",
+            "avatars.sfo1.example.com",
+            '.'
+        ));
+        assert!(!matches_glob(
+            "avatars.*.example.com",
+            "avatars.example.com",
+            '.'
+        ));
+        assert!(!matches_glob(
+            "avatars.*.example.com",
+            "avatars.a.b.example.com",
+            '.'
+        ));
+        assert!(matches_glob("**.example.com", "deep.sub.example.com", '.'));
+        assert!(!matches_glob("**.example.com", "example.com", '.'));
+        assert!(!matches_glob("**.example.com", "sub.example.com.uk", '.'));
+        assert!(matches_glob(
+            "/act123/*/pic.jpg",
+            "/act123/usr4/pic.jpg",
+            '/'
+        ));
+        assert!(!matches_glob(
+            "/act123/*/pic.jpg",
+            "/act123/a/b/pic.jpg",
+            '/'
+        ));
+        assert!(!matches_glob(
+            "/act123/*/pic.jpg",
+            "/act123/usr4/picsjpg",
+            '/'
+        ));
+        assert!(matches_glob("/act123/**", "/act123/a/b/pic.jpg", '/'));
+    }
+
+    #[test]
+    fn test_is_allowed_remote_url() {
+        let image_config = ImageConfig {
+            domains: vec!["cdn.example.com".to_string()],
+            remote_patterns: vec![RemotePattern {
+                hostname: "**.example.net".to_string(),
+                protocol: Some(RemotePatternProtocal::Https),
+                port: Some("".to_string()),
+                pathname: Some("/images/**".to_string()),
+            }],
+            ..Default::default()
+        };
+        let is_allowed =
+            |url: &str| is_allowed_remote_url(&image_config, &Url::parse(url).unwrap());
+
+        assert!(is_allowed("https://cdn.example.com/logo.png"));
+        assert!(is_allowed("http://cdn.example.com:8080/logo.png"));
+        assert!(!is_allowed("https://example.com/logo.png"));
+        assert!(is_allowed("https://assets.example.net/images/logo.png"));
+        assert!(!is_allowed("http://assets.example.net/images/logo.png"));
+        assert!(!is_allowed(
+            "https://assets.example.net:8443/images/logo.png"
+        ));
+        assert!(!is_allowed("https://assets.example.net/logo.png"));
+        assert!(!is_allowed("ftp://cdn.example.com/logo.png"));
+    }
+
+    #[test]
+    fn test_image_headers() {
+        let mut image_config = ImageConfig::default();
+        assert_eq!(
+            image_headers(&image_config, "images/logo.svg"),
+            vec![(
+                "content-disposition".to_string(),
+                "inline; filename=\"logo.svg\"".to_string()
+            )]
+        );
+
+        image_config.content_disposition_type = ContentDispositionType::Attachment;
+        image_config.content_security_policy = "script-src 'none'; sandbox;".to_string();
+        assert_eq!(
+            image_headers(&image_config, "logo.svg"),
+            vec![
+                (
+                    "content-disposition".to_string(),
+                    "attachment; filename=\"logo.svg\"".to_string()
+                ),
+                (
+                    "content-security-policy".to_string(),
+                    "script-src 'none'; sandbox;".to_string()
+                ),
+            ]
         );
-        Ok(ContentSourceContentVc::static_content(
-            AssetContent::File(optimized_file_content).into(),
-        ))
     }
 }
@@ -1,9 +1,12 @@
 use std::collections::{BTreeMap, HashMap};
 
 use anyhow::{Context, Result};
+use serde_json::Value as JsonValue;
 use turbo_tasks::Value;
 use turbopack_binding::{
-    turbo::tasks_fs::{glob::GlobVc, FileSystem, FileSystemPathVc},
+    turbo::tasks_fs::{
+        glob::GlobVc, FileJsonContent, FileSystem, FileSystemEntryType, FileSystemPathVc,
+    },
     turbopack::{
         core::{
             asset::Asset,
@@ -17,6 +20,7 @@ use turbopack_binding::{
                 resolve, AliasPattern, ResolveAliasMapVc, SubpathValue,
             },
         },
+        ecmascript::typescript::resolve::read_from_tsconfigs,
         node::execution_context::ExecutionContextVc,
         turbopack::{resolve_options, resolve_options_context::ResolveOptionsContext},
     },
@@ -24,6 +28,7 @@ use turbopack_binding::{
 
 use crate::{
     embed_js::{next_js_fs, VIRTUAL_PACKAGE_NAME},
+    mode::NextMode,
     next_client::context::ClientContextType,
     next_config::NextConfigVc,
     next_font::{
@@ -31,6 +36,8 @@ use crate::{
         local::{NextFontLocalCssModuleReplacerVc, NextFontLocalReplacerVc},
     },
     next_server::context::ServerContextType,
+    next_shared::resolve::{SubpathImportsReplacerVc, UnsupportedNodeBuiltinReplacerVc},
+    transform_options::get_typescript_options,
 };
 
 // Make sure to not add any external requests here.
@@ -39,6 +46,7 @@ use crate::{
 pub async fn get_next_client_import_map(
     project_path: FileSystemPathVc,
     ty: Value<ClientContextType>,
+    mode: NextMode,
     next_config: NextConfigVc,
     execution_context: ExecutionContextVc,
 ) -> Result<ImportMapVc> {
@@ -60,6 +68,8 @@ pub async fn get_next_client_import_map(
     )
     .await?;
 
+    insert_subpath_imports_alias(&mut import_map, &["browser"]);
+
     match ty.into_value() {
         ClientContextType::Pages { pages_dir } => {
             insert_alias_to_alternatives(
@@ -117,14 +127,23 @@ pub async fn get_next_client_import_map(
         ClientContextType::Other => {}
     }
 
+    if !mode.is_react_development() && *next_config.react_production_profiling().await? {
+        insert_react_profiling_alias(&mut import_map, project_path, ty.into_value());
+    }
+
     match ty.into_value() {
         ClientContextType::Pages { .. }
         | ClientContextType::App { .. }
         | ClientContextType::Fallback => {
+            let polyfill = *next_config.fallback_node_polyfills().await?;
             for (original, alias) in NEXT_ALIASES {
                 import_map.insert_exact_alias(
                     format!("node:{original}"),
-                    request_to_import_mapping(project_path, alias),
+                    if polyfill {
+                        request_to_import_mapping(project_path, alias)
+                    } else {
+                        ImportMapping::Ignore.into()
+                    },
                 );
             }
         }
@@ -159,8 +178,14 @@ pub fn get_next_build_import_map() -> ImportMapVc {
 
 /// Computes the Next-specific client fallback import map, which provides
 /// polyfills to Node.js externals.
+///
+/// When `experimental.fallbackNodePolyfills` is disabled, the builtins resolve
+/// to empty modules instead. Builtins without a browser polyfill emit an issue.
 #[turbo_tasks::function]
-pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> ImportMapVc {
+pub async fn get_next_client_fallback_import_map(
+    ty: Value<ClientContextType>,
+    next_config: NextConfigVc,
+) -> Result<ImportMapVc> {
     let mut import_map = ImportMap::empty();
 
     match ty.into_value() {
@@ -170,9 +195,25 @@ pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> Impo
         | ClientContextType::App {
             app_dir: context_dir,
         } => {
+            let polyfill = *next_config.fallback_node_polyfills().await?;
             for (original, alias) in NEXT_ALIASES {
-                import_map
-                    .insert_exact_alias(original, request_to_import_mapping(context_dir, alias));
+                import_map.insert_exact_alias(
+                    original,
+                    if polyfill {
+                        request_to_import_mapping(context_dir, alias)
+                    } else {
+                        ImportMapping::Ignore.into()
+                    },
+                );
+            }
+            for builtin in NODE_BUILTINS {
+                if NEXT_ALIASES
+                    .iter()
+                    .any(|(original, _)| *original == builtin)
+                {
+                    continue;
+                }
+                insert_unsupported_node_builtin(&mut import_map, builtin, false);
             }
         }
         ClientContextType::Fallback => {}
@@ -181,7 +222,7 @@ pub fn get_next_client_fallback_import_map(ty: Value<ClientContextType>) -> Impo
 
     insert_turbopack_dev_alias(&mut import_map);
 
-    import_map.cell()
+    Ok(import_map.cell())
 }
 
 /// Computes the Next-specific server-side import map.
@@ -210,6 +251,8 @@ pub async fn get_next_server_import_map(
     )
     .await?;
 
+    insert_subpath_imports_alias(&mut import_map, &["node"]);
+
     let ty = ty.into_value();
 
     insert_next_server_special_aliases(&mut import_map, ty).await?;
@@ -274,6 +317,8 @@ pub async fn get_next_edge_import_map(
     )
     .await?;
 
+    insert_subpath_imports_alias(&mut import_map, &["edge-light", "worker"]);
+
     let ty = ty.into_value();
 
     insert_next_server_special_aliases(&mut import_map, ty).await?;
@@ -298,6 +343,49 @@ pub async fn get_next_edge_import_map(
     Ok(import_map.cell())
 }
 
+/// Computes the Next-specific edge-side fallback import map, which provides
+/// the Node.js builtins supported by the Edge Runtime.
+#[turbo_tasks::function]
+pub fn get_next_edge_fallback_import_map(project_path: FileSystemPathVc) -> ImportMapVc {
+    let mut import_map = ImportMap::empty();
+
+    for (original, alias) in EDGE_NODE_ALIASES {
+        let mapping = request_to_import_mapping(project_path, alias);
+        import_map.insert_exact_alias(original, mapping);
+        import_map.insert_exact_alias(format!("node:{original}"), mapping);
+    }
+    for builtin in NODE_BUILTINS {
+        // `async_hooks` is provided by the Edge Runtime itself.
+        if builtin == "async_hooks"
+            || EDGE_NODE_ALIASES
+                .iter()
+                .any(|(original, _)| *original == builtin)
+        {
+            continue;
+        }
+        insert_unsupported_node_builtin(&mut import_map, builtin, true);
+    }
+
+    import_map.cell()
+}
+
+/// Resolves `#` requests with the `imports` field of the nearest package.json,
+/// and reports the ones that aren't defined there.
+fn insert_subpath_imports_alias(import_map: &mut ImportMap, conditions: &[&str]) {
+    let conditions = conditions.iter().map(|c| c.to_string()).collect();
+    import_map.insert_wildcard_alias(
+        "#",
+        ImportMapping::Dynamic(SubpathImportsReplacerVc::new(conditions).into()).into(),
+    );
+}
+
+fn insert_unsupported_node_builtin(import_map: &mut ImportMap, builtin: &str, edge: bool) {
+    let mapping: ImportMappingVc =
+        ImportMapping::Dynamic(UnsupportedNodeBuiltinReplacerVc::new(builtin, edge).into()).into();
+    import_map.insert_exact_alias(builtin, mapping);
+    import_map.insert_exact_alias(format!("node:{builtin}"), mapping);
+}
+
 pub fn get_next_client_resolved_map(
     context: FileSystemPathVc,
     root: FileSystemPathVc,
@@ -346,6 +434,59 @@ static NEXT_ALIASES: [(&str, &str); 23] = [
     ("setImmediate", "next/dist/compiled/setimmediate"),
 ];
 
+static EDGE_NODE_ALIASES: [(&str, &str); 5] = [
+    ("assert", "next/dist/compiled/assert"),
+    ("buffer", "next/dist/compiled/buffer"),
+    ("events", "next/dist/compiled/events"),
+    ("process", "next/dist/build/polyfills/process"),
+    ("util", "next/dist/compiled/util"),
+];
+
+static NODE_BUILTINS: [&str; 42] = [
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
 pub async fn insert_next_server_special_aliases(
     import_map: &mut ImportMap,
     ty: ServerContextType,
@@ -476,7 +617,8 @@ pub async fn insert_next_shared_aliases(
     import_map.insert_alias(
         AliasPattern::exact("@vercel/turbopack-next/internal/font/google/cssmodule.module.css"),
         ImportMapping::Dynamic(
-            NextFontGoogleCssModuleReplacerVc::new(project_path, execution_context).into(),
+            NextFontGoogleCssModuleReplacerVc::new(project_path, execution_context, next_config)
+                .into(),
         )
         .into(),
     );
@@ -511,6 +653,118 @@ pub async fn insert_next_shared_aliases(
         turbopack_binding::turbopack::node::embed_js::embed_fs().root(),
     );
 
+    insert_default_src_alias(import_map, project_path, next_config).await?;
+    insert_custom_tsconfig_paths(import_map, project_path, next_config).await?;
+
+    Ok(())
+}
+
+/// Maps `@/*` to `./src/*` when the project has a `src` directory, which is
+/// the alias `create-next-app` sets up, so it works without a tsconfig. An
+/// `@/` mapping in `compilerOptions.paths` takes precedence.
+async fn insert_default_src_alias(
+    import_map: &mut ImportMap,
+    project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
+) -> Result<()> {
+    let src_dir = project_path.join("src");
+    if *src_dir.get_type().await? != FileSystemEntryType::Directory {
+        return Ok(());
+    }
+    if let Some(tsconfigs) = get_typescript_options(project_path, next_config).await {
+        let has_src_alias = read_from_tsconfigs(&tsconfigs, |json, _| {
+            json["compilerOptions"]["paths"]
+                .as_object()?
+                .keys()
+                .any(|key| key.starts_with("@/"))
+                .then_some(())
+        })
+        .await?;
+        if has_src_alias.is_some() {
+            return Ok(());
+        }
+    }
+
+    import_map.insert_wildcard_alias("@/", request_to_import_mapping(src_dir, "./*"));
+    Ok(())
+}
+
+/// Swaps `react-dom` for its profiling build, like `reactProductionProfiling`
+/// does with webpack.
+fn insert_react_profiling_alias(
+    import_map: &mut ImportMap,
+    project_path: FileSystemPathVc,
+    ty: ClientContextType,
+) {
+    let react_dom = match ty {
+        ClientContextType::App { .. } => "next/dist/compiled/react-dom/profiling",
+        _ => "react-dom/profiling",
+    };
+    import_map.insert_exact_alias(
+        "react-dom",
+        request_to_import_mapping(project_path, react_dom),
+    );
+}
+
+/// Turbopack only applies `compilerOptions.paths` from the default
+/// tsconfig.json. When `typescript.tsconfigPath` points somewhere else, its
+/// paths are mapped here instead.
+async fn insert_custom_tsconfig_paths(
+    import_map: &mut ImportMap,
+    project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
+) -> Result<()> {
+    if next_config.typescript_tsconfig_path().await?.is_none() {
+        return Ok(());
+    }
+    let Some(tsconfigs) = get_typescript_options(project_path, next_config).await else {
+        return Ok(());
+    };
+
+    // Configs are ordered from the configured tsconfig to the ones it extends, so
+    // iterate in reverse to let the configured one override inherited paths.
+    for (content, source) in tsconfigs.iter().rev() {
+        let FileJsonContent::Content(json) = &*content.await? else {
+            continue;
+        };
+        let JsonValue::Object(paths) = &json["compilerOptions"]["paths"] else {
+            continue;
+        };
+        let mut context = source.ident().path().parent();
+        if let Some(base_url) = json["compilerOptions"]["baseUrl"].as_str() {
+            if let Some(base_url) = *context.try_join(base_url).await? {
+                context = base_url;
+            }
+        }
+
+        for (key, value) in paths {
+            let JsonValue::Array(targets) = value else {
+                continue;
+            };
+            let alternatives: Vec<_> = targets
+                .iter()
+                .filter_map(|target| target.as_str())
+                .filter(|target| !target.ends_with(".d.ts"))
+                .map(|target| {
+                    if target.starts_with('.') || target.starts_with('/') {
+                        request_to_import_mapping(context, target)
+                    } else {
+                        request_to_import_mapping(context, &format!("./{target}"))
+                    }
+                })
+                .collect();
+            if alternatives.is_empty() {
+                continue;
+            }
+            let mapping = ImportMapping::Alternatives(alternatives).cell();
+            if let Some(prefix) = key.strip_suffix('*') {
+                import_map.insert_wildcard_alias(prefix, mapping);
+            } else {
+                import_map.insert_exact_alias(key, mapping);
+            }
+        }
+    }
+
     Ok(())
 }
 
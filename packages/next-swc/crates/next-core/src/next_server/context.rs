@@ -43,7 +43,10 @@ use crate::{
     next_import_map::{get_next_server_import_map, mdx_import_source_file},
     next_server::resolve::ExternalPredicate,
     next_shared::{
-        resolve::UnsupportedModulesResolvePluginVc,
+        resolve::{
+            AssetQueryResolvePluginVc, NativeModulesResolvePluginVc,
+            UnsupportedModulesResolvePluginVc,
+        },
         transforms::{
             emotion::get_emotion_transform_plugin, get_relay_transform_plugin,
             styled_components::get_styled_components_transform_plugin,
@@ -83,9 +86,11 @@ pub async fn get_server_resolve_options_context(
     let foreign_code_context_condition = foreign_code_context_condition(next_config).await?;
     let root_dir = project_path.root().resolve().await?;
     let unsupported_modules_resolve_plugin = UnsupportedModulesResolvePluginVc::new(project_path);
+    let asset_query_resolve_plugin = AssetQueryResolvePluginVc::new(project_path);
     let server_component_externals_plugin = ExternalCjsModulesResolvePluginVc::new(
         project_path,
         ExternalPredicate::Only(next_config.server_component_externals()).cell(),
+        next_config.esm_externals(),
     );
 
     Ok(match ty.into_value() {
@@ -93,6 +98,7 @@ pub async fn get_server_resolve_options_context(
             let external_cjs_modules_plugin = ExternalCjsModulesResolvePluginVc::new(
                 project_path,
                 ExternalPredicate::AllExcept(next_config.transpile_packages()).cell(),
+                next_config.esm_externals(),
             );
 
             let resolve_options_context = ResolveOptionsContext {
@@ -100,9 +106,11 @@ pub async fn get_server_resolve_options_context(
                 enable_node_externals: true,
                 enable_node_native_modules: true,
                 module: true,
+                enable_mjs_extension: true,
                 custom_conditions: vec![mode.node_env().to_string(), "node".to_string()],
                 import_map: Some(next_server_import_map),
                 plugins: vec![
+                    asset_query_resolve_plugin.into(),
                     external_cjs_modules_plugin.into(),
                     unsupported_modules_resolve_plugin.into(),
                 ],
@@ -124,6 +132,7 @@ pub async fn get_server_resolve_options_context(
                 enable_node_externals: true,
                 enable_node_native_modules: true,
                 module: true,
+                enable_mjs_extension: true,
                 custom_conditions: vec![
                     mode.node_env().to_string(),
                     // TODO!
@@ -131,6 +140,7 @@ pub async fn get_server_resolve_options_context(
                 ],
                 import_map: Some(next_server_import_map),
                 plugins: vec![
+                    asset_query_resolve_plugin.into(),
                     server_component_externals_plugin.into(),
                     unsupported_modules_resolve_plugin.into(),
                 ],
@@ -152,6 +162,7 @@ pub async fn get_server_resolve_options_context(
                 enable_node_externals: true,
                 enable_node_native_modules: true,
                 module: true,
+                enable_mjs_extension: true,
                 custom_conditions: vec![
                     mode.node_env().to_string(),
                     "react-server".to_string(),
@@ -160,6 +171,7 @@ pub async fn get_server_resolve_options_context(
                 ],
                 import_map: Some(next_server_import_map),
                 plugins: vec![
+                    asset_query_resolve_plugin.into(),
                     server_component_externals_plugin.into(),
                     unsupported_modules_resolve_plugin.into(),
                 ],
@@ -178,10 +190,13 @@ pub async fn get_server_resolve_options_context(
         ServerContextType::AppRoute { .. } => {
             let resolve_options_context = ResolveOptionsContext {
                 enable_node_modules: Some(root_dir),
+                enable_node_native_modules: true,
                 module: true,
+                enable_mjs_extension: true,
                 custom_conditions: vec![mode.node_env().to_string(), "node".to_string()],
                 import_map: Some(next_server_import_map),
                 plugins: vec![
+                    asset_query_resolve_plugin.into(),
                     server_component_externals_plugin.into(),
                     unsupported_modules_resolve_plugin.into(),
                 ],
@@ -202,8 +217,13 @@ pub async fn get_server_resolve_options_context(
                 enable_node_modules: Some(root_dir),
                 enable_node_externals: true,
                 module: true,
+                enable_mjs_extension: true,
                 custom_conditions: vec![mode.node_env().to_string()],
-                plugins: vec![unsupported_modules_resolve_plugin.into()],
+                plugins: vec![
+                    asset_query_resolve_plugin.into(),
+                    unsupported_modules_resolve_plugin.into(),
+                    NativeModulesResolvePluginVc::new(project_path, true).into(),
+                ],
                 ..Default::default()
             };
             ResolveOptionsContext {
@@ -299,8 +319,8 @@ pub async fn get_server_module_options_context(
     )));
 
     // ModuleOptionsContext related options
-    let tsconfig = get_typescript_transform_options(project_path);
-    let decorators_options = get_decorators_transform_options(project_path);
+    let tsconfig = get_typescript_transform_options(project_path, next_config);
+    let decorators_options = get_decorators_transform_options(project_path, next_config);
     let enable_mdx_rs = if *next_config.mdx_rs().await? {
         Some(
             MdxTransformModuleOptions {
@@ -311,7 +331,7 @@ pub async fn get_server_module_options_context(
     } else {
         None
     };
-    let jsx_runtime_options = get_jsx_transform_options(project_path, mode, None);
+    let jsx_runtime_options = get_jsx_transform_options(project_path, mode, None, next_config);
 
     let source_transforms: Vec<TransformPluginVc> = vec![
         *get_swc_ecma_transform_plugin(project_path, next_config).await?,
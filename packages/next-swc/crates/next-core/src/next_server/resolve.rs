@@ -18,6 +18,8 @@ use turbopack_binding::{
     },
 };
 
+use crate::next_config::{EsmExternals, EsmExternalsVc};
+
 /// The predicated based on which the [ExternalCjsModulesResolvePlugin] decides
 /// whether to mark a module as external.
 #[turbo_tasks::value(into = "shared")]
@@ -36,13 +38,23 @@ pub enum ExternalPredicate {
 pub(crate) struct ExternalCjsModulesResolvePlugin {
     root: FileSystemPathVc,
     predicate: ExternalPredicateVc,
+    esm_externals: EsmExternalsVc,
 }
 
 #[turbo_tasks::value_impl]
 impl ExternalCjsModulesResolvePluginVc {
     #[turbo_tasks::function]
-    pub fn new(root: FileSystemPathVc, predicate: ExternalPredicateVc) -> Self {
-        ExternalCjsModulesResolvePlugin { root, predicate }.cell()
+    pub fn new(
+        root: FileSystemPathVc,
+        predicate: ExternalPredicateVc,
+        esm_externals: EsmExternalsVc,
+    ) -> Self {
+        ExternalCjsModulesResolvePlugin {
+            root,
+            predicate,
+            esm_externals,
+        }
+        .cell()
     }
 }
 
@@ -67,6 +79,79 @@ async fn is_node_resolveable(
     Ok(BoolVc::cell(true))
 }
 
+/// Whether `request` can be required by node.js from `context`, i. e. it
+/// resolves to a CommonJS module with the CommonJS conditions. That module
+/// needs to be part of the same package as `expected`, the file the request
+/// was resolved to for bundling.
+#[turbo_tasks::function]
+async fn is_node_requireable(
+    context: FileSystemPathVc,
+    request: RequestVc,
+    expected: FileSystemPathVc,
+) -> Result<BoolVc> {
+    let node_resolve_result = resolve(context, request, node_cjs_resolve_options(context.root()));
+    let primary_node_assets = node_resolve_result.primary_assets().await?;
+    let Some(node_asset) = primary_node_assets.first() else {
+        // can't resolve request with node.js options
+        return Ok(BoolVc::cell(false));
+    };
+    let node_path = node_asset.ident().path();
+
+    let node_path_value = node_path.await?;
+    let Some(node_package_dir) = package_dir(&node_path_value.path) else {
+        return Ok(BoolVc::cell(false));
+    };
+    if package_dir(&expected.await?.path) != Some(node_package_dir) {
+        // node.js resolves to a different package
+        return Ok(BoolVc::cell(false));
+    }
+
+    Ok(BoolVc::cell(is_cjs(node_path).await?))
+}
+
+/// Returns the directory of the package in `node_modules` that contains
+/// `path`, e. g. `node_modules/@scope/package` for
+/// `node_modules/@scope/package/dist/cjs/index.js`.
+fn package_dir(path: &str) -> Option<&str> {
+    let start = path.rfind("node_modules/")? + "node_modules/".len();
+    let mut segments = path[start..].splitn(3, '/');
+    let name = segments.next()?;
+    let len = if name.starts_with('@') {
+        name.len() + 1 + segments.next()?.len()
+    } else {
+        name.len()
+    };
+    Some(&path[..start + len])
+}
+
+/// Whether node.js loads the file at `fs_path` as a CommonJS module.
+async fn is_cjs(fs_path: FileSystemPathVc) -> Result<bool> {
+    let raw_fs_path = &*fs_path.await?;
+
+    // node.js only supports these file extensions
+    // mjs is an esm module and we can't bundle that yet
+    if !matches!(
+        raw_fs_path.extension(),
+        Some("cjs" | "js" | "node" | "json")
+    ) {
+        return Ok(false);
+    }
+
+    let FindContextFileResult::Found(package_json, _) =
+        *find_context_file(fs_path.parent(), package_json()).await?
+    else {
+        // can't find package.json
+        return Ok(false);
+    };
+    let FileJsonContent::Content(package) = &*package_json.read_json().await? else {
+        // can't parse package.json
+        return Ok(false);
+    };
+
+    // esm modules are never external
+    Ok(!matches!(package["type"].as_str(), Some("module")))
+}
+
 static PNPM: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?:/|^)node_modules/(.pnpm/.+)").unwrap());
 
 #[turbo_tasks::function]
@@ -115,28 +200,19 @@ impl ResolvePlugin for ExternalCjsModulesResolvePlugin {
             }
         }
 
-        // node.js only supports these file extensions
-        // mjs is an esm module and we can't bundle that yet
-        if !matches!(
-            raw_fs_path.extension(),
-            Some("cjs" | "js" | "node" | "json")
-        ) {
-            return Ok(ResolveResultOptionVc::none());
+        // with `esmExternals: false`, packages are loaded from their CommonJS
+        // entry, even when they were resolved to esm for bundling
+        if matches!(*self.esm_externals.await?, EsmExternals::Cjs)
+            && *is_node_requireable(context, request, fs_path).await?
+        {
+            // mark as external
+            return Ok(ResolveResultOptionVc::some(
+                ResolveResult::primary(PrimaryResolveResult::OriginalReferenceExternal).cell(),
+            ));
         }
 
-        let FindContextFileResult::Found(package_json, _) =
-            *find_context_file(fs_path.parent(), package_json()).await?
-        else {
-            // can't find package.json
-            return Ok(ResolveResultOptionVc::none());
-        };
-        let FileJsonContent::Content(package) = &*package_json.read_json().await? else {
-            // can't parse package.json
-            return Ok(ResolveResultOptionVc::none());
-        };
-
         // always bundle esm modules
-        if let Some("module") = package["type"].as_str() {
+        if !is_cjs(fs_path).await? {
             return Ok(ResolveResultOptionVc::none());
         }
 
@@ -183,3 +259,26 @@ async fn packages_glob(packages: StringsVc) -> Result<GlobVc> {
         packages.await?.join(",")
     )))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::package_dir;
+
+    #[test]
+    fn test_package_dir() {
+        assert_eq!(
+            package_dir("node_modules/package/dist/cjs/index.js"),
+            Some("node_modules/package")
+        );
+        assert_eq!(
+            package_dir("app/node_modules/@scope/package/index.mjs"),
+            Some("app/node_modules/@scope/package")
+        );
+        assert_eq!(
+            package_dir("node_modules/.pnpm/a@1.0.0/node_modules/a/index.js"),
+            Some("node_modules/.pnpm/a@1.0.0/node_modules/a")
+        );
+        assert_eq!(package_dir("node_modules/@scope"), None);
+        assert_eq!(package_dir("src/index.js"), None);
+    }
+}
@@ -1,18 +1,33 @@
 use std::collections::HashSet;
 
 use anyhow::Result;
+use base64::{engine::general_purpose, Engine};
 use lazy_static::lazy_static;
+use serde_json::Value as JsonValue;
+use turbo_tasks::{primitives::StringVc, Value};
 use turbo_tasks_fs::glob::GlobVc;
 use turbopack_binding::{
-    turbo::tasks_fs::FileSystemPathVc,
-    turbopack::core::{
-        issue::unsupported_module::UnsupportedModuleIssue,
-        resolve::{
-            parse::{Request, RequestVc},
-            pattern::Pattern,
-            plugin::{ResolvePlugin, ResolvePluginConditionVc, ResolvePluginVc},
-            ResolveResultOptionVc,
+    turbo::tasks_fs::{FileContent, FileJsonContent, FileSystemPathVc},
+    turbopack::{
+        core::{
+            issue::{
+                unsupported_module::UnsupportedModuleIssue, Issue, IssueSeverity, IssueSeverityVc,
+            },
+            resolve::{
+                find_context_file,
+                options::{
+                    ImportMapResult, ImportMapResultVc, ImportMapping, ImportMappingReplacement,
+                    ImportMappingReplacementVc, ImportMappingVc,
+                },
+                package_json,
+                parse::{Request, RequestVc},
+                pattern::Pattern,
+                plugin::{ResolvePlugin, ResolvePluginConditionVc, ResolvePluginVc},
+                FindContextFileResult, ResolveResult, ResolveResultOptionVc,
+            },
+            virtual_source::VirtualSourceVc,
         },
+        ecmascript::utils::StringifyJs,
     },
 };
 
@@ -83,3 +98,611 @@ impl ResolvePlugin for UnsupportedModulesResolvePlugin {
         Ok(ResolveResultOptionVc::none())
     }
 }
+
+/// Reports native Node.js addons (`.node` files), which can only be loaded by
+/// Node.js itself, when they are resolved for the browser or the Edge
+/// Runtime.
+#[turbo_tasks::value]
+pub(crate) struct NativeModulesResolvePlugin {
+    root: FileSystemPathVc,
+    edge: bool,
+}
+
+#[turbo_tasks::value_impl]
+impl NativeModulesResolvePluginVc {
+    #[turbo_tasks::function]
+    pub fn new(root: FileSystemPathVc, edge: bool) -> Self {
+        NativeModulesResolvePlugin { root, edge }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ResolvePlugin for NativeModulesResolvePlugin {
+    #[turbo_tasks::function]
+    fn after_resolve_condition(&self) -> ResolvePluginConditionVc {
+        ResolvePluginConditionVc::new(self.root.root(), GlobVc::new("**/*.node"))
+    }
+
+    #[turbo_tasks::function]
+    fn after_resolve(
+        &self,
+        fs_path: FileSystemPathVc,
+        context: FileSystemPathVc,
+        _request: RequestVc,
+    ) -> ResolveResultOptionVc {
+        UnsupportedNativeModuleIssue {
+            context,
+            path: fs_path,
+            edge: self.edge,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+
+        ResolveResultOptionVc::none()
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct UnsupportedNativeModuleIssue {
+    context: FileSystemPathVc,
+    path: FileSystemPathVc,
+    edge: bool,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnsupportedNativeModuleIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("resolve".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.context
+    }
+
+    #[turbo_tasks::function]
+    async fn title(&self) -> Result<StringVc> {
+        let runtime = if self.edge {
+            "the Edge Runtime"
+        } else {
+            "the browser"
+        };
+        Ok(StringVc::cell(format!(
+            "The native Node.js addon \"{}\" can't be loaded in {}",
+            self.path.await?.file_name(),
+            runtime
+        )))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(if self.edge {
+            "Native addons are loaded by Node.js and can't run in the Edge Runtime. Use the \
+             Node.js runtime for code that depends on this module."
+                .to_string()
+        } else {
+            "Native addons are loaded by Node.js and can't run in the browser. Move the code that \
+             depends on this module into server-only code, such as `getServerSideProps` or a \
+             Server Component."
+                .to_string()
+        })
+    }
+}
+
+/// Handles queries on imports of files: `?raw` imports the contents of a file
+/// as a string, like webpack's `raw-loader`, `?url` imports the URL of the
+/// emitted file and `?inline` imports the file as a data URL, regardless of
+/// its size.
+#[turbo_tasks::value]
+pub(crate) struct AssetQueryResolvePlugin {
+    root: FileSystemPathVc,
+}
+
+#[turbo_tasks::value_impl]
+impl AssetQueryResolvePluginVc {
+    #[turbo_tasks::function]
+    pub fn new(root: FileSystemPathVc) -> Self {
+        AssetQueryResolvePlugin { root }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ResolvePlugin for AssetQueryResolvePlugin {
+    #[turbo_tasks::function]
+    fn after_resolve_condition(&self) -> ResolvePluginConditionVc {
+        ResolvePluginConditionVc::new(self.root.root(), GlobVc::new("**"))
+    }
+
+    #[turbo_tasks::function]
+    async fn after_resolve(
+        &self,
+        fs_path: FileSystemPathVc,
+        _context: FileSystemPathVc,
+        request: RequestVc,
+    ) -> Result<ResolveResultOptionVc> {
+        let (Request::Raw { query, .. }
+        | Request::Relative { query, .. }
+        | Request::Module { query, .. }) = &*request.await?
+        else {
+            return Ok(ResolveResultOptionVc::none());
+        };
+        let query = query.await?;
+        let Some(query) = query.as_ref() else {
+            return Ok(ResolveResultOptionVc::none());
+        };
+        let Some(kind) = ["raw", "inline", "url"]
+            .into_iter()
+            .find(|kind| query.contains_key(*kind))
+        else {
+            return Ok(ResolveResultOptionVc::none());
+        };
+
+        let file_name = fs_path.await?.file_name().to_string();
+        let code = if kind == "url" {
+            url_module_code(&file_name)
+        } else {
+            let FileContent::Content(file) = &*fs_path.read().await? else {
+                return Ok(ResolveResultOptionVc::none());
+            };
+            let content = file.content().to_bytes()?;
+            if kind == "raw" {
+                raw_module_code(&String::from_utf8_lossy(&content))
+            } else {
+                inline_module_code(&file_name, &content)
+            }
+        };
+        let module = VirtualSourceVc::new(
+            fs_path.parent().join(&format!("{file_name}.{kind}.js")),
+            FileContent::Content(code.into()).into(),
+        );
+
+        Ok(ResolveResultOptionVc::some(
+            ResolveResult::asset(module.into()).cell(),
+        ))
+    }
+}
+
+/// The code of the module that a `?raw` import of a file with `content`
+/// resolves to.
+fn raw_module_code(content: &str) -> String {
+    format!("export default {};\n", StringifyJs(content))
+}
+
+/// The code of the module that a `?url` import of the file `file_name`
+/// resolves to. The file is imported as usual, so that it's emitted, and
+/// images export their URL as `src`.
+fn url_module_code(file_name: &str) -> String {
+    format!(
+        "import asset from {};\nexport default typeof asset === \"object\" ? asset.src : asset;\n",
+        StringifyJs(&format!("./{file_name}"))
+    )
+}
+
+/// The code of the module that an `?inline` import of the file `file_name`
+/// with `content` resolves to.
+fn inline_module_code(file_name: &str, content: &[u8]) -> String {
+    let data_url = format!(
+        "data:{};base64,{}",
+        mime_type(file_name),
+        general_purpose::STANDARD.encode(content)
+    );
+    format!("export default {};\n", StringifyJs(&data_url))
+}
+
+fn mime_type(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("png") => "image/png",
+        Some("apng") => "image/apng",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("avif") => "image/avif",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("txt") => "text/plain",
+        Some("json") => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Reports requests for Node.js builtins that can't be provided in the
+/// current runtime. It's only used in fallback import maps, so it's only
+/// consulted when the request couldn't be resolved otherwise.
+#[turbo_tasks::value(shared)]
+pub(crate) struct UnsupportedNodeBuiltinReplacer {
+    builtin: String,
+    edge: bool,
+}
+
+#[turbo_tasks::value_impl]
+impl UnsupportedNodeBuiltinReplacerVc {
+    #[turbo_tasks::function]
+    pub fn new(builtin: &str, edge: bool) -> Self {
+        Self::cell(UnsupportedNodeBuiltinReplacer {
+            builtin: builtin.to_string(),
+            edge,
+        })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for UnsupportedNodeBuiltinReplacer {
+    #[turbo_tasks::function]
+    fn replace(&self, _capture: &str) -> ImportMappingVc {
+        ImportMapping::Ignore.into()
+    }
+
+    #[turbo_tasks::function]
+    fn result(&self, context: FileSystemPathVc, _request: RequestVc) -> ImportMapResultVc {
+        UnsupportedNodeBuiltinIssue {
+            context,
+            builtin: self.builtin.clone(),
+            edge: self.edge,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+
+        ImportMapResult::NoEntry.into()
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct UnsupportedNodeBuiltinIssue {
+    context: FileSystemPathVc,
+    builtin: String,
+    edge: bool,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnsupportedNodeBuiltinIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("resolve".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.context
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        let runtime = if self.edge {
+            "the Edge Runtime"
+        } else {
+            "the browser"
+        };
+        StringVc::cell(format!(
+            "The Node.js module \"{}\" is not supported in {}",
+            self.builtin, runtime
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(if self.edge {
+            "The Edge Runtime only provides a subset of the Node.js builtins. Use the Node.js \
+             runtime for code that depends on this module."
+                .to_string()
+        } else {
+            "There is no browser polyfill for this module. Move the code that depends on it into \
+             server-only code, such as `getServerSideProps` or a Server Component."
+                .to_string()
+        })
+    }
+}
+
+/// Resolves `#`-prefixed requests with the `imports` field of the nearest
+/// `package.json`, like Node.js' subpath imports. Conditional targets are
+/// matched against `conditions`.
+#[turbo_tasks::value(shared)]
+pub(crate) struct SubpathImportsReplacer {
+    conditions: Vec<String>,
+}
+
+#[turbo_tasks::value_impl]
+impl SubpathImportsReplacerVc {
+    #[turbo_tasks::function]
+    pub fn new(conditions: Vec<String>) -> Self {
+        Self::cell(SubpathImportsReplacer { conditions })
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ImportMappingReplacement for SubpathImportsReplacer {
+    #[turbo_tasks::function]
+    fn replace(self_vc: SubpathImportsReplacerVc, _capture: &str) -> ImportMappingVc {
+        // Registered as a `#` wildcard alias, so the whole request is looked up
+        // in `result` instead.
+        ImportMapping::Dynamic(self_vc.into()).into()
+    }
+
+    #[turbo_tasks::function]
+    async fn result(
+        &self,
+        context: FileSystemPathVc,
+        request: RequestVc,
+    ) -> Result<ImportMapResultVc> {
+        let Some(specifier) = request.await?.request() else {
+            return Ok(ImportMapResult::NoEntry.into());
+        };
+
+        let FindContextFileResult::Found(package_json, _) =
+            *find_context_file(context, package_json()).await?
+        else {
+            return Ok(unmapped_subpath_import(context, specifier, None));
+        };
+        let FileJsonContent::Content(package) = &*package_json.read_json().await? else {
+            return Ok(unmapped_subpath_import(
+                context,
+                specifier,
+                Some(package_json),
+            ));
+        };
+
+        let conditions = self
+            .conditions
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let targets = subpath_import_targets(&package["imports"], &specifier, &conditions);
+        if targets.is_empty() {
+            return Ok(unmapped_subpath_import(
+                context,
+                specifier,
+                Some(package_json),
+            ));
+        }
+
+        let package_dir = package_json.parent();
+        let mut results = targets
+            .into_iter()
+            .map(|target| {
+                ImportMapResult::Alias(
+                    RequestVc::parse(Value::new(Pattern::Constant(target))),
+                    Some(package_dir),
+                )
+            })
+            .collect::<Vec<_>>();
+        Ok(if results.len() == 1 {
+            results.remove(0).into()
+        } else {
+            ImportMapResult::Alternatives(results).into()
+        })
+    }
+}
+
+fn unmapped_subpath_import(
+    context: FileSystemPathVc,
+    specifier: String,
+    package_json: Option<FileSystemPathVc>,
+) -> ImportMapResultVc {
+    UnmappedSubpathImportIssue {
+        context,
+        specifier,
+        package_json,
+    }
+    .cell()
+    .as_issue()
+    .emit();
+
+    ImportMapResult::NoEntry.into()
+}
+
+/// Returns the targets of `specifier` in the `imports` field of a
+/// `package.json`. A key can contain a single `*`, which matches any string
+/// and is substituted into the target. Exact keys take precedence, then the
+/// pattern with the longest prefix.
+fn subpath_import_targets(
+    imports: &JsonValue,
+    specifier: &str,
+    conditions: &[&str],
+) -> Vec<String> {
+    let JsonValue::Object(imports) = imports else {
+        return Vec::new();
+    };
+
+    if let Some(target) = imports.get(specifier) {
+        let mut targets = Vec::new();
+        add_subpath_import_targets(target, None, conditions, &mut targets);
+        return targets;
+    }
+
+    let best_match = imports
+        .iter()
+        .filter_map(|(key, target)| {
+            let (prefix, suffix) = key.split_once('*')?;
+            let capture = specifier.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), capture, target))
+        })
+        .max_by_key(|(prefix_len, ..)| *prefix_len);
+
+    let mut targets = Vec::new();
+    if let Some((_, capture, target)) = best_match {
+        add_subpath_import_targets(target, Some(capture), conditions, &mut targets);
+    }
+    targets
+}
+
+fn add_subpath_import_targets(
+    target: &JsonValue,
+    capture: Option<&str>,
+    conditions: &[&str],
+    targets: &mut Vec<String>,
+) {
+    match target {
+        JsonValue::String(target) => targets.push(match capture {
+            Some(capture) => target.replace('*', capture),
+            None => target.clone(),
+        }),
+        JsonValue::Array(alternatives) => {
+            for alternative in alternatives {
+                add_subpath_import_targets(alternative, capture, conditions, targets);
+            }
+        }
+        // Node.js picks the first active condition in the object's order, which
+        // isn't preserved when parsing, so the runtime's conditions are preferred over
+        // the ones that are always active.
+        JsonValue::Object(conditional) => {
+            let target = conditions
+                .iter()
+                .chain(&["import", "require", "default"])
+                .find_map(|condition| conditional.get(*condition));
+            if let Some(target) = target {
+                add_subpath_import_targets(target, capture, conditions, targets);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct UnmappedSubpathImportIssue {
+    context: FileSystemPathVc,
+    specifier: String,
+    package_json: Option<FileSystemPathVc>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnmappedSubpathImportIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("resolve".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.context
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(format!(
+            "Subpath import \"{}\" is not defined",
+            self.specifier
+        ))
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<StringVc> {
+        Ok(StringVc::cell(match self.package_json {
+            Some(package_json) => format!(
+                "Requests starting with `#` are resolved with the `imports` field of the nearest \
+                 package.json, but {} has no entry matching \"{}\" for the current conditions.",
+                package_json.await?.path,
+                self.specifier
+            ),
+            None => "Requests starting with `#` are resolved with the `imports` field of the \
+                     nearest package.json, but there is no package.json in any parent directory."
+                .to_string(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{inline_module_code, raw_module_code, subpath_import_targets, url_module_code};
+
+    #[test]
+    fn test_raw_module_code() {
+        assert_eq!(
+            raw_module_code("Hello \"world\"\n"),
+            "export default \"Hello \\\"world\\\"\\n\";\n"
+        );
+    }
+
+    #[test]
+    fn test_url_module_code() {
+        assert_eq!(
+            url_module_code("logo.png"),
+            "import asset from \"./logo.png\";\nexport default typeof asset === \"object\" ? \
+             asset.src : asset;\n"
+        );
+    }
+
+    #[test]
+    fn test_inline_module_code() {
+        assert_eq!(
+            inline_module_code("pixel.PNG", b"\x89PNG"),
+            "export default \"data:image/png;base64,iVBORw==\";\n"
+        );
+        assert_eq!(
+            inline_module_code("data.bin", b"abc"),
+            "export default \"data:application/octet-stream;base64,YWJj\";\n"
+        );
+    }
+
+    #[test]
+    fn test_exact_subpath_import() {
+        let imports = json!({ "#utils": "./src/utils.js" });
+        assert_eq!(
+            subpath_import_targets(&imports, "#utils", &["browser"]),
+            vec!["./src/utils.js"]
+        );
+        assert!(subpath_import_targets(&imports, "#missing", &["browser"]).is_empty());
+    }
+
+    #[test]
+    fn test_pattern_subpath_import() {
+        let imports = json!({
+            "#lib/*": "./src/lib/*.js",
+            "#lib/internal/*": "./src/internal/*.js",
+        });
+        assert_eq!(
+            subpath_import_targets(&imports, "#lib/a", &[]),
+            vec!["./src/lib/a.js"]
+        );
+        assert_eq!(
+            subpath_import_targets(&imports, "#lib/internal/b", &[]),
+            vec!["./src/internal/b.js"]
+        );
+    }
+
+    #[test]
+    fn test_conditional_subpath_import() {
+        let imports = json!({
+            "#env": {
+                "browser": "./src/env.browser.js",
+                "node": "./src/env.node.js",
+                "default": "./src/env.js",
+            },
+        });
+        assert_eq!(
+            subpath_import_targets(&imports, "#env", &["browser"]),
+            vec!["./src/env.browser.js"]
+        );
+        assert_eq!(
+            subpath_import_targets(&imports, "#env", &["node"]),
+            vec!["./src/env.node.js"]
+        );
+        assert_eq!(
+            subpath_import_targets(&imports, "#env", &["edge-light"]),
+            vec!["./src/env.js"]
+        );
+    }
+}
@@ -25,7 +25,10 @@ use turbopack_binding::{
     },
 };
 
-use crate::{embed_js::next_js_file_path, util::get_asset_path_from_pathname};
+use crate::{
+    cache_control_source::CacheControlContentSourceVc, embed_js::next_js_file_path,
+    util::get_asset_path_from_pathname,
+};
 
 #[turbo_tasks::function]
 pub async fn create_page_loader(
@@ -44,7 +47,8 @@ pub async fn create_page_loader(
     }
     .cell();
 
-    Ok(AssetGraphContentSourceVc::new_lazy(server_root, asset.into()).into())
+    let source = AssetGraphContentSourceVc::new_lazy(server_root, asset.into());
+    Ok(CacheControlContentSourceVc::new(source.into()).into())
 }
 
 #[turbo_tasks::value(shared)]
@@ -47,9 +47,13 @@ use turbopack_binding::{
 };
 
 use crate::{
+    cache_query_params_source::CacheQueryParamsContentSourceVc,
+    circular_imports::emit_circular_import_issues,
+    conditional_source::ConditionalContentSourceVc,
     embed_js::next_asset,
     env::env_for_js,
     fallback::get_fallback_page,
+    large_page_data_source::LargePageDataContentSourceVc,
     mode::NextMode,
     next_client::{
         context::{
@@ -62,7 +66,9 @@ use crate::{
     next_client_chunks::client_chunks_transition::NextClientChunksTransitionVc,
     next_config::NextConfigVc,
     next_edge::{
-        context::{get_edge_compile_time_info, get_edge_resolve_options_context},
+        context::{
+            get_edge_compile_time_info, get_edge_resolve_options_context, get_edge_runtime_entries,
+        },
         route_transition::NextEdgeRouteTransition,
     },
     next_route_matcher::{
@@ -78,6 +84,7 @@ use crate::{
         PagesDirectoryStructure, PagesDirectoryStructureVc, PagesStructure, PagesStructureItem,
         PagesStructureVc,
     },
+    tenant_source::TenantHeaderContentSourceVc,
     util::{parse_config_from_source, pathname_for_path, render_data, NextRuntime, PathType},
 };
 
@@ -149,7 +156,7 @@ pub async fn create_page_source(
     .cell()
     .into();
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_root, server_addr);
+    let edge_compile_time_info = get_edge_compile_time_info(project_root, mode, server_addr);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_root,
@@ -160,14 +167,20 @@ pub async fn create_page_source(
     )
     .reference_chunk_source_maps(should_debug("page_source"))
     .build();
-    let edge_resolve_options_context =
-        get_edge_resolve_options_context(project_root, server_ty, next_config, execution_context);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_root,
+        server_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
 
     let next_edge_transition = NextEdgeRouteTransition {
         edge_compile_time_info,
         edge_chunking_context,
         edge_module_options_context: None,
         edge_resolve_options_context,
+        runtime_entries: get_edge_runtime_entries(project_root, env, next_config),
         output_path: node_root,
         base_path: project_root,
         bootstrap_asset: next_asset("entry/edge-bootstrap.ts"),
@@ -263,7 +276,7 @@ pub async fn create_page_source(
         next_config,
     );
 
-    let render_data = render_data(next_config, server_addr);
+    let render_data = render_data(next_config, env, server_addr);
     let page_extensions = next_config.page_extensions();
 
     let sources = vec![
@@ -283,6 +296,7 @@ pub async fn create_page_source(
             RouteType::Exact,
             NextExactMatcherVc::new(StringVc::cell("_next/404".to_string())).into(),
             render_data,
+            next_config,
         )
         .issue_context(pages_dir, "Next.js pages directory not found"),
         create_page_source_for_root_directory(
@@ -298,6 +312,7 @@ pub async fn create_page_source(
             client_root,
             node_root,
             render_data,
+            next_config,
         ),
         AssetGraphContentSourceVc::new_eager(client_root, fallback_page.as_asset())
             .as_content_source()
@@ -317,6 +332,7 @@ pub async fn create_page_source(
             RouteType::NotFound,
             NextFallbackMatcherVc::new().into(),
             render_data,
+            next_config,
         )
         .issue_context(pages_dir, "Next.js pages directory not found fallback"),
     ];
@@ -343,6 +359,7 @@ async fn create_page_source_for_file(
     node_path: FileSystemPathVc,
     node_root: FileSystemPathVc,
     render_data: JsonValueVc,
+    next_config: NextConfigVc,
 ) -> Result<ContentSourceVc> {
     let server_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -395,6 +412,7 @@ async fn create_page_source_for_file(
                 node_path,
                 node_root,
                 project_path,
+                next_config,
             }
             .cell()
             .into(),
@@ -419,6 +437,7 @@ async fn create_page_source_for_file(
             node_path,
             node_root,
             project_path,
+            next_config,
         }
         .cell()
         .into();
@@ -432,37 +451,80 @@ async fn create_page_source_for_file(
             node_path: data_node_path,
             node_root,
             project_path,
+            next_config,
         }
         .cell()
         .into();
 
+        let html_source = create_node_rendered_source(
+            project_path,
+            env,
+            base_segments.clone(),
+            route_type.clone(),
+            client_root,
+            route_matcher.into(),
+            pathname,
+            ssr_entry,
+            fallback_page,
+            render_data,
+            should_debug("page_source"),
+        );
+        let html_source = TenantHeaderContentSourceVc::new(
+            html_source,
+            base_segments.clone(),
+            route_type.clone(),
+            next_config,
+        );
+        let html_source = CacheQueryParamsContentSourceVc::new(
+            html_source.into(),
+            base_segments.clone(),
+            route_type.clone(),
+            next_config,
+        );
+        let html_source = ConditionalContentSourceVc::new(
+            html_source.into(),
+            base_segments,
+            route_type,
+            next_config,
+        );
+
+        let data_source = create_node_rendered_source(
+            project_path,
+            env,
+            data_base_segments.clone(),
+            data_route_type.clone(),
+            client_root,
+            data_route_matcher.into(),
+            pathname,
+            ssr_data_entry,
+            fallback_page,
+            render_data,
+            should_debug("page_source"),
+        );
+        let data_source = TenantHeaderContentSourceVc::new(
+            data_source,
+            data_base_segments.clone(),
+            data_route_type.clone(),
+            next_config,
+        );
+        let data_source = LargePageDataContentSourceVc::new(
+            data_source.into(),
+            data_base_segments.clone(),
+            data_route_type.clone(),
+            page_asset.ident().path(),
+            pathname,
+            next_config,
+        );
+        let data_source = CacheQueryParamsContentSourceVc::new(
+            data_source.into(),
+            data_base_segments,
+            data_route_type,
+            next_config,
+        );
+
         CombinedContentSourceVc::new(vec![
-            create_node_rendered_source(
-                project_path,
-                env,
-                base_segments.clone(),
-                route_type.clone(),
-                client_root,
-                route_matcher.into(),
-                pathname,
-                ssr_entry,
-                fallback_page,
-                render_data,
-                should_debug("page_source"),
-            ),
-            create_node_rendered_source(
-                project_path,
-                env,
-                data_base_segments,
-                data_route_type,
-                client_root,
-                data_route_matcher.into(),
-                pathname,
-                ssr_data_entry,
-                fallback_page,
-                render_data,
-                should_debug("page_source"),
-            ),
+            html_source.into(),
+            data_source.into(),
             create_page_loader(
                 client_root,
                 client_context,
@@ -506,6 +568,7 @@ async fn create_not_found_page_source(
     route_type: RouteType,
     route_matcher: RouteMatcherVc,
     render_data: JsonValueVc,
+    next_config: NextConfigVc,
 ) -> Result<ContentSourceVc> {
     let server_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -546,6 +609,7 @@ async fn create_not_found_page_source(
         node_path,
         node_root: node_path,
         project_path,
+        next_config,
     }
     .cell()
     .into();
@@ -559,19 +623,25 @@ async fn create_not_found_page_source(
     );
 
     Ok(CombinedContentSourceVc::new(vec![
-        create_node_rendered_source(
-            project_path,
-            env,
+        CacheQueryParamsContentSourceVc::new(
+            create_node_rendered_source(
+                project_path,
+                env,
+                base_segments.clone(),
+                route_type.clone(),
+                client_root,
+                route_matcher,
+                pathname,
+                ssr_entry,
+                fallback_page,
+                render_data,
+                should_debug("page_source"),
+            ),
             base_segments,
             route_type,
-            client_root,
-            route_matcher,
-            pathname,
-            ssr_entry,
-            fallback_page,
-            render_data,
-            should_debug("page_source"),
-        ),
+            next_config,
+        )
+        .into(),
         page_loader,
     ])
     .into())
@@ -594,6 +664,7 @@ async fn create_page_source_for_root_directory(
     client_root: FileSystemPathVc,
     node_root: FileSystemPathVc,
     render_data: JsonValueVc,
+    next_config: NextConfigVc,
 ) -> Result<ContentSourceVc> {
     let PagesStructure {
         app: _,
@@ -619,6 +690,7 @@ async fn create_page_source_for_root_directory(
             false,
             node_root,
             render_data,
+            next_config,
         ));
     }
 
@@ -637,6 +709,7 @@ async fn create_page_source_for_root_directory(
             true,
             node_root,
             render_data,
+            next_config,
         ));
     }
 
@@ -661,6 +734,7 @@ async fn create_page_source_for_directory(
     is_api_path: bool,
     node_root: FileSystemPathVc,
     render_data: JsonValueVc,
+    next_config: NextConfigVc,
 ) -> Result<ContentSourceVc> {
     let PagesDirectoryStructure {
         ref items,
@@ -691,6 +765,7 @@ async fn create_page_source_for_directory(
             node_root,
             node_root,
             render_data,
+            next_config,
         )
         .issue_context(
             project_path,
@@ -718,6 +793,7 @@ async fn create_page_source_for_directory(
             is_api_path,
             node_root,
             render_data,
+            next_config,
         ))
     }
 
@@ -766,6 +842,7 @@ pub struct SsrEntry {
     node_path: FileSystemPathVc,
     node_root: FileSystemPathVc,
     project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
 }
 
 #[derive(
@@ -798,6 +875,16 @@ impl SsrEntryVc {
         } else {
             this.ty
         };
+        if ty == SsrType::Html {
+            // Only reports unsupported config options like `amp`, the runtime of
+            // pages is decided when the page source is created.
+            parse_config_from_source(entry_asset_page).await?;
+        }
+        // The data entry renders the same modules as the HTML entry, which
+        // already reports their cycles.
+        if ty != SsrType::Data && *this.next_config.circular_import_warnings().await? {
+            emit_circular_import_issues(entry_asset_page.into(), this.project_path).await?;
+        }
         let (internal_asset, inner_assets): (_, IndexMap<_, AssetVc>) = match ty {
             SsrType::AutoApi => unreachable!(),
             SsrType::Api => (
@@ -1,8 +1,12 @@
 use anyhow::Result;
-use turbo_tasks::{primitives::StringsVc, CompletionVc};
+use turbo_tasks::{
+    primitives::{StringVc, StringsVc},
+    CompletionVc, ValueToString,
+};
 use turbo_tasks_fs::FileSystemPathOptionVc;
-use turbopack_binding::turbo::tasks_fs::{
-    DirectoryContent, DirectoryEntry, FileSystemEntryType, FileSystemPathVc,
+use turbopack_binding::{
+    turbo::tasks_fs::{DirectoryContent, DirectoryEntry, FileSystemEntryType, FileSystemPathVc},
+    turbopack::core::issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
 };
 
 use crate::{embed_js::next_js_file_path, next_config::NextConfigVc};
@@ -176,7 +180,9 @@ async fn get_pages_structure_for_root_directory(
             for (name, entry) in entries.iter() {
                 match entry {
                     DirectoryEntry::File(file_project_path) => {
-                        let Some(basename) = page_basename(name, page_extensions_raw) else {
+                        let Some((basename, extension_index)) =
+                            page_basename(name, page_extensions_raw)
+                        else {
                             continue;
                         };
                         match basename {
@@ -210,6 +216,7 @@ async fn get_pages_structure_for_root_directory(
                                 let item_original_path = next_router_path.join(basename);
                                 items.push((
                                     basename,
+                                    extension_index,
                                     PagesStructureItemVc::new(
                                         *file_project_path,
                                         item_next_router_path,
@@ -246,14 +253,14 @@ async fn get_pages_structure_for_root_directory(
         }
 
         // Ensure deterministic order since read_dir is not deterministic
-        items.sort_by_key(|(k, _)| *k);
+        emit_page_conflicts(dedupe_page_items(&mut items)).await?;
         children.sort_by_key(|(k, _)| *k);
 
         Some(
             PagesDirectoryStructure {
                 project_path: *project_path,
                 next_router_path,
-                items: items.into_iter().map(|(_, v)| v).collect(),
+                items: items.into_iter().map(|(_, _, v)| v).collect(),
                 children: children.into_iter().map(|(_, v)| v).collect(),
             }
             .cell(),
@@ -324,7 +331,9 @@ async fn get_pages_structure_for_directory(
         for (name, entry) in entries.iter() {
             match entry {
                 DirectoryEntry::File(file_project_path) => {
-                    let Some(basename) = page_basename(name, page_extensions_raw) else {
+                    let Some((basename, extension_index)) =
+                        page_basename(name, page_extensions_raw)
+                    else {
                         continue;
                     };
                     let item_next_router_path = match basename {
@@ -334,6 +343,7 @@ async fn get_pages_structure_for_directory(
                     let item_original_name = next_router_path.join(basename);
                     items.push((
                         basename,
+                        extension_index,
                         PagesStructureItemVc::new(
                             *file_project_path,
                             item_next_router_path,
@@ -358,7 +368,7 @@ async fn get_pages_structure_for_directory(
     }
 
     // Ensure deterministic order since read_dir is not deterministic
-    items.sort_by_key(|(k, _)| *k);
+    emit_page_conflicts(dedupe_page_items(&mut items)).await?;
 
     // Ensure deterministic order since read_dir is not deterministic
     children.sort_by_key(|(k, _)| *k);
@@ -366,21 +376,100 @@ async fn get_pages_structure_for_directory(
     Ok(PagesDirectoryStructure {
         project_path,
         next_router_path,
-        items: items.into_iter().map(|(_, v)| v).collect(),
+        items: items.into_iter().map(|(_, _, v)| v).collect(),
         children: children.into_iter().map(|(_, v)| v).collect(),
     }
     .cell())
 }
 
-fn page_basename<'a>(name: &'a str, page_extensions: &'a [String]) -> Option<&'a str> {
+/// Returns the basename of a page file and the index of its extension in
+/// `page_extensions`.
+fn page_basename<'a>(name: &'a str, page_extensions: &'a [String]) -> Option<(&'a str, usize)> {
     if let Some((basename, extension)) = name.rsplit_once('.') {
-        if page_extensions.iter().any(|allowed| allowed == extension) {
-            return Some(basename);
+        if let Some(index) = page_extensions
+            .iter()
+            .position(|allowed| allowed == extension)
+        {
+            return Some((basename, index));
         }
     }
     None
 }
 
+/// Sorts the items of a directory by basename and removes the items whose
+/// basename is taken by a file with an extension earlier in `pageExtensions`,
+/// e.g. `about.js` when there's also an `about.tsx`. Returns the removed items
+/// together with the items that were kept in their place.
+fn dedupe_page_items<T: Copy>(items: &mut Vec<(&str, usize, T)>) -> Vec<(T, T)> {
+    items.sort_by_key(|(basename, extension_index, _)| (*basename, *extension_index));
+    let mut conflicts = Vec::new();
+    items.dedup_by(|(basename, _, item), (kept_basename, _, kept_item)| {
+        let is_conflict = basename == kept_basename;
+        if is_conflict {
+            conflicts.push((*item, *kept_item));
+        }
+        is_conflict
+    });
+    conflicts
+}
+
+async fn emit_page_conflicts(
+    conflicts: Vec<(PagesStructureItemVc, PagesStructureItemVc)>,
+) -> Result<()> {
+    for (ignored, kept) in conflicts {
+        PageConflictIssue {
+            ignored: ignored.await?.project_path,
+            kept: kept.await?.project_path,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+    }
+    Ok(())
+}
+
+/// Two files in the pages directory map to the same route, so the one whose
+/// extension comes later in `pageExtensions` is ignored.
+#[turbo_tasks::value(shared)]
+struct PageConflictIssue {
+    ignored: FileSystemPathVc,
+    kept: FileSystemPathVc,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for PageConflictIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Warning.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("Duplicate page detected".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("next pages".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.ignored
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<StringVc> {
+        Ok(StringVc::cell(format!(
+            "{} and {} resolve to the same route. {} is ignored because its extension comes later \
+             in `pageExtensions`.",
+            self.kept.to_string().await?,
+            self.ignored.to_string().await?,
+            self.ignored.to_string().await?,
+        )))
+    }
+}
+
 fn next_router_path_for_basename(
     next_router_path: FileSystemPathVc,
     basename: &str,
@@ -391,3 +480,29 @@ fn next_router_path_for_basename(
         next_router_path.join(basename)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::dedupe_page_items;
+
+    #[test]
+    fn test_dedupe_page_items() {
+        // With the default `pageExtensions` of `["tsx", "ts", "jsx", "js"]`.
+        let mut items = vec![
+            ("index", 3, "index.js"),
+            ("about", 3, "about.js"),
+            ("contact", 1, "contact.ts"),
+            ("about", 0, "about.tsx"),
+        ];
+        let conflicts = dedupe_page_items(&mut items);
+        assert_eq!(
+            items,
+            vec![
+                ("about", 0, "about.tsx"),
+                ("contact", 1, "contact.ts"),
+                ("index", 3, "index.js"),
+            ]
+        );
+        assert_eq!(conflicts, vec![("about.js", "about.tsx")]);
+    }
+}
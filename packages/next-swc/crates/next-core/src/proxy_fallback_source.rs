@@ -0,0 +1,275 @@
+use anyhow::{bail, Result};
+use futures::TryStreamExt;
+use once_cell::sync::Lazy;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::{
+        core::{
+            introspect::{Introspectable, IntrospectableVc},
+            issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+        },
+        dev_server::source::{
+            route_tree::{RouteTreeVc, RouteType},
+            ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+            ContentSourceDataVary, ContentSourceDataVaryVc, ContentSourceVc,
+            GetContentSourceContent, GetContentSourceContentVc, ProxyResult,
+        },
+    },
+};
+
+use crate::next_config::NextConfigVc;
+
+/// Headers that only apply to a single connection, and are set anew for the
+/// request to the upstream server and for the proxied response.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "content-length",
+    "host",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+/// Proxies requests that no other source handles to an existing server, as
+/// configured by `experimental.proxyFallback`. This allows migrating an app
+/// to Next.js route by route, with the remaining routes still served by the
+/// existing server.
+///
+/// The method, headers and body of the request are forwarded, and the
+/// response of the server is passed through.
+#[turbo_tasks::value(shared)]
+pub struct ProxyFallbackContentSource {
+    project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl ProxyFallbackContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        project_path: FileSystemPathVc,
+        next_config: NextConfigVc,
+    ) -> ProxyFallbackContentSourceVc {
+        ProxyFallbackContentSource {
+            project_path,
+            next_config,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for ProxyFallbackContentSource {
+    #[turbo_tasks::function]
+    async fn get_routes(self_vc: ProxyFallbackContentSourceVc) -> Result<RouteTreeVc> {
+        let this = self_vc.await?;
+        if this.next_config.proxy_fallback().await?.is_none() {
+            return Ok(RouteTreeVc::empty());
+        }
+
+        Ok(RouteTreeVc::new_route(
+            Vec::new(),
+            RouteType::CatchAll,
+            self_vc.into(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for ProxyFallbackContentSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            method: true,
+            raw_headers: true,
+            raw_query: true,
+            body: true,
+            // The upstream server can respond differently to the same request.
+            cache_buster: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let ContentSourceData {
+            method: Some(method),
+            raw_headers: Some(raw_headers),
+            raw_query: Some(raw_query),
+            body: Some(body),
+            ..
+        } = &*data
+        else {
+            bail!("missing data for proxy fallback");
+        };
+
+        let proxy_fallback = self.next_config.proxy_fallback().await?;
+        let Some(config) = proxy_fallback.as_ref() else {
+            return Ok(ContentSourceContent::Next.cell());
+        };
+        let pathname = format!("/{path}");
+        if !config.includes(&pathname) {
+            return Ok(ContentSourceContent::Next.cell());
+        }
+
+        let mut body_stream = body.await?.read();
+        let mut request_body = Vec::new();
+        while let Some(data) = body_stream.try_next().await? {
+            request_body.extend_from_slice(&data);
+        }
+
+        let url = upstream_url(&config.upstream, &pathname, raw_query);
+        let mut request = CLIENT
+            .request(reqwest::Method::from_bytes(method.as_bytes())?, &url)
+            .body(request_body);
+        for (name, value) in raw_headers {
+            if !is_hop_by_hop_header(name) {
+                request = request.header(name, value);
+            }
+        }
+
+        let result = match proxy(request).await {
+            Ok(result) => result,
+            Err(err) => {
+                ProxyFallbackIssue {
+                    project_path: self.project_path,
+                    method: method.clone(),
+                    url,
+                    error: err.to_string(),
+                }
+                .cell()
+                .as_issue()
+                .emit();
+                ProxyResult {
+                    status: 502,
+                    headers: Vec::new(),
+                    body: "".into(),
+                }
+            }
+        };
+
+        Ok(ContentSourceContent::HttpProxy(result.cell()).cell())
+    }
+}
+
+async fn proxy(request: reqwest::RequestBuilder) -> reqwest::Result<ProxyResult> {
+    let response = request.send().await?;
+    let status = response.status().as_u16();
+    let headers = response
+        .headers()
+        .iter()
+        .filter(|(name, _)| !is_hop_by_hop_header(name.as_str()))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    let body = response.bytes().await?.to_vec();
+    Ok(ProxyResult {
+        status,
+        headers,
+        body: body.into(),
+    })
+}
+
+fn is_hop_by_hop_header(name: &str) -> bool {
+    HOP_BY_HOP_HEADERS
+        .iter()
+        .any(|header| header.eq_ignore_ascii_case(name))
+}
+
+/// A request couldn't be proxied to the upstream server of
+/// `experimental.proxyFallback`.
+#[turbo_tasks::value(shared)]
+struct ProxyFallbackIssue {
+    project_path: FileSystemPathVc,
+    method: String,
+    url: String,
+    error: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ProxyFallbackIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("Request couldn't be proxied to the upstream server".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("proxy fallback".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.project_path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(format!(
+            "{} {} failed: {}. Check that the server configured in \
+             `experimental.proxyFallback.upstream` is running.",
+            self.method, self.url, self.error
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for ProxyFallbackContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("proxy fallback source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell("proxies unmatched requests to an existing server.".to_string())
+    }
+}
+
+/// Joins the origin of the upstream server, like `http://localhost:4000`, with
+/// the pathname and the raw query of a request.
+fn upstream_url(upstream: &str, pathname: &str, raw_query: &str) -> String {
+    let upstream = upstream.trim_end_matches('/');
+    if raw_query.is_empty() {
+        format!("{upstream}{pathname}")
+    } else {
+        format!("{upstream}{pathname}?{raw_query}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::upstream_url;
+
+    #[test]
+    fn test_upstream_url() {
+        assert_eq!(
+            upstream_url("http://localhost:4000", "/legacy/about", ""),
+            "http://localhost:4000/legacy/about"
+        );
+        assert_eq!(
+            upstream_url("http://localhost:4000/", "/search", "q=a&page=2"),
+            "http://localhost:4000/search?q=a&page=2"
+        );
+        assert_eq!(
+            upstream_url("http://localhost:4000", "/", ""),
+            "http://localhost:4000/"
+        );
+    }
+}
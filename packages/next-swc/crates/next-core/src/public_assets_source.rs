@@ -0,0 +1,202 @@
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::{
+    turbo::tasks_fs::{
+        to_sys_path, DirectoryContent, DirectoryEntry, FileContent, FileSystemPathVc,
+    },
+    turbopack::{
+        core::{asset::AssetContent, introspect::Introspectable},
+        dev_server::source::{
+            route_tree::{BaseSegment, RouteTreeVc, RouteTreesVc, RouteType},
+            ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+            ContentSourceDataVary, ContentSourceDataVaryVc, GetContentSourceContent,
+            GetContentSourceContentVc, HeaderListVc, StaticContent,
+        },
+    },
+};
+
+use crate::{
+    cache_control_source::cache_control,
+    conditional_source::{etag, is_not_modified, not_modified, request_header, validators},
+    next_config::NextConfigVc,
+};
+
+/// Serves the files in the `public` directory, and answers conditional
+/// requests whose validators match with a `304 Not Modified`:
+///
+/// * With `generateEtags`, each file gets an `ETag` header with a hash of its
+///   content, which is compared to `If-None-Match`.
+/// * Each file gets a `Last-Modified` header with its modification time, which
+///   is compared to `If-Modified-Since`.
+///
+/// Files are revalidated on every request, unless their name contains a
+/// content hash.
+#[turbo_tasks::value(shared)]
+pub struct PublicAssetsContentSource {
+    public_dir: FileSystemPathVc,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl PublicAssetsContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        public_dir: FileSystemPathVc,
+        next_config: NextConfigVc,
+    ) -> PublicAssetsContentSourceVc {
+        PublicAssetsContentSource {
+            public_dir,
+            next_config,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for PublicAssetsContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(&self) -> RouteTreeVc {
+        get_routes_from_directory(self.public_dir, String::new(), self.next_config)
+    }
+}
+
+/// Creates an exact route for each file in `dir` and its subdirectories.
+/// `prefix` is the pathname of `dir` relative to the public directory.
+#[turbo_tasks::function]
+async fn get_routes_from_directory(
+    dir: FileSystemPathVc,
+    prefix: String,
+    next_config: NextConfigVc,
+) -> Result<RouteTreeVc> {
+    let DirectoryContent::Entries(entries) = &*dir.read_dir().await? else {
+        return Ok(RouteTreeVc::empty());
+    };
+
+    let routes = entries
+        .iter()
+        .filter_map(|(name, entry)| {
+            let pathname = format!("{prefix}{name}");
+            match entry {
+                DirectoryEntry::File(path) | DirectoryEntry::Symlink(path) => {
+                    Some(RouteTreeVc::new_route(
+                        BaseSegment::from_static_pathname(&pathname).collect(),
+                        RouteType::Exact,
+                        PublicAssetsContentSourceItem {
+                            path: *path,
+                            next_config,
+                        }
+                        .cell()
+                        .into(),
+                    ))
+                }
+                DirectoryEntry::Directory(path) => Some(get_routes_from_directory(
+                    *path,
+                    format!("{pathname}/"),
+                    next_config,
+                )),
+                _ => None,
+            }
+        })
+        .collect();
+    Ok(RouteTreesVc::cell(routes).merge())
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for PublicAssetsContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("public assets source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        self.public_dir.to_string()
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell(
+            "serves the files in the public directory with ETag and Last-Modified headers."
+                .to_string(),
+        )
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct PublicAssetsContentSourceItem {
+    path: FileSystemPathVc,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for PublicAssetsContentSourceItem {
+    /// The method and the headers of the request, which hold its validators.
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            method: true,
+            raw_headers: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let file_content = self.path.read();
+        let FileContent::Content(file) = &*file_content.await? else {
+            return Ok(ContentSourceContent::NotFound.cell());
+        };
+
+        let etag = if *self.next_config.generate_etags().await? {
+            Some(etag(&file.content().to_bytes()?))
+        } else {
+            None
+        };
+        let last_modified = last_modified(self.path).await?;
+        let validators = validators(etag.as_deref(), last_modified);
+        if matches!(data.method.as_deref(), Some("GET" | "HEAD"))
+            && is_not_modified(
+                request_header(&data, "if-none-match"),
+                request_header(&data, "if-modified-since"),
+                etag.as_deref(),
+                last_modified,
+            )
+        {
+            return Ok(not_modified(validators));
+        }
+
+        let mut headers = validators;
+        headers.push(("cache-control".to_string(), cache_control(path).to_string()));
+        Ok(ContentSourceContent::Static(
+            StaticContent {
+                content: AssetContent::File(file_content).cell().into(),
+                status_code: 200,
+                headers: HeaderListVc::new(headers),
+            }
+            .cell(),
+        )
+        .cell())
+    }
+}
+
+/// The modification time of a file on disk, in seconds since the Unix epoch.
+async fn last_modified(path: FileSystemPathVc) -> Result<Option<u64>> {
+    let Some(path) = to_sys_path(path).await? else {
+        return Ok(None);
+    };
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return Ok(None);
+    };
+    Ok(metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|modified| modified.as_secs()))
+}
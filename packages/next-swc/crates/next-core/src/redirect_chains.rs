@@ -0,0 +1,205 @@
+//! Detects redirects from `next.config.js` that send a request in a loop or
+//! through more redirects than `experimental.maxRedirectDepth` allows.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use turbo_tasks::{primitives::StringVc, CompletionVc};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::core::issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+};
+
+use crate::next_config::NextConfigVc;
+
+/// Emits a [RedirectChainIssue] for every redirect loop and every chain of
+/// redirects that is longer than the configured maximum depth.
+///
+/// Only redirects with static paths and without `has` or `missing`
+/// conditions are followed, as those are the only ones that are known to
+/// apply to their destination.
+#[turbo_tasks::function]
+pub async fn check_redirect_chains(
+    project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
+) -> Result<CompletionVc> {
+    let redirects = next_config.redirects().await?;
+    let max_depth = *next_config.max_redirect_depth().await? as usize;
+
+    let mut destinations = HashMap::new();
+    for redirect in redirects.iter() {
+        if redirect.has.is_some() || redirect.missing.is_some() {
+            continue;
+        }
+        if is_static_path(&redirect.source) && is_static_path(&redirect.destination) {
+            // The first matching redirect wins.
+            destinations
+                .entry(redirect.source.as_str())
+                .or_insert(redirect.destination.as_str());
+        }
+    }
+
+    let mut reported = HashSet::new();
+    for redirect in redirects.iter() {
+        let source = redirect.source.as_str();
+        if !destinations.contains_key(source) || reported.contains(source) {
+            continue;
+        }
+        if let Some(chain) = find_redirect_chain(&destinations, source, max_depth) {
+            let (chain, is_loop) = match chain {
+                RedirectChain::Loop(chain) => (chain, true),
+                RedirectChain::TooLong(chain) => (chain, false),
+            };
+            reported.extend(chain.iter().copied());
+            RedirectChainIssue {
+                path: project_path,
+                chain: chain.into_iter().map(ToOwned::to_owned).collect(),
+                is_loop,
+                max_depth,
+            }
+            .cell()
+            .as_issue()
+            .emit();
+        }
+    }
+
+    Ok(CompletionVc::new())
+}
+
+fn is_static_path(path: &str) -> bool {
+    path.starts_with('/') && !path.contains([':', '(', '*', '?', '{'])
+}
+
+#[derive(Debug, PartialEq)]
+enum RedirectChain<'a> {
+    /// The redirects lead back to a path of the chain, which is repeated at
+    /// its end.
+    Loop(Vec<&'a str>),
+    /// The redirects are followed more than the maximum depth allows.
+    TooLong(Vec<&'a str>),
+}
+
+/// Follows the redirects of `source` and returns the paths it is sent
+/// through when they loop or exceed `max_depth` redirects.
+fn find_redirect_chain<'a>(
+    destinations: &HashMap<&'a str, &'a str>,
+    source: &'a str,
+    max_depth: usize,
+) -> Option<RedirectChain<'a>> {
+    let mut chain = vec![source];
+    let mut path = source;
+    while let Some(&destination) = destinations.get(path) {
+        let is_loop = chain.contains(&destination);
+        chain.push(destination);
+        if is_loop {
+            return Some(RedirectChain::Loop(chain));
+        }
+        if chain.len() - 1 > max_depth {
+            return Some(RedirectChain::TooLong(chain));
+        }
+        path = destination;
+    }
+    None
+}
+
+#[turbo_tasks::value(shared)]
+struct RedirectChainIssue {
+    path: FileSystemPathVc,
+    chain: Vec<String>,
+    is_loop: bool,
+    max_depth: usize,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for RedirectChainIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(if self.is_loop {
+            format!("Redirect loop detected for \"{}\"", self.chain[0])
+        } else {
+            format!("Too many redirects for \"{}\"", self.chain[0])
+        })
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        let chain = self.chain.join(" -> ");
+        StringVc::cell(if self.is_loop {
+            format!(
+                "The redirects in next.config.js send requests in a loop: {chain}. Change one of \
+                 them to break the loop."
+            )
+        } else {
+            format!(
+                "The redirects in next.config.js send requests through more than {} redirects: \
+                 {chain}. Redirect to the final destination directly, or raise \
+                 `experimental.maxRedirectDepth`.",
+                self.max_depth
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{find_redirect_chain, is_static_path, RedirectChain};
+
+    #[test]
+    fn test_is_static_path() {
+        assert!(is_static_path("/old"));
+        assert!(is_static_path("/docs/intro"));
+        assert!(!is_static_path("/blog/:slug"));
+        assert!(!is_static_path("/docs/:path*"));
+        assert!(!is_static_path("/post/(\\d+)"));
+        assert!(!is_static_path("/search?q=next"));
+        assert!(!is_static_path("https://example.com/"));
+    }
+
+    #[test]
+    fn test_find_redirect_chain() {
+        let destinations: HashMap<_, _> = [("/a", "/b"), ("/b", "/c"), ("/c", "/a"), ("/d", "/c")]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            find_redirect_chain(&destinations, "/a", 10),
+            Some(RedirectChain::Loop(vec!["/a", "/b", "/c", "/a"]))
+        );
+        assert_eq!(
+            find_redirect_chain(&destinations, "/d", 10),
+            Some(RedirectChain::Loop(vec!["/d", "/c", "/a", "/b", "/c"]))
+        );
+
+        let destinations: HashMap<_, _> = [("/self", "/self")].into_iter().collect();
+        assert_eq!(
+            find_redirect_chain(&destinations, "/self", 10),
+            Some(RedirectChain::Loop(vec!["/self", "/self"]))
+        );
+
+        let destinations: HashMap<_, _> = [("/1", "/2"), ("/2", "/3"), ("/3", "/4")]
+            .into_iter()
+            .collect();
+        assert_eq!(find_redirect_chain(&destinations, "/1", 3), None);
+        assert_eq!(
+            find_redirect_chain(&destinations, "/1", 2),
+            Some(RedirectChain::TooLong(vec!["/1", "/2", "/3", "/4"]))
+        );
+        assert_eq!(find_redirect_chain(&destinations, "/2", 2), None);
+    }
+}
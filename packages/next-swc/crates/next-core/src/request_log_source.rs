@@ -0,0 +1,254 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use indexmap::IndexSet;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::{
+        core::{
+            introspect::{Introspectable, IntrospectableChildrenVc, IntrospectableVc},
+            issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+        },
+        dev_server::source::{
+            route_tree::{RouteTreeVc, RouteType},
+            ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+            ContentSourceDataFilter, ContentSourceDataVary, ContentSourceDataVaryVc,
+            ContentSourceVc, GetContentSourceContent, GetContentSourceContentVc,
+        },
+    },
+};
+
+/// Logs every request handled by the inner source with the source that
+/// responded, the kind of response, its status and the time it took, to
+/// help understand routing decisions. Enabled by `--log-requests`.
+///
+/// Each entry is emitted as a [RequestLogIssue] and printed by the issue
+/// reporter of the dev server. All request data is passed through, including a
+/// cache buster, so that every request runs in its own task and is logged. This
+/// defeats caching of the inner sources' responses and is only meant for
+/// debugging.
+#[turbo_tasks::value(shared)]
+pub struct RequestLogContentSource {
+    inner: ContentSourceVc,
+    project_path: FileSystemPathVc,
+}
+
+#[turbo_tasks::value_impl]
+impl RequestLogContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: ContentSourceVc,
+        project_path: FileSystemPathVc,
+    ) -> RequestLogContentSourceVc {
+        RequestLogContentSource {
+            inner,
+            project_path,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for RequestLogContentSource {
+    #[turbo_tasks::function]
+    fn get_routes(self_vc: RequestLogContentSourceVc) -> RouteTreeVc {
+        RouteTreeVc::new_route(Vec::new(), RouteType::CatchAll, self_vc.into())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for RequestLogContentSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            method: true,
+            url: true,
+            original_url: true,
+            raw_headers: true,
+            headers: Some(ContentSourceDataFilter::All),
+            raw_query: true,
+            query: Some(ContentSourceDataFilter::All),
+            body: true,
+            cache_buster: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let start = Instant::now();
+        let mut source = None;
+        let mut content = ContentSourceContent::Next.cell();
+        for get_content in self.inner.get_routes().get(path).await?.iter() {
+            let inner_content = get_content.get(path, data.clone());
+            if matches!(*inner_content.await?, ContentSourceContent::Next) {
+                continue;
+            }
+            source = match IntrospectableVc::resolve_from(*get_content).await? {
+                Some(introspectable) => Some(introspectable.ty().await?.clone_value()),
+                None => Some("unknown".to_string()),
+            };
+            content = inner_content;
+            break;
+        }
+
+        let (kind, status) = match &*content.await? {
+            ContentSourceContent::Static(content) => ("static", Some(content.await?.status_code)),
+            ContentSourceContent::HttpProxy(result) => ("dynamic", Some(result.await?.status)),
+            ContentSourceContent::Rewrite(_) => ("rewrite", None),
+            ContentSourceContent::NotFound | ContentSourceContent::Next => ("not found", Some(404)),
+        };
+        RequestLogIssue {
+            project_path: self.project_path,
+            title: format!("{} /{path}", data.method.as_deref().unwrap_or("GET")),
+            details: format_request_log(&RequestLog {
+                source: source.as_deref(),
+                kind,
+                status,
+                duration: start.elapsed(),
+            }),
+        }
+        .cell()
+        .as_issue()
+        .emit();
+
+        Ok(content)
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for RequestLogContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("request log source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell("logs every request with the source that responded.".to_string())
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<IntrospectableChildrenVc> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = IntrospectableVc::resolve_from(self.inner).await? {
+            children.insert((StringVc::cell("inner".to_string()), inner));
+        }
+        Ok(IntrospectableChildrenVc::cell(children))
+    }
+}
+
+/// A request handled by a [RequestLogContentSource].
+#[turbo_tasks::value(shared)]
+pub struct RequestLogIssue {
+    project_path: FileSystemPathVc,
+    /// The method and path of the request.
+    title: String,
+    /// The formatted [RequestLog].
+    details: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for RequestLogIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Info.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(self.title.clone())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("request".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.project_path
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(self.details.clone())
+    }
+}
+
+struct RequestLog<'a> {
+    /// The type of the source that responded, if any did.
+    source: Option<&'a str>,
+    /// `static`, `dynamic`, `rewrite` or `not found`.
+    kind: &'a str,
+    /// Rewrites are followed by the dev server and have no status yet.
+    status: Option<u16>,
+    duration: Duration,
+}
+
+/// Formats the details of a log entry as `logfmt`, e.g.
+/// `source="next.js page source" kind=dynamic status=200 duration=12ms`.
+fn format_request_log(log: &RequestLog) -> String {
+    fn value(value: &str) -> String {
+        if value.is_empty() || value.contains([' ', '"', '=']) {
+            format!("{value:?}")
+        } else {
+            value.to_string()
+        }
+    }
+
+    let mut line = String::new();
+    if let Some(source) = log.source {
+        line.push_str(&format!("source={} ", value(source)));
+    }
+    line.push_str(&format!("kind={}", value(log.kind)));
+    if let Some(status) = log.status {
+        line.push_str(&format!(" status={status}"));
+    }
+    line.push_str(&format!(" duration={}ms", log.duration.as_millis()));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_request_log, RequestLog};
+
+    #[test]
+    fn test_format_request_log() {
+        assert_eq!(
+            format_request_log(&RequestLog {
+                source: Some("next.js page source"),
+                kind: "dynamic",
+                status: Some(200),
+                duration: Duration::from_millis(12),
+            }),
+            r#"source="next.js page source" kind=dynamic status=200 duration=12ms"#
+        );
+        assert_eq!(
+            format_request_log(&RequestLog {
+                source: None,
+                kind: "not found",
+                status: Some(404),
+                duration: Duration::from_micros(300),
+            }),
+            r#"kind="not found" status=404 duration=0ms"#
+        );
+        assert_eq!(
+            format_request_log(&RequestLog {
+                source: Some("router"),
+                kind: "rewrite",
+                status: None,
+                duration: Duration::from_millis(3),
+            }),
+            "source=router kind=rewrite duration=3ms"
+        );
+    }
+}
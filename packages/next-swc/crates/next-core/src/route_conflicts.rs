@@ -0,0 +1,174 @@
+//! Detects routes that are defined by both the app and the pages directory,
+//! which Next.js doesn't allow.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_recursion::async_recursion;
+use turbo_tasks::{primitives::StringVc, CompletionVc};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::core::issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+};
+
+use crate::{
+    app_structure::{get_entrypoints, OptionAppDirVc},
+    next_config::NextConfigVc,
+    pages_structure::{PagesDirectoryStructureVc, PagesStructureVc},
+};
+
+/// Emits an [AppPagesConflictIssue] for every page in the pages directory
+/// whose pathname is also defined in the app directory.
+#[turbo_tasks::function]
+pub async fn check_app_pages_conflicts(
+    app_dir: OptionAppDirVc,
+    pages_structure: PagesStructureVc,
+    next_router_root: FileSystemPathVc,
+    next_config: NextConfigVc,
+) -> Result<CompletionVc> {
+    let Some(app_dir) = *app_dir.await? else {
+        return Ok(CompletionVc::new());
+    };
+    let entrypoints = get_entrypoints(app_dir, next_config.page_extensions()).await?;
+    let app_pathnames: HashMap<_, _> = entrypoints
+        .keys()
+        .map(|pathname| (normalize_pathname(pathname), pathname.as_str()))
+        .collect();
+
+    let pages_structure = pages_structure.await?;
+    let mut pages = Vec::new();
+    for directory in [pages_structure.api, pages_structure.pages]
+        .into_iter()
+        .flatten()
+    {
+        collect_pages(directory, next_router_root, &mut pages).await?;
+    }
+
+    for (pathname, page) in pages {
+        if let Some(app_pathname) = app_pathnames.get(&normalize_pathname(&pathname)) {
+            AppPagesConflictIssue {
+                page,
+                pathname,
+                app_pathname: app_pathname.to_string(),
+            }
+            .cell()
+            .as_issue()
+            .emit();
+        }
+    }
+
+    Ok(CompletionVc::new())
+}
+
+#[async_recursion]
+async fn collect_pages(
+    directory: PagesDirectoryStructureVc,
+    next_router_root: FileSystemPathVc,
+    pages: &mut Vec<(String, FileSystemPathVc)>,
+) -> Result<()> {
+    let router_root = next_router_root.await?;
+    let directory = directory.await?;
+    for item in directory.items.iter() {
+        let item = item.await?;
+        if let Some(path) = router_root.get_path_to(&*item.next_router_path.await?) {
+            pages.push((format!("/{path}"), item.project_path));
+        }
+    }
+    for child in directory.children.iter() {
+        collect_pages(*child, next_router_root, pages).await?;
+    }
+    Ok(())
+}
+
+/// Normalizes a pathname so that pathnames that match the same requests are
+/// equal: `/blog/index` is `/blog`, and dynamic segments are compared
+/// regardless of their parameter names.
+fn normalize_pathname(pathname: &str) -> String {
+    let mut segments: Vec<_> = pathname
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            if segment.starts_with("[[...") {
+                "[[...]]"
+            } else if segment.starts_with("[...") {
+                "[...]"
+            } else if segment.starts_with('[') {
+                "[]"
+            } else {
+                segment
+            }
+        })
+        .collect();
+    if segments.last() == Some(&"index") {
+        segments.pop();
+    }
+    format!("/{}", segments.join("/"))
+}
+
+#[turbo_tasks::value(shared)]
+struct AppPagesConflictIssue {
+    page: FileSystemPathVc,
+    pathname: String,
+    app_pathname: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for AppPagesConflictIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("Conflicting app and page file was found".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("routing".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.page
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(format!(
+            "The page at \"{}\" conflicts with the route \"{}\" of the app directory. Remove one \
+             of them to continue.",
+            self.pathname, self.app_pathname
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_pathname;
+
+    #[test]
+    fn test_normalize_pathname() {
+        assert_eq!(normalize_pathname("/"), "/");
+        assert_eq!(normalize_pathname("/index"), "/");
+        assert_eq!(normalize_pathname("/blog/index"), "/blog");
+        assert_eq!(normalize_pathname("/blog/"), "/blog");
+        assert_eq!(
+            normalize_pathname("/blog/[slug]"),
+            normalize_pathname("/blog/[id]")
+        );
+        assert_eq!(
+            normalize_pathname("/docs/[...path]"),
+            normalize_pathname("/docs/[...slug]")
+        );
+        assert_ne!(
+            normalize_pathname("/docs/[...path]"),
+            normalize_pathname("/docs/[[...path]]")
+        );
+        assert_ne!(
+            normalize_pathname("/docs/[path]"),
+            normalize_pathname("/docs/[...path]")
+        );
+    }
+}
@@ -12,6 +12,7 @@ use turbo_tasks_fs::json::parse_json_with_source_context;
 use turbopack_binding::{
     turbo::{
         tasks_bytes::{Bytes, Stream},
+        tasks_env::ProcessEnvVc,
         tasks_fs::{to_sys_path, File, FileSystemPathVc},
     },
     turbopack::{
@@ -46,7 +47,9 @@ use crate::{
     mode::NextMode,
     next_config::NextConfigVc,
     next_edge::{
-        context::{get_edge_compile_time_info, get_edge_resolve_options_context},
+        context::{
+            get_edge_compile_time_info, get_edge_resolve_options_context, get_edge_runtime_entries,
+        },
         route_transition::NextEdgeRouteTransition,
     },
     next_import_map::get_next_build_import_map,
@@ -65,7 +68,7 @@ fn next_configs() -> StringsVc {
 }
 
 #[turbo_tasks::function]
-async fn middleware_files(page_extensions: StringsVc) -> Result<StringsVc> {
+pub(crate) async fn middleware_files(page_extensions: StringsVc) -> Result<StringsVc> {
     let extensions = page_extensions.await?;
     let files = ["middleware.", "src/middleware."]
         .into_iter()
@@ -230,10 +233,12 @@ fn edge_transition_map(
     server_addr: ServerAddrVc,
     project_path: FileSystemPathVc,
     output_path: FileSystemPathVc,
+    env: ProcessEnvVc,
     next_config: NextConfigVc,
     execution_context: ExecutionContextVc,
 ) -> TransitionsByNameVc {
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(project_path, NextMode::Development, server_addr);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -248,6 +253,7 @@ fn edge_transition_map(
     let edge_resolve_options_context = get_edge_resolve_options_context(
         project_path,
         Value::new(ServerContextType::Middleware),
+        NextMode::Development,
         next_config,
         execution_context,
     );
@@ -265,6 +271,7 @@ fn edge_transition_map(
         edge_chunking_context,
         edge_module_options_context: Some(server_module_options_context),
         edge_resolve_options_context,
+        runtime_entries: get_edge_runtime_entries(project_path, env, next_config),
         output_path: output_path.root(),
         base_path: project_path,
         bootstrap_asset: next_asset("entry/edge-bootstrap.ts"),
@@ -339,6 +346,7 @@ async fn route_internal(
             server_addr,
             project_path,
             chunking_context.output_root(),
+            env,
             next_config,
             execution_context,
         )),
@@ -0,0 +1,95 @@
+//! The precedence of the content sources that make up the dev server.
+//!
+//! A [CombinedContentSource] resolves a request with the first source that
+//! matches, so the order of its sources decides which one wins when paths
+//! overlap. Instead of relying on the order in which sources are pushed, the
+//! top-level source is assembled from sources tagged with a
+//! [ContentSourcePriority].
+//!
+//! Redirects, rewrites and middleware are not part of this list. They are
+//! handled by the router before any of these sources is consulted.
+
+use turbopack_binding::turbopack::dev_server::source::{
+    combined::{CombinedContentSource, CombinedContentSourceVc},
+    ContentSourceVc,
+};
+
+/// The priority of a top-level content source. Sources with a lower variant
+/// take precedence over sources with a higher one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContentSourcePriority {
+    /// Dev-only manifests, like
+    /// `/_next/static/development/_devPagesManifest.json`.
+    Manifest,
+    /// Files in the `public` directory. Like with `next start`, a public file
+    /// shadows a page with the same path.
+    PublicFiles,
+    /// Routes from the `app` directory. An app route takes precedence over a
+    /// page with the same path.
+    App,
+    /// Routes from the `pages` directory.
+    Pages,
+    /// Additional entries passed to the dev server.
+    WebEntry,
+    /// The existing server that requests no other source handles are proxied
+    /// to, see `experimental.proxyFallback`.
+    ProxyFallback,
+}
+
+/// Orders `sources` by priority. Sources with the same priority keep the
+/// order in which they were passed.
+pub fn sort_by_priority<T>(mut sources: Vec<(ContentSourcePriority, T)>) -> Vec<T> {
+    sources.sort_by_key(|(priority, _)| *priority);
+    sources.into_iter().map(|(_, source)| source).collect()
+}
+
+/// Combines `sources` into a single content source that resolves requests
+/// according to the sources' priority.
+pub fn combine_prioritized_sources(
+    sources: Vec<(ContentSourcePriority, ContentSourceVc)>,
+) -> CombinedContentSourceVc {
+    CombinedContentSource {
+        sources: sort_by_priority(sources),
+    }
+    .cell()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sort_by_priority, ContentSourcePriority};
+
+    #[test]
+    fn test_overlapping_sources_order() {
+        // All of these can serve `/about`, e.g. `public/about`, `app/about/page.js`
+        // and `pages/about.js`.
+        let sources = vec![
+            (ContentSourcePriority::WebEntry, "web entry"),
+            (ContentSourcePriority::Pages, "pages/about.js"),
+            (ContentSourcePriority::App, "app/about/page.js"),
+            (ContentSourcePriority::PublicFiles, "public/about"),
+            (ContentSourcePriority::Manifest, "manifest"),
+        ];
+
+        assert_eq!(
+            sort_by_priority(sources),
+            vec![
+                "manifest",
+                "public/about",
+                "app/about/page.js",
+                "pages/about.js",
+                "web entry",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_same_priority_is_stable() {
+        let sources = vec![
+            (ContentSourcePriority::Pages, "first"),
+            (ContentSourcePriority::App, "app"),
+            (ContentSourcePriority::Pages, "second"),
+        ];
+
+        assert_eq!(sort_by_priority(sources), vec!["app", "first", "second"]);
+    }
+}
@@ -0,0 +1,370 @@
+use anyhow::{bail, Result};
+use indexmap::IndexSet;
+use turbo_tasks::{primitives::StringVc, Value};
+use turbopack_binding::{
+    turbo::tasks_fs::{FileSystemEntryType, FileSystemPathVc},
+    turbopack::{
+        core::introspect::{Introspectable, IntrospectableChildrenVc, IntrospectableVc},
+        dev_server::source::{
+            route_tree::{BaseSegment, RouteTreeVc, RouteType},
+            ContentSource, ContentSourceContent, ContentSourceContentVc, ContentSourceData,
+            ContentSourceDataVary, ContentSourceDataVaryVc, ContentSourceVc,
+            GetContentSourceContent, GetContentSourceContentVc, RewriteBuilder,
+        },
+    },
+};
+
+use crate::next_config::{NextConfigVc, TenantRoutingConfig};
+
+/// The request header that holds the tenant of the host when a page is
+/// rendered.
+const TENANT_HEADER: &str = "x-next-tenant";
+
+/// The prefixes of the paths that are served by the dev server itself, like
+/// chunks, HMR and error overlays, which are never rewritten for a tenant.
+const INTERNAL_PATH_PREFIXES: &[&str] = &[
+    "_next/",
+    "__nextjs",
+    "__turbopack",
+    "__turbo_tasks__",
+    "turbopack-hmr",
+];
+
+/// Rewrites requests for a tenant's host to the tenant's path, as configured
+/// by `experimental.tenantRouting`. For example, with a host pattern of
+/// `*.example.com` and a rewrite of `/_tenants/:tenant/:path*`, a request for
+/// `tenant1.example.com/about` is handled by `/_tenants/tenant1/about`, so
+/// that pages receive the tenant as a route param.
+///
+/// Internal paths, like `_next/static`, and the files in `public_dir` are
+/// served as they are.
+#[turbo_tasks::value(shared)]
+pub struct TenantContentSource {
+    inner: ContentSourceVc,
+    public_dir: FileSystemPathVc,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl TenantContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: ContentSourceVc,
+        public_dir: FileSystemPathVc,
+        next_config: NextConfigVc,
+    ) -> TenantContentSourceVc {
+        TenantContentSource {
+            inner,
+            public_dir,
+            next_config,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for TenantContentSource {
+    #[turbo_tasks::function]
+    async fn get_routes(self_vc: TenantContentSourceVc) -> Result<RouteTreeVc> {
+        let this = self_vc.await?;
+        let has_rewrite = this
+            .next_config
+            .tenant_routing()
+            .await?
+            .as_ref()
+            .map_or(false, |config| config.rewrite.is_some());
+        if !has_rewrite {
+            return Ok(this.inner.get_routes());
+        }
+
+        Ok(RouteTreeVc::new_route(
+            Vec::new(),
+            RouteType::CatchAll,
+            self_vc.into(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for TenantContentSource {
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            raw_headers: true,
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let ContentSourceData {
+            raw_headers: Some(raw_headers),
+            raw_query: Some(raw_query),
+            ..
+        } = &*data
+        else {
+            bail!("missing data for tenant routing");
+        };
+
+        let tenant_routing = self.next_config.tenant_routing().await?;
+        let rewrite = match tenant_routing.as_ref() {
+            Some(config) => request_tenant(raw_headers, config).zip(config.rewrite.as_deref()),
+            None => None,
+        };
+        let pathname = match rewrite {
+            Some((tenant, rewrite)) if !self.is_served_as_is(path).await? => {
+                expand_tenant_rewrite(rewrite, tenant, path)
+            }
+            _ => format!("/{path}"),
+        };
+
+        let path_and_query = if raw_query.is_empty() {
+            pathname
+        } else {
+            format!("{pathname}?{raw_query}")
+        };
+
+        Ok(ContentSourceContent::Rewrite(
+            RewriteBuilder::new_source_with_path_and_query(self.inner, path_and_query).build(),
+        )
+        .cell())
+    }
+}
+
+impl TenantContentSource {
+    /// Whether a path is served without a rewrite, as it's internal or a file
+    /// in the public directory.
+    async fn is_served_as_is(&self, path: &str) -> Result<bool> {
+        if INTERNAL_PATH_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            return Ok(true);
+        }
+        Ok(matches!(
+            &*self.public_dir.join(path).get_type().await?,
+            FileSystemEntryType::File
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for TenantContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("tenant source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell("rewrites requests by the subdomain of their host.".to_string())
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<IntrospectableChildrenVc> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = IntrospectableVc::resolve_from(self.inner).await? {
+            children.insert((StringVc::cell("inner".to_string()), inner));
+        }
+        Ok(IntrospectableChildrenVc::cell(children))
+    }
+}
+
+/// Passes the tenant of the request's host to the inner rendered source in an
+/// `x-next-tenant` header, so pages can branch per tenant, whether or not
+/// their requests are rewritten. A header of the same name sent by the client
+/// is dropped.
+///
+/// `base_segments` and `route_type` are the ones of the inner source, so the
+/// page keeps its place in the route tree.
+#[turbo_tasks::value(shared)]
+pub struct TenantHeaderContentSource {
+    inner: ContentSourceVc,
+    base_segments: Vec<BaseSegment>,
+    route_type: RouteType,
+    next_config: NextConfigVc,
+}
+
+#[turbo_tasks::value_impl]
+impl TenantHeaderContentSourceVc {
+    #[turbo_tasks::function]
+    pub fn new(
+        inner: ContentSourceVc,
+        base_segments: Vec<BaseSegment>,
+        route_type: RouteType,
+        next_config: NextConfigVc,
+    ) -> TenantHeaderContentSourceVc {
+        TenantHeaderContentSource {
+            inner,
+            base_segments,
+            route_type,
+            next_config,
+        }
+        .cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl ContentSource for TenantHeaderContentSource {
+    #[turbo_tasks::function]
+    async fn get_routes(self_vc: TenantHeaderContentSourceVc) -> Result<RouteTreeVc> {
+        let this = self_vc.await?;
+        if this.next_config.tenant_routing().await?.is_none() {
+            return Ok(this.inner.get_routes());
+        }
+
+        Ok(RouteTreeVc::new_route(
+            this.base_segments.clone(),
+            this.route_type.clone(),
+            self_vc.into(),
+        ))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl GetContentSourceContent for TenantHeaderContentSource {
+    /// The request data a rendered source varies on.
+    #[turbo_tasks::function]
+    fn vary(&self) -> ContentSourceDataVaryVc {
+        ContentSourceDataVary {
+            method: true,
+            url: true,
+            original_url: true,
+            raw_headers: true,
+            raw_query: true,
+            ..Default::default()
+        }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn get(
+        &self,
+        path: &str,
+        data: Value<ContentSourceData>,
+    ) -> Result<ContentSourceContentVc> {
+        let tenant_routing = self.next_config.tenant_routing().await?;
+        let mut data = data.into_value();
+        if let Some(raw_headers) = &mut data.raw_headers {
+            let tenant = tenant_routing
+                .as_ref()
+                .and_then(|config| request_tenant(raw_headers, config))
+                .map(str::to_string);
+            raw_headers.retain(|(name, _)| !name.eq_ignore_ascii_case(TENANT_HEADER));
+            if let Some(tenant) = tenant {
+                raw_headers.push((TENANT_HEADER.to_string(), tenant));
+            }
+        }
+        let data = Value::new(data);
+
+        for get_content in self.inner.get_routes().get(path).await?.iter() {
+            let content = get_content.get(path, data.clone());
+            if !matches!(*content.await?, ContentSourceContent::Next) {
+                return Ok(content);
+            }
+        }
+        Ok(ContentSourceContent::Next.cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Introspectable for TenantHeaderContentSource {
+    #[turbo_tasks::function]
+    fn ty(&self) -> StringVc {
+        StringVc::cell("tenant header source".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn details(&self) -> StringVc {
+        StringVc::cell("passes the tenant of the host to the rendered page.".to_string())
+    }
+
+    #[turbo_tasks::function]
+    async fn children(&self) -> Result<IntrospectableChildrenVc> {
+        let mut children = IndexSet::new();
+        if let Some(inner) = IntrospectableVc::resolve_from(self.inner).await? {
+            children.insert((StringVc::cell("inner".to_string()), inner));
+        }
+        Ok(IntrospectableChildrenVc::cell(children))
+    }
+}
+
+/// The tenant of the `Host` header of a request, if it matches the host
+/// pattern.
+fn request_tenant<'a>(
+    raw_headers: &'a [(String, String)],
+    config: &TenantRoutingConfig,
+) -> Option<&'a str> {
+    let host = raw_headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.as_str())?;
+    tenant_from_host(host, &config.host_pattern)
+}
+
+/// Extracts the tenant from a `Host` header value, given a pattern like
+/// `*.example.com`. The tenant is a single, non-empty subdomain label.
+fn tenant_from_host<'a>(host: &'a str, host_pattern: &str) -> Option<&'a str> {
+    let hostname = match host.rsplit_once(':') {
+        Some((hostname, port)) if port.bytes().all(|b| b.is_ascii_digit()) => hostname,
+        _ => host,
+    };
+    let (prefix, suffix) = host_pattern.split_once('*')?;
+    let tenant = hostname
+        .strip_prefix(prefix)?
+        .strip_suffix(suffix)
+        .filter(|tenant| !tenant.is_empty() && !tenant.contains('.'))?;
+    Some(tenant)
+}
+
+/// Expands a rewrite like `/_tenants/:tenant/:path*` for a tenant and a path
+/// without its leading slash.
+fn expand_tenant_rewrite(rewrite: &str, tenant: &str, path: &str) -> String {
+    let expanded = rewrite.replace(":tenant", tenant).replace(":path*", path);
+    match expanded.strip_suffix('/') {
+        Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+        _ => expanded,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_tenant_rewrite, tenant_from_host};
+
+    #[test]
+    fn test_tenant_from_host() {
+        let pattern = "*.example.com";
+        assert_eq!(
+            tenant_from_host("tenant1.example.com", pattern),
+            Some("tenant1")
+        );
+        assert_eq!(
+            tenant_from_host("tenant1.example.com:3000", pattern),
+            Some("tenant1")
+        );
+        assert_eq!(tenant_from_host("example.com", pattern), None);
+        assert_eq!(tenant_from_host(".example.com", pattern), None);
+        assert_eq!(tenant_from_host("a.b.example.com", pattern), None);
+        assert_eq!(tenant_from_host("tenant1.example.org", pattern), None);
+    }
+
+    #[test]
+    fn test_expand_tenant_rewrite() {
+        let rewrite = "/_tenants/:tenant/:path*";
+        assert_eq!(
+            expand_tenant_rewrite(rewrite, "tenant1", "blog/post"),
+            "/_tenants/tenant1/blog/post"
+        );
+        assert_eq!(
+            expand_tenant_rewrite(rewrite, "tenant1", ""),
+            "/_tenants/tenant1"
+        );
+        assert_eq!(expand_tenant_rewrite("/:path*", "tenant1", ""), "/");
+    }
+}
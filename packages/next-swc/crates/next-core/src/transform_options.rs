@@ -1,9 +1,11 @@
 use anyhow::Result;
+use turbo_tasks::primitives::StringVc;
 use turbopack_binding::{
-    turbo::tasks_fs::{FileJsonContentVc, FileSystemPathVc},
+    turbo::tasks_fs::{FileJsonContentVc, FileSystemEntryType, FileSystemPathVc},
     turbopack::{
         core::{
             file_source::FileSourceVc,
+            issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
             resolve::{find_context_file, node::node_cjs_resolve_options, FindContextFileResult},
             source::SourceVc,
         },
@@ -19,24 +21,52 @@ use turbopack_binding::{
     },
 };
 
-use crate::mode::NextMode;
+use crate::{mode::NextMode, next_config::NextConfigVc};
 
-async fn get_typescript_options(
+#[turbo_tasks::value(transparent)]
+pub struct OptionTsConfigPath(Option<FileSystemPathVc>);
+
+/// Finds the tsconfig of the project. A `typescript.tsconfigPath` configured
+/// in next.config.js takes precedence over the default `tsconfig.json` lookup.
+#[turbo_tasks::function]
+pub async fn get_tsconfig_path(
     project_path: FileSystemPathVc,
-) -> Option<Vec<(FileJsonContentVc, SourceVc)>> {
+    next_config: NextConfigVc,
+) -> Result<OptionTsConfigPathVc> {
+    if let Some(tsconfig_path) = &*next_config.typescript_tsconfig_path().await? {
+        let path = project_path.join(tsconfig_path);
+        if !matches!(&*path.get_type().await?, FileSystemEntryType::File) {
+            TsConfigNotFoundIssue {
+                path,
+                tsconfig_path: tsconfig_path.clone(),
+            }
+            .cell()
+            .as_issue()
+            .emit();
+            return Ok(OptionTsConfigPathVc::cell(None));
+        }
+        return Ok(OptionTsConfigPathVc::cell(Some(path)));
+    }
+
     let tsconfig = find_context_file(project_path, tsconfig());
-    match *tsconfig.await.ok()? {
-        FindContextFileResult::Found(path, _) => Some(
-            read_tsconfigs(
-                path.read(),
-                FileSourceVc::new(path).into(),
-                node_cjs_resolve_options(path.root()),
-            )
-            .await
-            .ok()?,
-        ),
+    Ok(OptionTsConfigPathVc::cell(match *tsconfig.await? {
+        FindContextFileResult::Found(path, _) => Some(path),
         FindContextFileResult::NotFound(_) => None,
-    }
+    }))
+}
+
+pub(crate) async fn get_typescript_options(
+    project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
+) -> Option<Vec<(FileJsonContentVc, SourceVc)>> {
+    let path = (*get_tsconfig_path(project_path, next_config).await.ok()?)?;
+    read_tsconfigs(
+        path.read(),
+        FileSourceVc::new(path).into(),
+        node_cjs_resolve_options(path.root()),
+    )
+    .await
+    .ok()
 }
 
 /// Build the transform options for specifically for the typescript's runtime
@@ -44,8 +74,9 @@ async fn get_typescript_options(
 #[turbo_tasks::function]
 pub async fn get_typescript_transform_options(
     project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
 ) -> Result<TypescriptTransformOptionsVc> {
-    let tsconfig = get_typescript_options(project_path).await;
+    let tsconfig = get_typescript_options(project_path, next_config).await;
 
     let use_define_for_class_fields = if let Some(tsconfig) = tsconfig {
         read_from_tsconfigs(&tsconfig, |json, _| {
@@ -69,8 +100,9 @@ pub async fn get_typescript_transform_options(
 #[turbo_tasks::function]
 pub async fn get_decorators_transform_options(
     project_path: FileSystemPathVc,
+    next_config: NextConfigVc,
 ) -> Result<DecoratorsOptionsVc> {
-    let tsconfig = get_typescript_options(project_path).await;
+    let tsconfig = get_typescript_options(project_path, next_config).await;
 
     let decorators_transform_options = if let Some(tsconfig) = tsconfig {
         read_from_tsconfigs(&tsconfig, |json, _| {
@@ -127,15 +159,17 @@ pub async fn get_jsx_transform_options(
     project_path: FileSystemPathVc,
     mode: NextMode,
     resolve_options_context: Option<ResolveOptionsContextVc>,
+    next_config: NextConfigVc,
 ) -> Result<JsxTransformOptionsVc> {
-    let tsconfig = get_typescript_options(project_path).await;
-
-    let enable_react_refresh = if let Some(resolve_options_context) = resolve_options_context {
-        assert_can_resolve_react_refresh(project_path, resolve_options_context)
-            .await?
-            .is_found()
-    } else {
-        false
+    let tsconfig = get_typescript_options(project_path, next_config).await;
+
+    let enable_react_refresh = match resolve_options_context {
+        Some(resolve_options_context) if mode.is_react_refresh_enabled() => {
+            assert_can_resolve_react_refresh(project_path, resolve_options_context)
+                .await?
+                .is_found()
+        }
+        _ => false,
     };
 
     // [NOTE]: ref: WEB-901
@@ -168,3 +202,44 @@ pub async fn get_jsx_transform_options(
 
     Ok(react_transform_options.cell())
 }
+
+#[turbo_tasks::value]
+struct TsConfigNotFoundIssue {
+    path: FileSystemPathVc,
+    tsconfig_path: String,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for TsConfigNotFoundIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("config".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(format!(
+            "The configured tsconfig \"{}\" could not be found",
+            self.tsconfig_path
+        ))
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(
+            "`typescript.tsconfigPath` in next.config.js points to a file that does not exist. \
+             Fix the path or remove the option to use the default tsconfig.json."
+                .to_string(),
+        )
+    }
+}
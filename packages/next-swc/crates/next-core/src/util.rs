@@ -1,4 +1,6 @@
 use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use swc_core::ecma::ast::Program;
 use turbo_tasks::{
@@ -7,7 +9,10 @@ use turbo_tasks::{
     TaskInput, Value, ValueToString,
 };
 use turbopack_binding::{
-    turbo::tasks_fs::{json::parse_json_rope_with_source_context, FileContent, FileSystemPathVc},
+    turbo::{
+        tasks_env::ProcessEnvVc,
+        tasks_fs::{json::parse_json_rope_with_source_context, FileContent, FileSystemPathVc},
+    },
     turbopack::{
         core::{
             asset::Asset,
@@ -22,7 +27,7 @@ use turbopack_binding::{
             },
         },
         ecmascript::{
-            analyzer::{JsValue, ObjectPart},
+            analyzer::{ConstantValue, JsValue, ObjectPart},
             parse::ParseResult,
             EcmascriptModuleAssetVc,
         },
@@ -165,6 +170,44 @@ impl Issue for NextSourceConfigParsingIssue {
     }
 }
 
+/// An issue for a page that opts into AMP, which Turbopack doesn't support.
+#[turbo_tasks::value(shared)]
+pub struct UnsupportedAmpConfigIssue {
+    ident: AssetIdentVc,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for UnsupportedAmpConfigIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell("AMP is not supported by Turbopack".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("unsupported".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.ident.path()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> StringVc {
+        StringVc::cell(
+            "This page exports `config.amp`, but AMP pages can't be rendered with Turbopack. \
+             Remove the `amp` property or run `next dev` without `--turbo`."
+                .to_string(),
+        )
+    }
+}
+
 #[turbo_tasks::function]
 pub async fn parse_config_from_source(module: ModuleVc) -> Result<NextSourceConfigVc> {
     if let Some(ecmascript_asset) = EcmascriptModuleAssetVc::resolve_from(module).await? {
@@ -259,6 +302,15 @@ fn parse_config_from_js_value(module_asset: ModuleVc, value: &JsValue) -> NextSo
                                 );
                             }
                         }
+                        if key == "amp" && !matches!(value, JsValue::Constant(ConstantValue::False))
+                        {
+                            UnsupportedAmpConfigIssue {
+                                ident: module_asset.ident(),
+                            }
+                            .cell()
+                            .as_issue()
+                            .emit();
+                        }
                         if key == "matcher" {
                             let mut matchers = vec![];
                             match value {
@@ -357,20 +409,57 @@ pub async fn load_next_json<T: DeserializeOwned>(
     Ok(result)
 }
 
+/// The size of a page's data above which a warning is logged, same as the
+/// default of `experimental.largePageDataBytes`.
+pub(crate) const DEFAULT_LARGE_PAGE_DATA_BYTES: f64 = 128.0 * 1000.0;
+
+/// The maximum length of the `Link` header of a rendered page, same as the
+/// default of `reactMaxHeadersLength`.
+const DEFAULT_REACT_MAX_HEADERS_LENGTH: f64 = 6000.0;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreviewProps {
+    preview_mode_id: String,
+    preview_mode_signing_key: String,
+    preview_mode_encryption_key: String,
+}
+
+/// The preview secrets used when none are configured. Like `next dev`, they are
+/// generated once per server, so cookies are valid across all renderers.
+static GENERATED_PREVIEW_PROPS: Lazy<PreviewProps> = Lazy::new(|| PreviewProps {
+    preview_mode_id: random_hex(16),
+    preview_mode_signing_key: random_hex(32),
+    preview_mode_encryption_key: random_hex(32),
+});
+
+fn random_hex(len: usize) -> String {
+    (0..len)
+        .map(|_| format!("{:02x}", rand::random::<u8>()))
+        .collect()
+}
+
 #[turbo_tasks::function]
 pub async fn render_data(
     next_config: NextConfigVc,
+    env: ProcessEnvVc,
     server_addr: ServerAddrVc,
 ) -> Result<JsonValueVc> {
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
-    struct Data {
+    struct Data<'a> {
         next_config_output: Option<OutputType>,
         server_info: Option<ServerInfo>,
         allowed_revalidate_header_keys: Option<Vec<String>>,
         fetch_cache_key_prefix: Option<String>,
         isr_memory_cache_size: Option<f64>,
         isr_flush_to_disk: Option<bool>,
+        preview_props: PreviewProps,
+        public_runtime_config: &'a IndexMap<String, serde_json::Value>,
+        server_runtime_config: &'a IndexMap<String, serde_json::Value>,
+        large_page_data_bytes: f64,
+        render_timeout: Option<f64>,
+        react_max_headers_length: f64,
     }
 
     let config = next_config.await?;
@@ -378,6 +467,21 @@ pub async fn render_data(
 
     let experimental = &config.experimental;
 
+    // Draft mode and preview mode cookies are signed with the configured
+    // preview secrets, or with secrets generated for this server.
+    let preview_props = match (
+        &*env.read("__NEXT_PREVIEW_MODE_ID").await?,
+        &*env.read("__NEXT_PREVIEW_MODE_SIGNING_KEY").await?,
+        &*env.read("__NEXT_PREVIEW_MODE_ENCRYPTION_KEY").await?,
+    ) {
+        (Some(id), Some(signing_key), Some(encryption_key)) => PreviewProps {
+            preview_mode_id: id.clone(),
+            preview_mode_signing_key: signing_key.clone(),
+            preview_mode_encryption_key: encryption_key.clone(),
+        },
+        _ => GENERATED_PREVIEW_PROPS.clone(),
+    };
+
     let value = serde_json::to_value(Data {
         next_config_output: config.output.clone(),
         server_info: server_info.ok(),
@@ -385,6 +489,16 @@ pub async fn render_data(
         fetch_cache_key_prefix: experimental.fetch_cache_key_prefix.clone(),
         isr_memory_cache_size: experimental.isr_memory_cache_size,
         isr_flush_to_disk: experimental.isr_flush_to_disk,
+        preview_props,
+        public_runtime_config: &config.public_runtime_config,
+        server_runtime_config: &config.server_runtime_config,
+        large_page_data_bytes: experimental
+            .large_page_data_bytes
+            .unwrap_or(DEFAULT_LARGE_PAGE_DATA_BYTES),
+        render_timeout: experimental.render_timeout,
+        react_max_headers_length: config
+            .react_max_headers_length
+            .unwrap_or(DEFAULT_REACT_MAX_HEADERS_LENGTH),
     })?;
     Ok(JsonValue(value).cell())
 }
@@ -164,6 +164,95 @@ fn test(resource: PathBuf) {
     };
 }
 
+/// Checks that `--log-requests` reports every request, including repeated
+/// identical requests, which must not reuse the log entry of a previous one.
+#[test]
+fn test_log_requests() {
+    run_async_test(async {
+        register();
+
+        let package_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let tests_dir = package_root.join("tests");
+        let project_dir = tests_dir.join("temp").join("request-log");
+        let _ = std::fs::remove_dir_all(&project_dir);
+        std::fs::create_dir_all(&project_dir).expect("failed to create temporary directory");
+        copy_recursive(&tests_dir.join("request-log"), &project_dir)
+            .expect("failed to copy test files to temporary directory");
+        let workspace_root = canonicalize(&package_root)
+            .unwrap()
+            .ancestors()
+            .nth(4)
+            .unwrap()
+            .to_path_buf();
+
+        let (issue_tx, mut issue_rx) = unbounded_channel();
+        let issue_tx = TransientInstance::new(issue_tx);
+        let requested_addr = get_free_local_addr().unwrap();
+        let server = NextDevServerBuilder::new(
+            TurboTasks::new(MemoryBackend::default()),
+            project_dir.to_string_lossy().to_string(),
+            workspace_root.to_string_lossy().to_string(),
+        )
+        .eager_compile(false)
+        .hostname(requested_addr.ip())
+        .port(requested_addr.port())
+        .log_requests(true)
+        .issue_reporter(Box::new(move || {
+            TestIssueReporterVc::new(issue_tx.clone()).into()
+        }))
+        .build()
+        .await
+        .unwrap();
+        let local_addr =
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), server.addr.port());
+
+        let requests = [
+            (reqwest::Method::GET, "api/hello"),
+            (reqwest::Method::GET, "api/hello"),
+            (reqwest::Method::POST, "api/hello"),
+            (reqwest::Method::GET, "missing"),
+        ];
+        let run = async {
+            let client = reqwest::Client::new();
+            for (method, path) in requests.iter() {
+                client
+                    .request(method.clone(), format!("http://{local_addr}/{path}"))
+                    .send()
+                    .await?;
+            }
+
+            let mut logged = vec![];
+            while logged.len() < requests.len() {
+                let (issue, _) = tokio::time::timeout(Duration::from_secs(30), issue_rx.recv())
+                    .await
+                    .context("timed out waiting for request logs")?
+                    .context("issue reporter was dropped")?;
+                if issue.category == "request" {
+                    logged.push(issue.title.clone());
+                }
+            }
+            anyhow::Ok(logged)
+        };
+        let mut logged = tokio::select! {
+            logged = run => logged.unwrap(),
+            _ = server.future => panic!("Never resolves"),
+        };
+
+        logged.sort();
+        assert_eq!(
+            logged,
+            [
+                "GET /api/hello",
+                "GET /api/hello",
+                "GET /missing",
+                "POST /api/hello"
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(&project_dir);
+    })
+}
+
 fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
     let from = canonicalize(from)?;
     let to = canonicalize(to)?;
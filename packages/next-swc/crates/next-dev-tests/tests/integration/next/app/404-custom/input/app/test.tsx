@@ -69,8 +69,17 @@ function runTests(harness: Harness, iframe: HTMLIFrameElement) {
     TIMEOUT
   )
 
-  // TODO(WEB-980) Fix this test once we no longer throw an error when rendering a 404 page.
-  it.skip(
+  it(
+    'server renders the segment 404 page',
+    async () => {
+      const res = await fetch('/segment')
+      expect(res.status).toBe(404)
+      expect(await res.text()).toContain('data-test-segmentnotfound')
+    },
+    TIMEOUT
+  )
+
+  it(
     'navigates to the segment 404 page',
     async () => {
       await harness.load(iframe, '/link-segment')
@@ -92,8 +101,7 @@ function runTests(harness: Harness, iframe: HTMLIFrameElement) {
     TIMEOUT
   )
 
-  // TODO(WEB-980) Fix this test once we no longer throw an error when rendering a 404 page.
-  it.skip(
+  it(
     'renders a segment 404 page',
     async () => {
       await harness.load(iframe, '/segment')
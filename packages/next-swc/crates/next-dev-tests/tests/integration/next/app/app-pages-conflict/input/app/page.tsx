@@ -0,0 +1,10 @@
+import Test from './test'
+
+export default function Page() {
+  return (
+    <div>
+      <div id="app">app/page.tsx</div>
+      <Test />
+    </div>
+  )
+}
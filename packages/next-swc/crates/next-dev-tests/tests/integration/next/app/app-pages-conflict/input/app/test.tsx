@@ -0,0 +1,17 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should render the app route when a page has the same path', async () => {
+    const res = await fetch('/')
+    expect(res.status).toBe(200)
+    const text = await res.text()
+    expect(text).toContain('<div id="app">app/page.tsx</div>')
+    expect(text).not.toContain('pages/index.tsx')
+  })
+}
@@ -0,0 +1,3 @@
+export default function Page() {
+  return <div id="pages">pages/index.tsx</div>
+}
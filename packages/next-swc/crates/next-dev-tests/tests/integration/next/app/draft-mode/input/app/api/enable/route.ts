@@ -0,0 +1,6 @@
+import { draftMode } from 'next/headers'
+
+export function GET() {
+  draftMode().enable()
+  return new Response('enabled')
+}
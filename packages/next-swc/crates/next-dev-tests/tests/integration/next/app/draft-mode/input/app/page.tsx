@@ -0,0 +1,14 @@
+import { draftMode } from 'next/headers'
+import Test from './test'
+
+export default function Page() {
+  const { isEnabled } = draftMode()
+
+  return (
+    <div>
+      <div id="draft">{String(isEnabled)}</div>
+      {isEnabled && <div id="rendered-at">{Date.now()}</div>}
+      <Test />
+    </div>
+  )
+}
@@ -0,0 +1,32 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(runTests)
+}
+
+function getRenderedAt(html: string) {
+  return html.match(/<div id="rendered-at">(\d+)<\/div>/)?.[1]
+}
+
+function runTests() {
+  it('should be disabled without the bypass cookie', async () => {
+    const html = await (await fetch('/')).text()
+    expect(html).toContain('<div id="draft">false</div>')
+  })
+
+  it('should render dynamically once enabled', async () => {
+    const res = await fetch('/api/enable')
+    expect(await res.text()).toBe('enabled')
+
+    const first = await (await fetch('/')).text()
+    expect(first).toContain('<div id="draft">true</div>')
+
+    await new Promise((resolve) => setTimeout(resolve, 10))
+
+    const second = await (await fetch('/')).text()
+    expect(getRenderedAt(first)).toBeDefined()
+    expect(getRenderedAt(second)).not.toBe(getRenderedAt(first))
+  })
+}
@@ -0,0 +1,19 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should link the manifest', () => {
+      const manifest = document.querySelector('link[rel="manifest"]')
+      expect(manifest?.getAttribute('href')).toBe('/manifest.webmanifest')
+    })
+
+    it('should serve the manifest returned by manifest.ts', async () => {
+      const res = await fetch('/manifest.webmanifest')
+      expect(res.status).toBe(200)
+      expect(res.headers.get('content-type')).toBe('application/manifest+json')
+      expect(await res.json()).toEqual({ name: 'App' })
+    })
+  })
+}
@@ -0,0 +1,16 @@
+export default function RootLayout({ children }: { children: any }) {
+  return (
+    <html>
+      <body>{children}</body>
+    </html>
+  )
+}
+
+export const metadata = {
+  title: {
+    absolute: 'RootLayout',
+    template: '%s - RootLayout',
+  },
+  description: 'RootLayout description',
+  applicationName: 'RootLayout app',
+}
@@ -0,0 +1,17 @@
+import Test from './test'
+
+export default function Page() {
+  return (
+    <div>
+      <Test />
+    </div>
+  )
+}
+
+export async function generateMetadata(_props: any, parent: any) {
+  const { applicationName } = await parent
+  return {
+    title: `Generated by ${applicationName}`,
+    keywords: ['generated'],
+  }
+}
@@ -0,0 +1,23 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should render the title from generateMetadata', () => {
+      expect(document.title).toBe('Generated by RootLayout app - RootLayout')
+    })
+
+    it('should merge generateMetadata with static metadata', () => {
+      expect(
+        document.querySelector('meta[name="description"]')?.getAttribute(
+          'content'
+        )
+      ).toBe('RootLayout description')
+      expect(
+        document.querySelector('meta[name="keywords"]')?.getAttribute('content')
+      ).toBe('generated')
+    })
+  })
+  return null
+}
@@ -0,0 +1,12 @@
+import { cookies, headers } from 'next/headers'
+import Test from './test'
+
+export default function Page() {
+  return (
+    <div>
+      <div id="header">{headers().get('x-test-header') ?? 'none'}</div>
+      <div id="cookie">{cookies().get('test-cookie')?.value ?? 'none'}</div>
+      <Test />
+    </div>
+  )
+}
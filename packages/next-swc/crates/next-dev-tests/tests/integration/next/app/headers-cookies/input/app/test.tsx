@@ -0,0 +1,22 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should read request headers in server components', async () => {
+    const res = await fetch('/', { headers: { 'x-test-header': 'from-test' } })
+    const html = await res.text()
+    expect(html).toContain('<div id="header">from-test</div>')
+  })
+
+  it('should read request cookies in server components', async () => {
+    document.cookie = 'test-cookie=cookie-value; path=/'
+    const res = await fetch('/')
+    const html = await res.text()
+    expect(html).toContain('<div id="cookie">cookie-value</div>')
+  })
+}
@@ -0,0 +1,4 @@
+export default async function Page() {
+  await new Promise((resolve) => setTimeout(resolve, 1000))
+  return <p>Slow page content</p>
+}
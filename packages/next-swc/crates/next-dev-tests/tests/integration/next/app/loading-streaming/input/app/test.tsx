@@ -0,0 +1,17 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should stream the loading fallback before an async page', async () => {
+      const res = await fetch('/slow')
+      expect(res.status).toBe(200)
+      const html = await res.text()
+      const loading = html.indexOf('Loading slow page')
+      const content = html.indexOf('Slow page content')
+      expect(loading).toBeGreaterThan(-1)
+      expect(content).toBeGreaterThan(loading)
+    })
+  })
+}
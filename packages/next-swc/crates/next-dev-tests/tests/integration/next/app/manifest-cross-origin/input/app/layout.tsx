@@ -0,0 +1,11 @@
+export const metadata = {
+  manifestCrossOrigin: 'use-credentials',
+}
+
+export default function RootLayout({ children }: { children: any }) {
+  return (
+    <html>
+      <body>{children}</body>
+    </html>
+  )
+}
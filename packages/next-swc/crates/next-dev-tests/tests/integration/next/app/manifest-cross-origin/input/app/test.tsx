@@ -0,0 +1,16 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should request the manifest with credentials', () => {
+      const manifest = document.querySelector('link[rel="manifest"]')
+      expect(manifest?.getAttribute('href')).toMatch(
+        /^\/_next\/static\/.+\.webmanifest$/
+      )
+      expect(manifest?.getAttribute('crossorigin')).toBe('use-credentials')
+    })
+  })
+  return <div>Test</div>
+}
@@ -0,0 +1,7 @@
+export default function MarketingLayout({ children }: { children: any }) {
+  return (
+    <html data-root="marketing">
+      <body>{children}</body>
+    </html>
+  )
+}
@@ -0,0 +1,22 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should render the root layout of its route group', () => {
+      expect(document.documentElement.getAttribute('data-root')).toBe(
+        'marketing'
+      )
+    })
+
+    it('should render the root layout of another route group', async () => {
+      const res = await fetch('/shop')
+      expect(res.status).toBe(200)
+      const html = await res.text()
+      expect(html).toContain('<html data-root="shop">')
+      expect(html).toContain('Shop')
+      expect(html).not.toContain('data-root="marketing"')
+    })
+  })
+}
@@ -0,0 +1,7 @@
+export default function ShopLayout({ children }: { children: any }) {
+  return (
+    <html data-root="shop">
+      <body>{children}</body>
+    </html>
+  )
+}
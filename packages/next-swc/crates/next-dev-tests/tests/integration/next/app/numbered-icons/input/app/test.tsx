@@ -0,0 +1,29 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should have a link tag for every icon', () => {
+      const links = Array.from(document.querySelectorAll('link[rel=icon]'))
+      expect(
+        links.map((l) => ({
+          href: l.getAttribute('href'),
+          sizes: l.getAttribute('sizes'),
+          type: l.getAttribute('type'),
+        }))
+      ).toEqual([
+        {
+          href: expect.stringMatching(/^\/_next\/static\/.+\.png$/),
+          sizes: '32x32',
+          type: 'image/png',
+        },
+        {
+          href: expect.stringMatching(/^\/_next\/static\/.+\.png$/),
+          sizes: '64x64',
+          type: 'image/png',
+        },
+      ])
+    })
+  })
+}
@@ -0,0 +1,3 @@
+export default function LoginPage() {
+  return <div data-test-login>Login</div>
+}
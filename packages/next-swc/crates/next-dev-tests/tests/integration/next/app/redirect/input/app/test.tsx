@@ -0,0 +1,18 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('redirects when a page calls redirect()', async () => {
+    // The browser follows the 307 response, so the redirect is observed
+    // through the final URL rather than the status code.
+    const res = await fetch('/protected')
+    expect(res.redirected).toBe(true)
+    expect(new URL(res.url).pathname).toBe('/login')
+    expect(await res.text()).toContain('data-test-login')
+  }, 20000)
+}
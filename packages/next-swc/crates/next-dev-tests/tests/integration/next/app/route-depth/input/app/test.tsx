@@ -0,0 +1,47 @@
+'use client'
+
+import { useRef } from 'react'
+import { Harness, useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  const iframeRef = useRef<HTMLIFrameElement | null>(null)
+
+  useTestHarness((harness) => runTests(harness, iframeRef.current!))
+
+  return (
+    <iframe
+      style={{ width: 800, height: 600 }}
+      src="/a/b/c/d"
+      ref={iframeRef}
+    />
+  )
+}
+
+function runTests(harness: Harness, iframe: HTMLIFrameElement) {
+  const TIMEOUT = 40000
+
+  it(
+    'should render the route without the segments below the maximum depth',
+    async () => {
+      const res = await fetch('/a/b/c/d')
+      expect(res.status).toBe(200)
+      expect(await res.text()).not.toContain('Too deep')
+    },
+    TIMEOUT
+  )
+
+  it(
+    'should report the route that is nested too deeply',
+    async () => {
+      await harness.waitForLoaded(iframe)
+      const errorOverlay = await harness.waitForErrorOverlay(iframe)
+      const issues = await harness.waitForSelector(
+        errorOverlay,
+        '#turbopack-issues'
+      )
+      expect(issues.innerHTML).toContain('Route is nested too deeply')
+      expect(issues.innerHTML).toContain('deeper than 3 levels at /a/b/c')
+    },
+    TIMEOUT
+  )
+}
@@ -0,0 +1,5 @@
+module.exports = {
+  experimental: {
+    maxRouteDepth: 3,
+  },
+}
@@ -0,0 +1,3 @@
+export default function AboutPage() {
+  return <div data-test-about>About</div>
+}
@@ -0,0 +1,26 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('responds with a flight payload to requests with the RSC header', async () => {
+    const res = await fetch('/about', { headers: { RSC: '1' } })
+    expect(res.status).toBe(200)
+    expect(res.headers.get('content-type')).toBe('text/x-component')
+    expect(res.headers.get('vary')).toContain('Next-Router-State-Tree')
+
+    const body = await res.text()
+    expect(body).not.toContain('<html')
+    expect(body).toContain('data-test-about')
+  })
+
+  it('responds with HTML to requests without the RSC header', async () => {
+    const res = await fetch('/about')
+    expect(res.headers.get('content-type')).toBe('text/html; charset=utf-8')
+    expect(await res.text()).toContain('<html')
+  })
+}
@@ -0,0 +1,3 @@
+export default function Error() {
+  return <p>Something went wrong</p>
+}
@@ -0,0 +1,38 @@
+'use client'
+
+import { useRef } from 'react'
+import { Harness, useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  const iframeRef = useRef<HTMLIFrameElement | null>(null)
+
+  useTestHarness((harness) => runTests(harness, iframeRef.current!))
+
+  return (
+    <iframe
+      style={{ width: 800, height: 600 }}
+      src="/boundary"
+      ref={iframeRef}
+    />
+  )
+}
+
+function runTests(harness: Harness, iframe: HTMLIFrameElement) {
+  const TIMEOUT = 40000
+
+  it(
+    'should report an error boundary without "use client"',
+    async () => {
+      await harness.waitForLoaded(iframe)
+      const errorOverlay = await harness.waitForErrorOverlay(iframe)
+      const issues = await harness.waitForSelector(
+        errorOverlay,
+        '#turbopack-issues'
+      )
+      expect(issues.innerHTML).toContain(
+        'Error boundaries must be Client Components'
+      )
+    },
+    TIMEOUT
+  )
+}
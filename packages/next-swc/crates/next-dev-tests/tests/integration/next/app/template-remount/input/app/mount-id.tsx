@@ -0,0 +1,18 @@
+'use client'
+
+import { useState } from 'react'
+
+export default function MountId({
+  name,
+  children,
+}: {
+  name: string
+  children: any
+}) {
+  const [id] = useState(() => Math.random().toString(36).slice(2))
+  return (
+    <div data-mount={name} data-id={id}>
+      {children}
+    </div>
+  )
+}
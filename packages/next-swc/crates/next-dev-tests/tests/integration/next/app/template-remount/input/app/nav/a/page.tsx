@@ -0,0 +1,14 @@
+'use client'
+
+import Link from 'next/link'
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness((mod) => mod.markAsHydrated())
+
+  return (
+    <Link href="/nav/b" data-test-link>
+      -&gt; B
+    </Link>
+  )
+}
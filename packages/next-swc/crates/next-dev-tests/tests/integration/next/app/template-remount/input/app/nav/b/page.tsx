@@ -0,0 +1,3 @@
+export default function Page() {
+  return <p data-test-b>B</p>
+}
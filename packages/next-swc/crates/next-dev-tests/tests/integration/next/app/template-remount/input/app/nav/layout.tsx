@@ -0,0 +1,5 @@
+import MountId from '../mount-id'
+
+export default function Layout({ children }: { children: any }) {
+  return <MountId name="layout">{children}</MountId>
+}
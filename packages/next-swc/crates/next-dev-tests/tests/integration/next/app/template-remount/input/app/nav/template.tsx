@@ -0,0 +1,5 @@
+import MountId from '../mount-id'
+
+export default function Template({ children }: { children: any }) {
+  return <MountId name="template">{children}</MountId>
+}
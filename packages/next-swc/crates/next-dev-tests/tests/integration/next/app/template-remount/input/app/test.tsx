@@ -0,0 +1,45 @@
+'use client'
+
+import { useRef } from 'react'
+import { useTestHarness, Harness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  const iframeRef = useRef<HTMLIFrameElement | null>(null)
+
+  useTestHarness((harness) => runTests(harness, iframeRef.current!))
+
+  return <iframe style={{ width: 800, height: 600 }} ref={iframeRef} />
+}
+
+function runTests(harness: Harness, iframe: HTMLIFrameElement) {
+  const TIMEOUT = 20000
+
+  const mountId = (name: string) =>
+    iframe
+      .contentDocument!.querySelector(`[data-mount="${name}"]`)
+      ?.getAttribute('data-id')
+
+  it(
+    'remounts the template but not the layout on navigation',
+    async () => {
+      await harness.load(iframe, '/nav/a')
+      await harness.waitForHydration(iframe, '/nav/a')
+
+      const layoutId = mountId('layout')
+      const templateId = mountId('template')
+      expect(layoutId).toBeTruthy()
+      expect(templateId).toBeTruthy()
+
+      const link = iframe.contentDocument!.querySelector('a[data-test-link]')
+      expect(link).not.toBeNull()
+      ;(link as HTMLAnchorElement).click()
+
+      await harness.waitForSelector(iframe, '[data-test-b]')
+
+      expect(mountId('layout')).toBe(layoutId)
+      expect(mountId('template')).toBeTruthy()
+      expect(mountId('template')).not.toBe(templateId)
+    },
+    TIMEOUT
+  )
+}
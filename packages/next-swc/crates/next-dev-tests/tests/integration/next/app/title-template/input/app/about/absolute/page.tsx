@@ -0,0 +1,9 @@
+export default function Page() {
+  return <div>Absolute</div>
+}
+
+export const metadata = {
+  title: {
+    absolute: 'Absolute',
+  },
+}
@@ -0,0 +1,13 @@
+import Test from '../test'
+
+export default function Page() {
+  return (
+    <div>
+      <Test />
+    </div>
+  )
+}
+
+export const metadata = {
+  title: 'About',
+}
@@ -0,0 +1,14 @@
+export default function RootLayout({ children }: { children: any }) {
+  return (
+    <html>
+      <body>{children}</body>
+    </html>
+  )
+}
+
+export const metadata = {
+  title: {
+    template: '%s | Site',
+    default: 'Site',
+  },
+}
@@ -0,0 +1,18 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should apply the parent title template', () => {
+      expect(document.title).toBe('About | Site')
+    })
+
+    it('should not apply the template to an absolute title', async () => {
+      const res = await fetch('/about/absolute')
+      const html = await res.text()
+      expect(html).toContain('<title>Absolute</title>')
+    })
+  })
+  return null
+}
@@ -0,0 +1,15 @@
+import Test from './test'
+
+export default function Page() {
+  return (
+    <div>
+      <Test />
+    </div>
+  )
+}
+
+export const viewport = {
+  width: 'device-width',
+  initialScale: 2,
+  themeColor: '#000',
+}
@@ -0,0 +1,23 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should render the theme color from the viewport export', () => {
+      const themeColors = Array.from(
+        document.querySelectorAll('meta[name="theme-color"]')
+      ).map((meta) => meta.getAttribute('content'))
+      expect(themeColors).toEqual(['#000'])
+    })
+
+    it('should render the viewport meta tag from the viewport export', () => {
+      expect(
+        document
+          .querySelector('meta[name="viewport"]')
+          ?.getAttribute('content')
+      ).toBe('width=device-width, initial-scale=2')
+    })
+  })
+  return <div>Test</div>
+}
@@ -0,0 +1,43 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return (
+    <>
+      <div className="background" id="background" />
+      <div className="base" id="base" />
+      <div className="deep" id="deep" />
+    </>
+  )
+}
+
+function getBackgroundUrl(id) {
+  const { backgroundImage } = getComputedStyle(document.getElementById(id))
+  return backgroundImage.match(/^url\("?(.*?)"?\)$/)?.[1]
+}
+
+function runTests() {
+  it('should resolve nested @import chains', () => {
+    expect(getComputedStyle(document.getElementById('base')).color).toBe(
+      'rgb(255, 0, 0)'
+    )
+    expect(getComputedStyle(document.getElementById('deep')).color).toBe(
+      'rgb(0, 0, 255)'
+    )
+  })
+
+  it('should rewrite url() to the emitted asset', async () => {
+    const url = getBackgroundUrl('background')
+    expect(url).toMatch(/\/_next\/.*\.png$/)
+    expect(url).not.toContain('./bg.png')
+
+    const res = await fetch(url)
+    expect(res.status).toBe(200)
+    expect(res.headers.get('content-type')).toBe('image/png')
+  })
+
+  it('should resolve url() relative to the imported stylesheet', () => {
+    expect(getBackgroundUrl('deep')).toBe(getBackgroundUrl('background'))
+  })
+}
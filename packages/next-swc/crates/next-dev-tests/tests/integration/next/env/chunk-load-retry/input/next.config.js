@@ -0,0 +1,8 @@
+module.exports = {
+  experimental: {
+    chunkLoadRetry: {
+      retries: 3,
+      timeout: 10000,
+    },
+  },
+}
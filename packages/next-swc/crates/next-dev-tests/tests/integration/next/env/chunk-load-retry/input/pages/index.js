@@ -0,0 +1,14 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should inject the chunk load retry config into the client runtime', () => {
+    expect(Number(process.env.__NEXT_CHUNK_LOAD_RETRIES)).toBe(3)
+    expect(Number(process.env.__NEXT_CHUNK_LOAD_TIMEOUT)).toBe(10000)
+  })
+}
@@ -0,0 +1,5 @@
+module.exports = {
+  env: {
+    STRING_ENV_VAR_FROM_CONFIG: 'Hello World',
+  },
+}
@@ -0,0 +1,18 @@
+export const config = {
+  runtime: 'edge',
+}
+
+export default function handler() {
+  return new Response(
+    JSON.stringify({
+      fromConfig: process.env.STRING_ENV_VAR_FROM_CONFIG,
+      fromDotEnv: process.env.NEXT_PUBLIC_STRING_ENV_VAR_FROM_DOT_ENV,
+    }),
+    {
+      status: 200,
+      headers: {
+        'content-type': 'application/json',
+      },
+    }
+  )
+}
@@ -0,0 +1,17 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+}
+
+function runTests() {
+  it('should support env vars in edge api routes', async () => {
+    const res = await fetch('/api/edge')
+    const json = await res.json()
+
+    expect(json).toEqual({
+      fromConfig: 'Hello World',
+      fromDotEnv: 'Hello World',
+    })
+  })
+}
@@ -0,0 +1,17 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should inject the scroll restoration flag into the client runtime', () => {
+    expect(String(process.env.__NEXT_SCROLL_RESTORATION)).toBe('true')
+  })
+
+  it('should let the router take over scroll restoration', () => {
+    expect(window.history.scrollRestoration).toBe('manual')
+  })
+}
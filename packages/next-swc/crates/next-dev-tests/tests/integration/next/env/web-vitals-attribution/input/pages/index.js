@@ -0,0 +1,14 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should inject the web vitals attribution into the client runtime', () => {
+    expect(String(process.env.__NEXT_HAS_WEB_VITALS_ATTRIBUTION)).toBe('true')
+    expect(process.env.__NEXT_WEB_VITALS_ATTRIBUTION).toEqual(['CLS', 'LCP'])
+  })
+}
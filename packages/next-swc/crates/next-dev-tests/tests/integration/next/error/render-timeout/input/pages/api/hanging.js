@@ -0,0 +1,4 @@
+export default function handler() {
+  // Never sends a response.
+  return new Promise(() => {})
+}
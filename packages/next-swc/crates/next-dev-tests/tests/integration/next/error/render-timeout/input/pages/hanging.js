@@ -0,0 +1,8 @@
+export default function Hanging() {
+  return <div>never rendered</div>
+}
+
+export function getServerSideProps() {
+  // Never resolves, like a fetch to a server that doesn't respond.
+  return new Promise(() => {})
+}
@@ -0,0 +1,39 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return <div>Test</div>
+}
+
+function runTests() {
+  const TIMEOUT = 20000
+
+  it(
+    'returns a 500 status code for a render that times out',
+    async () => {
+      const res = await fetch('/hanging')
+      expect(res.status).toBe(500)
+      expect(await res.text()).toContain('timed out after 1000ms')
+    },
+    TIMEOUT
+  )
+
+  it(
+    'returns a 500 status code for an API route that times out',
+    async () => {
+      const res = await fetch('/api/hanging')
+      expect(res.status).toBe(500)
+    },
+    TIMEOUT
+  )
+
+  it(
+    'still renders other pages after a timeout',
+    async () => {
+      const res = await fetch('/')
+      expect(res.status).toBe(200)
+    },
+    TIMEOUT
+  )
+}
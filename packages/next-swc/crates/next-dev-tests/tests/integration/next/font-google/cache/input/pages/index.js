@@ -0,0 +1,38 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { Inter } from 'next/font/google'
+
+const inter = Inter()
+
+export default function Home() {
+  useTestHarness(runTests)
+
+  return <div className={inter.className}>Test</div>
+}
+
+function runTests() {
+  it('uses the stylesheet cached in the dist directory', async () => {
+    const fontFamily = inter.style.fontFamily.split(',')[0].replace(/'/g, '')
+    const fontFaces = []
+    for (const stylesheet of document.querySelectorAll(
+      'link[rel=stylesheet]'
+    )) {
+      if (stylesheet.sheet == null) {
+        await new Promise((resolve) => {
+          stylesheet.addEventListener('load', resolve)
+        })
+      }
+      for (const rule of stylesheet.sheet.cssRules) {
+        if (
+          rule instanceof CSSFontFaceRule &&
+          rule.style.fontFamily.replace(/"/g, '') === fontFamily
+        ) {
+          fontFaces.push(rule)
+        }
+      }
+    }
+
+    // Google Fonts responds with a font face for every subset, the cached
+    // stylesheet only has the latin one.
+    expect(fontFaces).toHaveLength(1)
+  })
+}
@@ -0,0 +1,21 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should serve SVGs as they are when allowed', async () => {
+    const res = await fetch('/_next/image?url=%2Flogo.svg&w=64&q=75')
+    expect(res.status).toBe(200)
+    expect(res.headers.get('content-disposition')).toBe(
+      'attachment; filename="logo.svg"'
+    )
+    expect(res.headers.get('content-security-policy')).toBe(
+      "default-src 'self'; script-src 'none'; sandbox;"
+    )
+    expect(await res.text()).toContain('<svg')
+  })
+}
@@ -0,0 +1,15 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should not serve SVGs by default', async () => {
+    const res = await fetch('/_next/image?url=%2Flogo.svg&w=64&q=75')
+    expect(res.status).toBe(400)
+    expect(await res.text()).toContain('images.dangerouslyAllowSVG')
+  })
+}
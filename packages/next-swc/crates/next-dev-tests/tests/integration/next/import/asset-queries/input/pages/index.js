@@ -0,0 +1,24 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import url from '../pixel.png?url'
+import inline from '../pixel.png?inline'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return <img src={url} />
+}
+
+function runTests() {
+  it('should import the URL of the emitted file with ?url', async () => {
+    expect(url).toContain('/_next/static/media/')
+
+    const res = await fetch(url)
+    expect(res.status).toBe(200)
+  })
+
+  it('should import a data URL with ?inline', () => {
+    expect(inline).toBe(
+      'data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAAC0lEQVR4nGNgAAIAAAUAAXpeqz8AAAAASUVORK5CYII='
+    )
+  })
+}
@@ -0,0 +1,2 @@
+exports.impl = require('./impl.js')
+exports.stubbed = require('./stubbed.js')
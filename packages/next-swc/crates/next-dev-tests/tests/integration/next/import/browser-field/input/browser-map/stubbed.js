@@ -0,0 +1 @@
+module.exports = 'not stubbed'
@@ -0,0 +1,43 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import browserMap from 'browser-map'
+import browserString from 'browser-string'
+
+export default function Page({ server }) {
+  useTestHarness(() => runTests(server))
+
+  return 'index'
+}
+
+export function getServerSideProps() {
+  return {
+    props: {
+      server: {
+        impl: browserMap.impl,
+        stubbed: browserMap.stubbed,
+        string: browserString,
+      },
+    },
+  }
+}
+
+function runTests(server) {
+  it('should apply the string form of the browser field on the client', () => {
+    expect(browserString).toBe('browser')
+  })
+
+  it('should apply browser field remappings on the client', () => {
+    expect(browserMap.impl).toBe('browser')
+  })
+
+  it('should replace modules mapped to false with an empty module on the client', () => {
+    expect(browserMap.stubbed).toEqual({})
+  })
+
+  it('should not apply the browser field on the server', () => {
+    expect(server).toEqual({
+      impl: 'node',
+      stubbed: 'not stubbed',
+      string: 'node',
+    })
+  })
+}
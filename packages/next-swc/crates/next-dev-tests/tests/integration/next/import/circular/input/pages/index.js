@@ -0,0 +1,21 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { getB } from '../a'
+import { getA } from '../b'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return (
+    <div>
+      {getA()}
+      {getB()}
+    </div>
+  )
+}
+
+function runTests() {
+  it('should still render a page with a circular import', () => {
+    expect(getA()).toBe('a')
+    expect(getB()).toBe('b')
+  })
+}
@@ -1,6 +1,9 @@
 'use client'
 
 import { useTestHarness } from '@turbo/pack-test-harness'
+import imported from 'import-require'
+
+const required = require('import-require')
 
 export default function Test() {
   useTestHarness(runTests)
@@ -89,6 +92,20 @@ function runTests() {
     })
   })
 
+  it('client should use the browser import condition for imports', () => {
+    expect(imported).toBe('browser-import')
+  })
+
+  it('client should use the browser require condition for requires', () => {
+    expect(required).toBe('browser-require')
+  })
+
+  it('server should not use browser conditions', async () => {
+    const json = await getJson('/import-require')
+    expect(json.imported).not.toMatch(/^browser-/)
+    expect(json.required).toBe('require')
+  })
+
   it('middleware should import edge conditions', async () => {
     const res = await fetch('/middleware')
     const json = await res.json()
@@ -0,0 +1,10 @@
+import imported from 'import-require'
+
+const required = require('import-require')
+
+export default function ImportRequire() {
+  return JSON.stringify({
+    imported,
+    required,
+  })
+}
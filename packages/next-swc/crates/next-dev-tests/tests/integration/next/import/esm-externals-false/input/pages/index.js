@@ -0,0 +1,26 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { format } from 'dual-package'
+
+export default function Page({ server }) {
+  useTestHarness(() => runTests(server))
+
+  return <div>{format}</div>
+}
+
+export function getServerSideProps() {
+  return {
+    props: {
+      server: { format },
+    },
+  }
+}
+
+function runTests(server) {
+  it('should require external packages as CommonJS on the server', () => {
+    expect(server.format).toBe('cjs')
+  })
+
+  it('should still bundle the ESM entry for the client', () => {
+    expect(format).toBe('esm')
+  })
+}
@@ -0,0 +1,6 @@
+// ESM is always strict, so `this` is undefined in a plain function call.
+export const isStrict = (function () {
+  return this === undefined
+})()
+
+export const format = 'esm'
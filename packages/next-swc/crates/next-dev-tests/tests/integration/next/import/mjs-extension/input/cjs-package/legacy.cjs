@@ -0,0 +1,6 @@
+module.exports = {
+  isStrict: (function () {
+    return this === undefined
+  })(),
+  format: 'cjs',
+}
@@ -0,0 +1,34 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { format, isStrict } from 'cjs-package/esm'
+import legacy from 'cjs-package/legacy.cjs'
+
+export default function Page({ server }) {
+  useTestHarness(() => runTests(server))
+
+  return <div>{format}</div>
+}
+
+export function getServerSideProps() {
+  return {
+    props: {
+      server: { format, isStrict, legacy },
+    },
+  }
+}
+
+function runTests(server) {
+  it('should resolve .mjs files without their extension', () => {
+    expect(format).toBe('esm')
+    expect(server.format).toBe('esm')
+  })
+
+  it('should treat .mjs files as ESM in a CommonJS package', () => {
+    expect(isStrict).toBe(true)
+    expect(server.isStrict).toBe(true)
+  })
+
+  it('should treat .cjs files as CommonJS', () => {
+    expect(legacy).toEqual({ isStrict: false, format: 'cjs' })
+    expect(server.legacy).toEqual({ isStrict: false, format: 'cjs' })
+  })
+}
@@ -0,0 +1,10 @@
+export default function handler(req, res) {
+  try {
+    // The placeholder isn't a valid addon, so loading it from disk fails in
+    // Node.js. Bundling it would fail in Turbopack instead.
+    require('../../native/addon.node')
+    res.status(200).json({ code: null })
+  } catch (err) {
+    res.status(200).json({ code: err.code })
+  }
+}
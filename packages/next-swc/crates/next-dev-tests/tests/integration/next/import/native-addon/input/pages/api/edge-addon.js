@@ -0,0 +1,11 @@
+export const config = {
+  runtime: 'edge',
+}
+
+export default function handler() {
+  try {
+    // Reported as an issue, as the Edge Runtime can't load native addons.
+    require('../../native/addon.node')
+  } catch {}
+  return new Response('edge')
+}
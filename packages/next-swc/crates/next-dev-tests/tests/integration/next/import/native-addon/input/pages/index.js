@@ -0,0 +1,21 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should load .node files with Node.js on the server', async () => {
+    const res = await fetch('/api/addon')
+    expect(res.status).toBe(200)
+    expect(await res.json()).toEqual({ code: 'ERR_DLOPEN_FAILED' })
+  })
+
+  it('should still serve an edge route that imports a .node file', async () => {
+    const res = await fetch('/api/edge-addon')
+    expect(res.status).toBe(200)
+    expect(await res.text()).toBe('edge')
+  })
+}
@@ -0,0 +1,23 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { Buffer } from 'buffer'
+import { Buffer as PrefixedBuffer } from 'node:buffer'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should resolve buffer to a polyfill on the client', () => {
+    expect(Buffer.from('hello').toString('base64')).toBe('aGVsbG8=')
+  })
+
+  it('should resolve node:buffer to the same polyfill on the client', () => {
+    expect(PrefixedBuffer).toBe(Buffer)
+  })
+
+  it('should not provide fs on the client', async () => {
+    await expect(import('fs')).rejects.toThrow()
+  })
+}
@@ -0,0 +1,14 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import text from '../a.txt?raw'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return <pre>{text}</pre>
+}
+
+function runTests() {
+  it('should import the contents of a file with ?raw', () => {
+    expect(text).toBe('Hello "world"\nfrom a text file\n')
+  })
+}
@@ -0,0 +1,30 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { greet } from '#utils'
+import env from '#env'
+
+export default function Page({ serverGreeting, serverEnv }) {
+  useTestHarness(() => runTests(serverGreeting, serverEnv))
+
+  return <div>{greet('client')}</div>
+}
+
+export function getServerSideProps() {
+  return {
+    props: {
+      serverGreeting: greet('server'),
+      serverEnv: env,
+    },
+  }
+}
+
+function runTests(serverGreeting, serverEnv) {
+  it('should resolve subpath imports on the server', () => {
+    expect(serverGreeting).toBe('Hello server')
+    expect(serverEnv).toBe('server')
+  })
+
+  it('should resolve subpath imports on the client', () => {
+    expect(greet('client')).toBe('Hello client')
+    expect(env).toBe('browser')
+  })
+}
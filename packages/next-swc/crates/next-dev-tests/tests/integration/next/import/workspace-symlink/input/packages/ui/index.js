@@ -0,0 +1 @@
+export const registry = new Map()
@@ -0,0 +1,16 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { registry } from 'ui'
+import { registry as directRegistry } from '../packages/ui/index.js'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should resolve a symlinked workspace package to its real location', () => {
+    expect(registry).toBeInstanceOf(Map)
+    expect(registry).toBe(directRegistry)
+  })
+}
@@ -0,0 +1,5 @@
+export const config = { amp: true }
+
+export default function Amp() {
+  return <div>amp page</div>
+}
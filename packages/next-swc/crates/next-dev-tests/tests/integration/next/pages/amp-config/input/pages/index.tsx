@@ -0,0 +1,16 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should still render a page that exports config.amp', async () => {
+    const res = await fetch('/amp')
+    const html = await res.text()
+
+    expect(html).toContain('amp page')
+  })
+}
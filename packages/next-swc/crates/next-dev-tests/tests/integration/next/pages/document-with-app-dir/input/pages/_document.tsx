@@ -0,0 +1,13 @@
+import { Html, Head, Main, NextScript } from 'next/document'
+
+export default function Document() {
+  return (
+    <Html lang="fr">
+      <Head />
+      <body data-document="custom">
+        <Main />
+        <NextScript />
+      </body>
+    </Html>
+  )
+}
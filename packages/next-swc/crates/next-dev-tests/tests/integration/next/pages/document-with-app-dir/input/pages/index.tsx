@@ -0,0 +1,30 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return <div>pages page</div>
+}
+
+function runTests() {
+  it('should wrap pages with the custom _document', () => {
+    expect(document.documentElement.lang).toBe('fr')
+    expect(document.body.dataset.document).toBe('custom')
+  })
+
+  it('should include the custom _document in the SSR output', async () => {
+    const res = await fetch('/')
+    const html = await res.text()
+
+    expect(html).toMatch(/<html[^>]* lang="fr"/)
+    expect(html).toContain('data-document="custom"')
+  })
+
+  it('should not use the pages _document for app pages', async () => {
+    const res = await fetch('/app-page')
+    const html = await res.text()
+
+    expect(html).toContain('app page')
+    expect(html).not.toContain('data-document="custom"')
+  })
+}
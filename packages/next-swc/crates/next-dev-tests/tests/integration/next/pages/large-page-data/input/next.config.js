@@ -0,0 +1,5 @@
+module.exports = {
+  experimental: {
+    largePageDataBytes: 1000,
+  },
+}
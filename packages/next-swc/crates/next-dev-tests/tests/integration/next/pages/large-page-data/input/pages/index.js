@@ -0,0 +1,25 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page({ data }) {
+  useTestHarness(runTests)
+
+  return <div>{data.length}</div>
+}
+
+export function getServerSideProps() {
+  return {
+    props: {
+      data: 'a'.repeat(2000),
+    },
+  }
+}
+
+function runTests() {
+  it('should still serve page data above largePageDataBytes', async () => {
+    const res = await fetch('/_next/data/development/index.json')
+    expect(res.status).toBe(200)
+
+    const json = await res.json()
+    expect(json.pageProps.data).toHaveLength(2000)
+  })
+}
@@ -0,0 +1,3 @@
+module.exports = {
+  reactMaxHeadersLength: 100,
+}
@@ -0,0 +1,17 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return <div>Test</div>
+}
+
+function runTests() {
+  it('should drop the last links above reactMaxHeadersLength', async () => {
+    const res = await fetch('/preloads')
+    expect(res.status).toBe(200)
+    expect(res.headers.get('link')).toBe(
+      '</fonts/a.woff2>; rel=preload; as=font; crossorigin, </styles/b.css>; rel=preload; as=style'
+    )
+  })
+}
@@ -0,0 +1,12 @@
+export default function Page() {
+  return <div>Preloads</div>
+}
+
+export function getServerSideProps({ res }) {
+  res.setHeader('Link', [
+    '</fonts/a.woff2>; rel=preload; as=font; crossorigin',
+    '</styles/b.css>; rel=preload; as=style',
+    '</scripts/c.js>; rel=preload; as=script',
+  ])
+  return { props: {} }
+}
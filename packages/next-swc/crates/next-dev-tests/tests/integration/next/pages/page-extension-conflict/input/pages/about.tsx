@@ -0,0 +1,3 @@
+export default function About() {
+  return <div id="about">about.tsx</div>
+}
@@ -0,0 +1,15 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should prefer the page whose extension comes first', async () => {
+    const res = await fetch('/about')
+    expect(res.status).toBe(200)
+    expect(await res.text()).toContain('<div id="about">about.tsx</div>')
+  })
+}
@@ -0,0 +1,29 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page({ preview }) {
+  useTestHarness(runTests)
+
+  return <div id="preview">{String(preview)}</div>
+}
+
+export function getStaticProps(context) {
+  return {
+    props: {
+      preview: !!context.preview,
+    },
+  }
+}
+
+function runTests() {
+  it('should not be in preview mode without the preview cookies', () => {
+    expect(document.getElementById('preview').textContent).toBe('false')
+  })
+
+  it('should be in preview mode with generated preview secrets', async () => {
+    const res = await fetch('/api/preview')
+    expect(await res.text()).toBe('enabled')
+
+    const html = await (await fetch('/')).text()
+    expect(html).toContain('<div id="preview">true</div>')
+  })
+}
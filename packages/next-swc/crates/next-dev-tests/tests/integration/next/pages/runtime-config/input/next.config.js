@@ -0,0 +1,9 @@
+/** @type {import('next').NextConfig} */
+module.exports = {
+  publicRuntimeConfig: {
+    publicValue: 'public',
+  },
+  serverRuntimeConfig: {
+    serverValue: 'server',
+  },
+}
@@ -0,0 +1,44 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import getConfig from 'next/config'
+
+export default function Page({ server }) {
+  useTestHarness(() => runTests(server))
+
+  return 'index'
+}
+
+export function getServerSideProps() {
+  const { publicRuntimeConfig, serverRuntimeConfig } = getConfig()
+  return {
+    props: {
+      server: {
+        publicRuntimeConfig,
+        // Only expose whether the server config was set, so that its values don't
+        // end up in the page.
+        hasServerRuntimeConfig: serverRuntimeConfig.serverValue === 'server',
+      },
+    },
+  }
+}
+
+function runTests(server) {
+  it('should provide both runtime configs on the server', () => {
+    expect(server).toEqual({
+      publicRuntimeConfig: { publicValue: 'public' },
+      hasServerRuntimeConfig: true,
+    })
+  })
+
+  it('should provide only the public runtime config on the client', () => {
+    const { publicRuntimeConfig, serverRuntimeConfig } = getConfig()
+    expect(publicRuntimeConfig).toEqual({ publicValue: 'public' })
+    expect(serverRuntimeConfig).toEqual({})
+  })
+
+  it('should not leak the server runtime config into the page', () => {
+    expect(JSON.stringify(window.__NEXT_DATA__.runtimeConfig)).toBe(
+      JSON.stringify({ publicValue: 'public' })
+    )
+    expect(document.documentElement.innerHTML).not.toContain('serverValue')
+  })
+}
@@ -0,0 +1,5 @@
+import button from '@/components/button'
+
+it('resolves @/ to the src directory without a tsconfig', () => {
+  expect(button).toBe('button')
+})
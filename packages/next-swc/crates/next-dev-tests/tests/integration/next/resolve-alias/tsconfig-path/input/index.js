@@ -0,0 +1,5 @@
+import value from '@custom/value'
+
+it('resolves paths from the tsconfig configured with typescript.tsconfigPath', () => {
+  expect(value).toBe(42)
+})
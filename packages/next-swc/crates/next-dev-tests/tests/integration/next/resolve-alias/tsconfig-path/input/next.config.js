@@ -0,0 +1,5 @@
+module.exports = {
+  typescript: {
+    tsconfigPath: 'tsconfig.build.json',
+  },
+}
@@ -0,0 +1,10 @@
+/** @type {import('next').NextConfig} */
+module.exports = {
+  experimental: {
+    turbo: {
+      cacheQueryParams: {
+        exclude: ['utm_*'],
+      },
+    },
+  },
+}
@@ -0,0 +1,10 @@
+let renderCount = 0
+
+export default function Count({ renders, query }) {
+  return <pre id="data">{JSON.stringify({ renders, query })}</pre>
+}
+
+export function getServerSideProps({ query }) {
+  renderCount += 1
+  return { props: { renders: renderCount, query } }
+}
@@ -0,0 +1,32 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+async function fetchCount(query) {
+  const res = await fetch(`/count?${query}`)
+  const html = await res.text()
+  const match = html.match(/<script id="__NEXT_DATA__"[^>]*>(.*?)<\/script>/)
+  return JSON.parse(match[1]).props.pageProps
+}
+
+function runTests() {
+  it('should reuse the render when only an excluded param differs', async () => {
+    const first = await fetchCount('page=1&utm_source=a')
+    const second = await fetchCount('page=1&utm_source=b')
+
+    expect(first.query).toEqual({ page: '1' })
+    expect(second.renders).toBe(first.renders)
+  })
+
+  it('should render again when an included param differs', async () => {
+    const first = await fetchCount('page=2')
+    const second = await fetchCount('page=3')
+
+    expect(second.query).toEqual({ page: '3' })
+    expect(second.renders).toBeGreaterThan(first.renders)
+  })
+}
@@ -0,0 +1,53 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+async function expectNotModified(url) {
+  const res = await fetch(url, { cache: 'no-store' })
+  expect(res.status).toBe(200)
+  const etag = res.headers.get('etag')
+  expect(etag).toMatch(/^"[0-9a-f]+"$/)
+
+  const conditional = await fetch(url, {
+    cache: 'no-store',
+    headers: { 'If-None-Match': etag },
+  })
+  expect(conditional.status).toBe(304)
+  expect(conditional.headers.get('etag')).toBe(etag)
+}
+
+function runTests() {
+  it('should answer a conditional request for a page with 304', async () => {
+    await expectNotModified('/about')
+  })
+
+  it('should answer a conditional request for a public file with 304', async () => {
+    await expectNotModified('/data.txt')
+  })
+
+  it('should answer If-Modified-Since for a public file with 304', async () => {
+    const res = await fetch('/data.txt', { cache: 'no-store' })
+    const lastModified = res.headers.get('last-modified')
+    expect(lastModified).toMatch(/ GMT$/)
+
+    const conditional = await fetch('/data.txt', {
+      cache: 'no-store',
+      headers: { 'If-Modified-Since': lastModified },
+    })
+    expect(conditional.status).toBe(304)
+    expect(conditional.headers.get('last-modified')).toBe(lastModified)
+  })
+
+  it('should respond in full when the ETag does not match', async () => {
+    const res = await fetch('/data.txt', {
+      cache: 'no-store',
+      headers: { 'If-None-Match': '"outdated"' },
+    })
+    expect(res.status).toBe(200)
+    expect(await res.text()).toBe('Hello from a public file\n')
+  })
+}
@@ -0,0 +1,9 @@
+/** @type {import('next').NextConfig} */
+module.exports = {
+  experimental: {
+    proxyFallback: {
+      upstream: process.env.TURBOPACK_TEST_ONLY_MOCK_SERVER,
+      paths: ['/legacy/*'],
+    },
+  },
+}
@@ -0,0 +1,32 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function runTests() {
+  it('should proxy unmatched paths to the upstream server', async () => {
+    const res = await fetch('/legacy/about?q=a', {
+      headers: { 'x-legacy': 'forwarded' },
+    })
+    expect(res.status).toBe(200)
+    expect(res.headers.get('x-upstream')).toBe('legacy')
+    expect(await res.text()).toBe('legacy about')
+  })
+
+  it('should proxy the method and body of a request', async () => {
+    const res = await fetch('/legacy/form', {
+      method: 'POST',
+      body: 'name=turbopack',
+    })
+    expect(res.status).toBe(201)
+    expect(await res.text()).toBe('legacy form')
+  })
+
+  it('should not proxy paths outside of the configured paths', async () => {
+    const res = await fetch('/other')
+    expect(res.status).toBe(404)
+  })
+}
@@ -0,0 +1,9 @@
+/** @type {import('next').NextConfig} */
+module.exports = {
+  experimental: {
+    tenantRouting: {
+      hostPattern: '*.localhost',
+      rewrite: '/tenants/:tenant/:path*',
+    },
+  },
+}
@@ -0,0 +1,6 @@
+export default function handler(req, res) {
+  res.status(200).json({
+    tenant: req.query.tenant,
+    path: req.query.path ?? [],
+  })
+}
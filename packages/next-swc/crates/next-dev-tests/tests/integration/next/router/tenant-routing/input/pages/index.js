@@ -0,0 +1,41 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return 'index'
+}
+
+function waitForHydratedTenant() {
+  return new Promise((resolve) => {
+    window.addEventListener('message', function listener(event) {
+      if (event.data?.type === 'tenant-hydrated') {
+        window.removeEventListener('message', listener)
+        resolve(event.data)
+      }
+    })
+  })
+}
+
+function runTests() {
+  it('should render and hydrate the page of a tenant', async () => {
+    const hydrated = waitForHydratedTenant()
+    const iframe = document.createElement('iframe')
+    iframe.src = `http://tenant1.localhost:${location.port}/blog/post`
+    document.body.appendChild(iframe)
+
+    expect(await hydrated).toEqual({
+      type: 'tenant-hydrated',
+      tenant: 'tenant1',
+      header: 'tenant1',
+      path: ['blog', 'post'],
+    })
+  }, 20000)
+
+  it('should not rewrite requests without a tenant', async () => {
+    const res = await fetch('/api/tenants/direct/page')
+    const json = await res.json()
+
+    expect(json).toEqual({ tenant: 'direct', path: ['page'] })
+  })
+}
@@ -0,0 +1,24 @@
+import { useEffect } from 'react'
+
+export default function TenantPage({ tenant, header, path }) {
+  useEffect(() => {
+    // The test page is served from `localhost`, so the tenant page is
+    // cross-origin and reports back once it's hydrated.
+    window.parent.postMessage(
+      { type: 'tenant-hydrated', tenant, header, path },
+      '*'
+    )
+  }, [tenant, header, path])
+
+  return <h1>Tenant {tenant}</h1>
+}
+
+export function getServerSideProps({ params, req }) {
+  return {
+    props: {
+      tenant: params.tenant,
+      header: req.headers['x-next-tenant'] ?? null,
+      path: params.path ?? [],
+    },
+  }
+}
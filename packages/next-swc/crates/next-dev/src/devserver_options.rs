@@ -73,6 +73,13 @@ pub struct DevServerOptions {
     #[cfg_attr(feature = "serializable", serde(default))]
     pub log_detail: bool,
 
+    /// Log every request with the source that responded, its status and
+    /// timing. The requests are reported as info issues, so those are shown
+    /// too.
+    #[cfg_attr(feature = "cli", clap(long))]
+    #[cfg_attr(feature = "serializable", serde(default))]
+    pub log_requests: bool,
+
     /// Whether to enable full task stats recording in Turbo Engine.
     #[cfg_attr(feature = "cli", clap(long))]
     #[cfg_attr(feature = "serializable", serde(default))]
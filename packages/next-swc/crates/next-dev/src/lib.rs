@@ -20,10 +20,21 @@ use devserver_options::DevServerOptions;
 use dunce::canonicalize;
 use indexmap::IndexMap;
 use next_core::{
-    app_structure::find_app_dir_if_enabled, create_app_source, create_page_source,
-    create_web_entry_source, manifest::DevManifestContentSource, next_config::load_next_config,
-    next_image::NextImageContentSourceVc, pages_structure::find_pages_structure,
-    router_source::NextRouterContentSourceVc, source_map::NextSourceMapTraceContentSourceVc,
+    app_structure::find_app_dir_if_enabled,
+    create_app_source, create_page_source, create_web_entry_source,
+    manifest::DevManifestContentSource,
+    next_config::load_next_config,
+    next_image::NextImageContentSourceVc,
+    pages_structure::find_pages_structure,
+    proxy_fallback_source::ProxyFallbackContentSourceVc,
+    public_assets_source::PublicAssetsContentSourceVc,
+    redirect_chains::check_redirect_chains,
+    request_log_source::RequestLogContentSourceVc,
+    route_conflicts::check_app_pages_conflicts,
+    router_source::NextRouterContentSourceVc,
+    source_map::NextSourceMapTraceContentSourceVc,
+    source_priority::{combine_prioritized_sources, ContentSourcePriority},
+    tenant_source::TenantContentSourceVc,
 };
 use once_cell::sync::Lazy;
 use owo_colors::OwoColorize;
@@ -61,8 +72,7 @@ use turbopack_binding::{
         dev_server::{
             introspect::IntrospectionSource,
             source::{
-                combined::CombinedContentSourceVc, router::PrefixedRouterContentSource,
-                source_maps::SourceMapContentSourceVc, static_assets::StaticAssetsContentSourceVc,
+                router::PrefixedRouterContentSource, source_maps::SourceMapContentSourceVc,
                 ContentSourceVc,
             },
             DevServer, DevServerBuilder,
@@ -92,6 +102,7 @@ pub struct NextDevServerBuilder {
     log_level: IssueSeverity,
     show_all: bool,
     log_detail: bool,
+    log_requests: bool,
     allow_retry: bool,
 }
 
@@ -116,6 +127,7 @@ impl NextDevServerBuilder {
             log_level: IssueSeverity::Warning,
             show_all: false,
             log_detail: false,
+            log_requests: false,
             allow_retry: false,
         }
     }
@@ -165,6 +177,11 @@ impl NextDevServerBuilder {
         self
     }
 
+    pub fn log_requests(mut self, log_requests: bool) -> NextDevServerBuilder {
+        self.log_requests = log_requests;
+        self
+    }
+
     pub fn issue_reporter(
         mut self,
         issue_reporter: Box<dyn IssueReporterProvider>,
@@ -225,13 +242,19 @@ impl NextDevServerBuilder {
         let eager_compile = self.eager_compile;
         let show_all = self.show_all;
         let log_detail = self.log_detail;
+        let log_requests = self.log_requests;
         let browserslist_query = self.browserslist_query;
         let log_options = Arc::new(LogOptions {
             current_dir: current_dir().unwrap(),
             project_dir: PathBuf::from(project_dir.clone()),
             show_all,
             log_detail,
-            log_level: self.log_level,
+            // Request logs are reported as info issues.
+            log_level: if log_requests {
+                IssueSeverity::Info
+            } else {
+                self.log_level
+            },
         });
         let entry_requests = Arc::new(self.entry_requests);
         let server_addr = Arc::new(server.addr);
@@ -250,6 +273,7 @@ impl NextDevServerBuilder {
                 turbo_tasks.clone().into(),
                 browserslist_query.clone(),
                 server_addr.clone().into(),
+                log_requests,
             )
         };
 
@@ -296,6 +320,7 @@ async fn source(
     turbo_tasks: TransientInstance<TurboTasks<MemoryBackend>>,
     browserslist_query: String,
     server_addr: TransientInstance<SocketAddr>,
+    log_requests: bool,
 ) -> Result<ContentSourceVc> {
     let output_fs = output_fs(&project_dir);
     let fs = project_fs(&root_dir);
@@ -376,35 +401,43 @@ async fn source(
         next_config,
         server_addr,
     );
+    check_app_pages_conflicts(app_dir, pages_structure, dev_server_root, next_config).await?;
+    check_redirect_chains(project_path, next_config).await?;
     let viz = turbo_tasks_viz::TurboTasksSource {
         turbo_tasks: turbo_tasks.into(),
     }
     .cell()
     .into();
     let static_source =
-        StaticAssetsContentSourceVc::new(String::new(), project_path.join("public")).into();
+        PublicAssetsContentSourceVc::new(project_path.join("public"), next_config).into();
     let manifest_source = DevManifestContentSource {
         page_roots: vec![page_source],
         next_config,
     }
     .cell()
     .into();
-    let main_source = CombinedContentSourceVc::new(vec![
-        manifest_source,
-        static_source,
-        app_source,
-        page_source,
-        web_source,
+    let proxy_fallback_source = ProxyFallbackContentSourceVc::new(project_path, next_config).into();
+    let main_source = combine_prioritized_sources(vec![
+        (ContentSourcePriority::Manifest, manifest_source),
+        (ContentSourcePriority::PublicFiles, static_source),
+        (ContentSourcePriority::App, app_source),
+        (ContentSourcePriority::Pages, page_source),
+        (ContentSourcePriority::WebEntry, web_source),
+        (ContentSourcePriority::ProxyFallback, proxy_fallback_source),
     ]);
     let introspect = IntrospectionSource {
         roots: HashSet::from([main_source.into()]),
     }
     .cell()
     .into();
-    let main_source = main_source.into();
+    let main_source = if log_requests {
+        RequestLogContentSourceVc::new(main_source.into(), project_path).into()
+    } else {
+        main_source.into()
+    };
     let source_maps = SourceMapContentSourceVc::new(main_source).into();
     let source_map_trace = NextSourceMapTraceContentSourceVc::new(main_source).into();
-    let img_source = NextImageContentSourceVc::new(main_source).into();
+    let img_source = NextImageContentSourceVc::new(main_source, next_config).into();
     let router_source = NextRouterContentSourceVc::new(
         main_source,
         execution_context,
@@ -414,6 +447,8 @@ async fn source(
         pages_structure,
     )
     .into();
+    let router_source =
+        TenantContentSourceVc::new(router_source, project_path.join("public"), next_config).into();
     let source = PrefixedRouterContentSource {
         prefix: StringVc::empty(),
         routes: vec![
@@ -556,6 +591,7 @@ pub async fn start_server(options: &DevServerOptions) -> Result<()> {
         .hostname(options.hostname)
         .port(options.port)
         .log_detail(options.log_detail)
+        .log_requests(options.log_requests)
         .show_all(options.show_all)
         .log_level(
             options
@@ -44,12 +44,28 @@ export function resolveRobots(data: MetadataRoute.Robots): string {
 // TODO-METADATA: support multi sitemap files
 // convert sitemap data to xml string
 export function resolveSitemap(data: MetadataRoute.Sitemap): string {
+  const hasAlternates = data.some(
+    (item) => Object.keys(item.alternates?.languages ?? {}).length > 0
+  )
+
   let content = ''
   content += '<?xml version="1.0" encoding="UTF-8"?>\n'
-  content += '<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">\n'
+  content += '<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"'
+  if (hasAlternates) {
+    content += ' xmlns:xhtml="http://www.w3.org/1999/xhtml"'
+  }
+  content += '>\n'
   for (const item of data) {
     content += '<url>\n'
     content += `<loc>${item.url}</loc>\n`
+
+    const languages = item.alternates?.languages
+    if (languages) {
+      for (const [language, url] of Object.entries(languages)) {
+        content += `<xhtml:link rel="alternate" hreflang="${language}" href="${url}" />\n`
+      }
+    }
+
     if (item.lastModified) {
       content += `<lastmod>${
         item.lastModified instanceof Date
@@ -125,6 +125,7 @@ const nextDev: CliCommand = async (argv) => {
     // To align current messages with native binary.
     // Will need to adjust subcommand later.
     '--show-all': Boolean,
+    '--log-requests': Boolean,
     '--root': String,
 
     // Aliases
@@ -301,6 +302,7 @@ const nextDev: CliCommand = async (argv) => {
     let server = bindings.turbo.startDev({
       ...devServerOptions,
       showAll: args['--show-all'] ?? false,
+      logRequests: args['--log-requests'] ?? false,
       root: args['--root'] ?? findRootDir(dir),
     })
     // Start preflight after server is listening and ignore errors:
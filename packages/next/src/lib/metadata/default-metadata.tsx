@@ -19,6 +19,7 @@ export function createDefaultMetadata(): ResolvedMetadata {
     publisher: null,
     robots: null,
     manifest: null,
+    manifestCrossOrigin: null,
     alternates: {
       canonical: null,
       languages: null,
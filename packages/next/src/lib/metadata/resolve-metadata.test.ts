@@ -299,6 +299,28 @@ describe('accumulateMetadata', () => {
     })
   })
 
+  describe('manifest', () => {
+    it('should apply manifestCrossOrigin to a file based manifest', async () => {
+      const metadataItems: MetadataItems = [
+        [
+          { manifestCrossOrigin: 'use-credentials' },
+          {
+            icon: undefined,
+            apple: undefined,
+            openGraph: undefined,
+            twitter: undefined,
+            manifest: '/manifest.webmanifest',
+          },
+        ],
+      ]
+      const metadata = await accumulateMetadata(metadataItems)
+      expect(metadata).toMatchObject({
+        manifest: '/manifest.webmanifest',
+        manifestCrossOrigin: 'use-credentials',
+      })
+    })
+  })
+
   describe('themeColor', () => {
     it('should support string theme color', async () => {
       const metadataItems: MetadataItems = [
@@ -2,6 +2,7 @@ import type {
   Metadata,
   ResolvedMetadata,
   ResolvingMetadata,
+  ViewportExport,
 } from './types/metadata-interface'
 import type { MetadataImageModule } from '../../build/webpack/loaders/metadata/types'
 import type { GetDynamicParamFromSegment } from '../../server/app-render/app-render'
@@ -204,6 +205,7 @@ function merge({
       case 'colorScheme':
       case 'formatDetection':
       case 'manifest':
+      case 'manifestCrossOrigin':
         // @ts-ignore TODO: support inferring
         target[key] = source[key] || null
         break
@@ -253,6 +255,59 @@ async function getDefinedMetadata(
   )
 }
 
+function viewportToMetadata(viewport: ViewportExport | null): Metadata | null {
+  if (!viewport) return null
+  const { themeColor, colorScheme, ...viewportFields } = viewport
+  const metadata: Metadata = {}
+  if (themeColor !== undefined) metadata.themeColor = themeColor
+  if (colorScheme !== undefined) metadata.colorScheme = colorScheme
+  if (Object.keys(viewportFields).length > 0) {
+    metadata.viewport = viewportFields
+  }
+  return metadata
+}
+
+async function getDefinedViewport(
+  mod: any,
+  props: any
+): Promise<Metadata | MetadataResolver | null> {
+  if (isClientReference(mod)) {
+    return null
+  }
+  if (mod.generateViewport) {
+    return async () =>
+      viewportToMetadata(await mod.generateViewport(props)) || {}
+  }
+  return viewportToMetadata(mod.viewport || null)
+}
+
+// The viewport export is merged into the metadata of its segment, so that it
+// takes precedence over the segment's viewport, themeColor and colorScheme.
+function mergeViewportExport(
+  metadataExport: Metadata | MetadataResolver | null,
+  viewportExport: Metadata | MetadataResolver | null
+): Metadata | MetadataResolver | null {
+  if (!viewportExport) return metadataExport
+  if (!metadataExport) return viewportExport
+  if (
+    typeof metadataExport !== 'function' &&
+    typeof viewportExport !== 'function'
+  ) {
+    return { ...metadataExport, ...viewportExport }
+  }
+  return async (parent) => {
+    const [metadata, viewport] = await Promise.all([
+      typeof metadataExport === 'function'
+        ? metadataExport(parent)
+        : metadataExport,
+      typeof viewportExport === 'function'
+        ? viewportExport(parent)
+        : viewportExport,
+    ])
+    return { ...metadata, ...viewport }
+  }
+}
+
 async function collectStaticImagesFiles(
   metadata: ComponentsType['metadata'],
   props: any,
@@ -312,7 +367,10 @@ export async function collectMetadata({
 
   const staticFilesMetadata = await resolveStaticMetadata(tree[2], props)
   const metadataExport = mod
-    ? await getDefinedMetadata(mod, props, route)
+    ? mergeViewportExport(
+        await getDefinedMetadata(mod, props, route),
+        await getDefinedViewport(mod, props)
+      )
     : null
 
   array.push([metadataExport, staticFilesMetadata])
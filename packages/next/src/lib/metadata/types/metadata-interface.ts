@@ -1,5 +1,6 @@
 import type {
   AlternateURLs,
+  Languages,
   ResolvedAlternateURLs,
 } from './alternative-urls-types'
 import type {
@@ -271,6 +272,19 @@ interface Metadata extends DeprecatedMetadataFields {
    */
   manifest?: null | string | URL
 
+  /**
+   * The CORS mode used to request the web application manifest. Manifests behind
+   * authentication need `'use-credentials'` to be requested with cookies.
+   *
+   * @see https://developer.mozilla.org/en-US/docs/Web/HTML/Attributes/crossorigin
+   * @example
+   * ```tsx
+   * "use-credentials"
+   * <link rel="manifest" href="https://example.com/manifest.json" crossorigin="use-credentials" />
+   * ```
+   */
+  manifestCrossOrigin?: null | 'anonymous' | 'use-credentials'
+
   /**
    * The Open Graph metadata for the document.
    *
@@ -491,6 +505,7 @@ interface ResolvedMetadata extends DeprecatedMetadataFields {
   openGraph: null | ResolvedOpenGraph
 
   manifest: null | string | URL
+  manifestCrossOrigin: null | 'anonymous' | 'use-credentials'
 
   twitter: null | ResolvedTwitterMetadata
 
@@ -558,13 +573,39 @@ type RobotsFile = {
 type SitemapFile = Array<{
   url: string
   lastModified?: string | Date
+  alternates?: {
+    languages?: Languages<string>
+  }
 }>
 
 type ResolvingMetadata = Promise<ResolvedMetadata>
+
+/**
+ * The `viewport` export of a layout or page, or the return value of its
+ * `generateViewport` function. It sets the viewport meta tag, theme color and
+ * color scheme separately from the metadata.
+ *
+ * @example
+ * ```tsx
+ * export const viewport = { width: 'device-width', themeColor: '#000' }
+ * <meta name="viewport" content="width=device-width" />
+ * <meta name="theme-color" content="#000" />
+ * ```
+ */
+interface ViewportExport extends Viewport {
+  themeColor?: Metadata['themeColor']
+  colorScheme?: Metadata['colorScheme']
+}
 declare namespace MetadataRoute {
   export type Robots = RobotsFile
   export type Sitemap = SitemapFile
   export type Manifest = ManifestFile
 }
 
-export { Metadata, ResolvedMetadata, ResolvingMetadata, MetadataRoute }
+export {
+  Metadata,
+  ResolvedMetadata,
+  ResolvingMetadata,
+  ViewportExport,
+  MetadataRoute,
+}
@@ -12,11 +12,16 @@ const supportedTurbopackNextConfigOptions = [
   'compiler.styledComponents',
   'images',
   'pageExtensions',
+  'publicRuntimeConfig',
+  'serverRuntimeConfig',
   'onDemandEntries',
   'rewrites',
   'redirects',
   'headers',
+  'generateEtags',
   'reactStrictMode',
+  'reactProductionProfiling',
+  'reactMaxHeadersLength',
   'swcMinify',
   'transpilePackages',
   'sassOptions.includePaths',
@@ -26,6 +31,19 @@ const supportedTurbopackNextConfigOptions = [
   'experimental.mdxRs',
   'experimental.swcFileReading',
   'experimental.forceSwcTransforms',
+  'experimental.fallbackNodePolyfills',
+  'experimental.tenantRouting',
+  'experimental.largePageDataBytes',
+  'experimental.circularImportWarnings',
+  'experimental.renderTimeout',
+  'experimental.maxRouteDepth',
+  'experimental.maxRedirectDepth',
+  'experimental.esmExternals',
+  'experimental.proxyFallback',
+  'experimental.chunkLoadRetry',
+  'experimental.scrollRestoration',
+  'experimental.webVitalsAttribution',
+  'experimental.bundleBudget',
   // options below are not really supported, but ignored
   'webpack',
   'devIndicators',
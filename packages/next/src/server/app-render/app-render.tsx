@@ -80,6 +80,34 @@ export const isEdgeRuntime = process.env.NEXT_RUNTIME === 'edge'
 
 const emptyLoaderTree: LoaderTree = ['', {}, {}]
 
+/**
+ * Replaces the children of the deepest segment that has a `not-found` file
+ * with a page rendering it, so `notFound()` renders the nearest not-found page
+ * inside of its layouts. Returns `null` when no segment has a `not-found` file.
+ */
+function createNotFoundLoaderTree(tree: LoaderTree): LoaderTree | null {
+  const [segment, parallelRoutes, components] = tree
+  const children = parallelRoutes.children
+  const notFoundChildren = children ? createNotFoundLoaderTree(children) : null
+  if (notFoundChildren) {
+    return [
+      segment,
+      { ...parallelRoutes, children: notFoundChildren },
+      components,
+    ]
+  }
+
+  const notFound = components['not-found']
+  if (!notFound) {
+    return null
+  }
+  return [
+    segment,
+    { ...parallelRoutes, children: ['__PAGE__', {}, { page: notFound }] },
+    components,
+  ]
+}
+
 export type GetDynamicParamFromSegment = (
   // [slug] / [[slug]] / [...slug]
   segment: string
@@ -1271,6 +1299,93 @@ export async function renderToHTMLOrFlight(
         }
       : {}
 
+    /**
+     * Creates the full component tree of the given loader tree wrapped in
+     * the app router.
+     */
+    const createAppTree = async (tree: LoaderTree, asNotFound: boolean) => {
+      // Create full component tree from root to leaf.
+      const injectedCSS = new Set<string>()
+      const injectedFontPreloadTags = new Set<string>()
+
+      const { Component: ComponentTree, styles } = await createComponentTree({
+        createSegmentPath: (child) => child,
+        loaderTree: tree,
+        parentParams: {},
+        firstItem: true,
+        injectedCSS,
+        injectedFontPreloadTags,
+        rootLayoutIncluded: false,
+        asNotFound,
+      })
+
+      const { 'not-found': notFound, layout } = tree[2]
+      const isLayout = typeof layout !== 'undefined'
+      const rootLayoutModule = layout?.[0]
+      const RootLayout = rootLayoutModule
+        ? interopDefault(await rootLayoutModule())
+        : null
+      const rootLayoutAtThisLevel = isLayout
+      const [NotFound, notFoundStyles] = notFound
+        ? await createComponentAndStyles({
+            filePath: notFound[1],
+            getComponent: notFound[0],
+            injectedCSS,
+          })
+        : rootLayoutAtThisLevel
+        ? [DefaultNotFound]
+        : []
+
+      const initialTree = createFlightRouterStateFromLoaderTree(
+        tree,
+        getDynamicParamFromSegment,
+        query
+      )
+
+      const createMetadata = (tree: LoaderTree) => (
+        // Adding key={requestId} to make metadata remount for each render
+        // @ts-expect-error allow to use async server component
+        <MetadataTree
+          key={requestId}
+          tree={tree}
+          pathname={pathname}
+          searchParams={providedSearchParams}
+          getDynamicParamFromSegment={getDynamicParamFromSegment}
+        />
+      )
+
+      return (
+        <>
+          {styles}
+          <AppRouter
+            buildId={renderOpts.buildId}
+            assetPrefix={assetPrefix}
+            initialCanonicalUrl={pathname}
+            initialTree={initialTree}
+            initialHead={
+              <>
+                {createMetadata(tree)}
+                {appUsingSizeAdjust ? <meta name="next-size-adjust" /> : null}
+              </>
+            }
+            globalErrorComponent={GlobalError}
+            notFound={
+              NotFound && RootLayout ? (
+                <RootLayout params={{}}>
+                  {createMetadata(emptyLoaderTree)}
+                  {notFoundStyles}
+                  <NotFound />
+                </RootLayout>
+              ) : undefined
+            }
+            asNotFound={asNotFound}
+          >
+            <ComponentTree />
+          </AppRouter>
+        </>
+      )
+    }
+
     /**
      * A new React Component that renders the provided React Component
      * using Flight which can then be rendered to HTML.
@@ -1278,88 +1393,7 @@ export async function renderToHTMLOrFlight(
     const ServerComponentsRenderer = createServerComponentRenderer<{
       asNotFound: boolean
     }>(
-      async (props) => {
-        // Create full component tree from root to leaf.
-        const injectedCSS = new Set<string>()
-        const injectedFontPreloadTags = new Set<string>()
-
-        const { Component: ComponentTree, styles } = await createComponentTree({
-          createSegmentPath: (child) => child,
-          loaderTree,
-          parentParams: {},
-          firstItem: true,
-          injectedCSS,
-          injectedFontPreloadTags,
-          rootLayoutIncluded: false,
-          asNotFound: props.asNotFound,
-        })
-
-        const { 'not-found': notFound, layout } = loaderTree[2]
-        const isLayout = typeof layout !== 'undefined'
-        const rootLayoutModule = layout?.[0]
-        const RootLayout = rootLayoutModule
-          ? interopDefault(await rootLayoutModule())
-          : null
-        const rootLayoutAtThisLevel = isLayout
-        const [NotFound, notFoundStyles] = notFound
-          ? await createComponentAndStyles({
-              filePath: notFound[1],
-              getComponent: notFound[0],
-              injectedCSS,
-            })
-          : rootLayoutAtThisLevel
-          ? [DefaultNotFound]
-          : []
-
-        const initialTree = createFlightRouterStateFromLoaderTree(
-          loaderTree,
-          getDynamicParamFromSegment,
-          query
-        )
-
-        const createMetadata = (tree: LoaderTree) => (
-          // Adding key={requestId} to make metadata remount for each render
-          // @ts-expect-error allow to use async server component
-          <MetadataTree
-            key={requestId}
-            tree={tree}
-            pathname={pathname}
-            searchParams={providedSearchParams}
-            getDynamicParamFromSegment={getDynamicParamFromSegment}
-          />
-        )
-
-        return (
-          <>
-            {styles}
-            <AppRouter
-              buildId={renderOpts.buildId}
-              assetPrefix={assetPrefix}
-              initialCanonicalUrl={pathname}
-              initialTree={initialTree}
-              initialHead={
-                <>
-                  {createMetadata(loaderTree)}
-                  {appUsingSizeAdjust ? <meta name="next-size-adjust" /> : null}
-                </>
-              }
-              globalErrorComponent={GlobalError}
-              notFound={
-                NotFound && RootLayout ? (
-                  <RootLayout params={{}}>
-                    {createMetadata(emptyLoaderTree)}
-                    {notFoundStyles}
-                    <NotFound />
-                  </RootLayout>
-                ) : undefined
-              }
-              asNotFound={props.asNotFound}
-            >
-              <ComponentTree />
-            </AppRouter>
-          </>
-        )
-      },
+      (props) => createAppTree(loaderTree, props.asNotFound),
       ComponentMod,
       serverComponentsRenderOpts,
       serverComponentsErrorHandler,
@@ -1578,12 +1612,26 @@ export async function renderToHTMLOrFlight(
             </html>
           )
 
-          const useDefaultError =
+          // `notFound()` renders the nearest `not-found` page within its
+          // layouts instead of the empty error shell.
+          const notFoundLoaderTree =
+            res.statusCode === 404 ? createNotFoundLoaderTree(loaderTree) : null
+          let useDefaultError =
             res.statusCode < 400 ||
-            res.statusCode === 404 ||
+            (res.statusCode === 404 && !notFoundLoaderTree) ||
             res.statusCode === 307
           const serverErrorElement = useDefaultError
             ? defaultErrorComponent
+            : notFoundLoaderTree
+            ? React.createElement(
+                createServerComponentRenderer(
+                  () => createAppTree(notFoundLoaderTree, false),
+                  ComponentMod,
+                  serverErrorComponentsRenderOpts,
+                  serverComponentsErrorHandler,
+                  nonce
+                )
+              )
             : React.createElement(
                 createServerComponentRenderer(
                   async () => {
@@ -1613,26 +1661,41 @@ export async function renderToHTMLOrFlight(
                 )
               )
 
-          const renderStream = await renderToInitialStream({
-            ReactDOMServer: require('react-dom/server.edge'),
-            element: serverErrorElement,
-            streamOptions: {
-              nonce,
-              // Include hydration scripts in the HTML
-              bootstrapScripts: subresourceIntegrityManifest
-                ? buildManifest.rootMainFiles.map((src) => ({
-                    src:
-                      `${assetPrefix}/_next/` +
-                      src +
-                      getAssetQueryString(false),
-                    integrity: subresourceIntegrityManifest[src],
-                  }))
-                : buildManifest.rootMainFiles.map(
-                    (src) =>
-                      `${assetPrefix}/_next/` + src + getAssetQueryString(false)
-                  ),
-            },
-          })
+          const streamOptions = {
+            nonce,
+            // Include hydration scripts in the HTML
+            bootstrapScripts: subresourceIntegrityManifest
+              ? buildManifest.rootMainFiles.map((src) => ({
+                  src:
+                    `${assetPrefix}/_next/` + src + getAssetQueryString(false),
+                  integrity: subresourceIntegrityManifest[src],
+                }))
+              : buildManifest.rootMainFiles.map(
+                  (src) =>
+                    `${assetPrefix}/_next/` + src + getAssetQueryString(false)
+                ),
+          }
+
+          let renderStream
+          try {
+            renderStream = await renderToInitialStream({
+              ReactDOMServer: require('react-dom/server.edge'),
+              element: serverErrorElement,
+              streamOptions,
+            })
+          } catch (notFoundRenderError) {
+            // A layout above the nearest `not-found` page can call `notFound()`
+            // as well, fall back to the error shell in that case.
+            if (!notFoundLoaderTree) {
+              throw notFoundRenderError
+            }
+            useDefaultError = true
+            renderStream = await renderToInitialStream({
+              ReactDOMServer: require('react-dom/server.edge'),
+              element: defaultErrorComponent,
+              streamOptions,
+            })
+          }
 
           return await continueFromInitialStream(renderStream, {
             dataStream: (useDefaultError
@@ -252,6 +252,9 @@ const configSchema = {
           },
           type: 'object',
         },
+        circularImportWarnings: {
+          type: 'boolean',
+        },
         clientRouterFilter: {
           type: 'boolean',
         },
@@ -400,6 +403,71 @@ const configSchema = {
           minimum: 0,
           type: 'number',
         },
+        renderTimeout: {
+          minimum: 0,
+          type: 'number',
+        },
+        maxRouteDepth: {
+          minimum: 1,
+          type: 'integer',
+        },
+        maxRedirectDepth: {
+          minimum: 1,
+          type: 'integer',
+        },
+        proxyFallback: {
+          type: 'object',
+          additionalProperties: false,
+          properties: {
+            upstream: {
+              minLength: 1,
+              type: 'string',
+            },
+            paths: {
+              items: {
+                type: 'string',
+              },
+              type: 'array',
+            },
+          },
+          required: ['upstream'],
+        },
+        chunkLoadRetry: {
+          type: 'object',
+          additionalProperties: false,
+          properties: {
+            retries: {
+              minimum: 0,
+              type: 'integer',
+            },
+            timeout: {
+              minimum: 0,
+              type: 'integer',
+            },
+          },
+          required: ['retries'],
+        },
+        bundleBudget: {
+          type: 'object',
+          additionalProperties: false,
+          properties: {
+            maxSize: {
+              minimum: 0,
+              type: 'integer',
+            },
+            routes: {
+              type: 'object',
+              additionalProperties: {
+                minimum: 0,
+                type: 'integer',
+              },
+            },
+            severity: {
+              enum: ['warning', 'error'],
+              type: 'string',
+            },
+          },
+        },
         serverComponentsExternalPackages: {
           items: {
             type: 'string',
@@ -436,6 +504,19 @@ const configSchema = {
         swcTraceProfiling: {
           type: 'boolean',
         },
+        tenantRouting: {
+          type: 'object',
+          additionalProperties: false,
+          properties: {
+            hostPattern: {
+              type: 'string',
+            },
+            rewrite: {
+              type: 'string',
+            },
+          },
+          required: ['hostPattern'],
+        },
         urlImports: {
           items: {
             type: 'string',
@@ -471,6 +552,24 @@ const configSchema = {
             resolveAlias: {
               type: 'object',
             },
+            cacheQueryParams: {
+              type: 'object',
+              additionalProperties: false,
+              properties: {
+                include: {
+                  type: 'array',
+                  items: {
+                    type: 'string',
+                  },
+                },
+                exclude: {
+                  type: 'array',
+                  items: {
+                    type: 'string',
+                  },
+                },
+              },
+            },
           },
         },
         instrumentationHook: {
@@ -518,6 +617,9 @@ const configSchema = {
         serverSourceMaps: {
           type: 'boolean',
         },
+        hiddenSourceMaps: {
+          type: 'boolean',
+        },
       },
       type: 'object',
     },
@@ -743,6 +845,10 @@ const configSchema = {
     publicRuntimeConfig: {
       type: 'object',
     },
+    reactMaxHeadersLength: {
+      minimum: 0,
+      type: 'number',
+    },
     reactProductionProfiling: {
       type: 'boolean',
     },
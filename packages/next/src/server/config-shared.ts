@@ -110,6 +110,15 @@ interface ExperimentalTurboOptions {
    * @see [Turbopack Loaders](https://nextjs.org/docs/app/api-reference/next-config-js/turbo#webpack-loaders)
    */
   loaders?: Record<string, TurboLoaderItem[]>
+
+  /**
+   * (`next --turbo` only) Which query params are part of the cache key of rendered pages.
+   * Patterns ending with `*` match by prefix. Excluded params are not passed to the page.
+   */
+  cacheQueryParams?: {
+    include?: string[]
+    exclude?: string[]
+  }
 }
 
 export interface WebpackConfigContext {
@@ -215,7 +224,70 @@ export interface ExperimentalConfig {
    */
   swcMinifyDebugOptions?: never
   swcPlugins?: Array<[string, Record<string, unknown>]>
+  /**
+   * (`next --turbo` only) Warns about import cycles between the modules of a
+   * page, which can cause exports to be read before they are initialized.
+   */
+  circularImportWarnings?: boolean
+  /**
+   * (`next --turbo` only) Rewrites requests by the subdomain of their host. With
+   * `{ hostPattern: '*.example.com', rewrite: '/_tenants/:tenant/:path*' }`, a
+   * request for `tenant1.example.com/about` is handled by `/_tenants/tenant1/about`.
+   * Pages receive the tenant in the `x-next-tenant` request header, with or
+   * without a `rewrite`. Internal paths and public files aren't rewritten.
+   */
+  tenantRouting?: {
+    hostPattern: string
+    rewrite?: string
+  }
   largePageDataBytes?: number
+  /**
+   * (`next --turbo` only) The time in milliseconds after which a server render
+   * or API route is aborted with a 500. Renders aren't aborted unless it's set.
+   */
+  renderTimeout?: number
+  /**
+   * (`next --turbo` only) The maximum number of nested segments and parallel
+   * routes of an app directory route. Deeper routes are reported as an error
+   * instead of being rendered. Defaults to 50.
+   */
+  maxRouteDepth?: number
+  /**
+   * (`next --turbo` only) The maximum number of redirects a request can be
+   * sent through. Longer chains of redirects and redirect loops are reported
+   * as an error. Defaults to 10.
+   */
+  maxRedirectDepth?: number
+  /**
+   * (`next --turbo` only) Proxies requests that no route matches to an
+   * existing server, e.g. `{ upstream: 'http://localhost:4000' }`.
+   * `paths` limits the proxied paths, where a trailing `*` matches any suffix.
+   */
+  proxyFallback?: {
+    upstream: string
+    paths?: string[]
+  }
+  /**
+   * (`next --turbo` only) Retries chunk loads that failed in the browser up to
+   * `retries` times, with an exponential backoff. `timeout` is the time in
+   * milliseconds after which a chunk load is considered failed.
+   */
+  chunkLoadRetry?: {
+    retries: number
+    timeout?: number
+  }
+  /**
+   * (`next --turbo` only) The maximum size in bytes of the client chunks each
+   * route loads initially, checked by `next build`. `routes` sets the budgets
+   * of individual routes, e.g. `{ '/dashboard': 300000 }`, over `maxSize`.
+   * Exceeding a budget is reported as a warning, or fails the build when
+   * `severity` is `'error'`.
+   */
+  bundleBudget?: {
+    maxSize?: number
+    routes?: Record<string, number>
+    severity?: 'warning' | 'error'
+  }
   /**
    * If set to `false`, webpack won't fall back to polyfill Node.js modules in the browser
    * Full list of old polyfills is accessible here:
@@ -289,7 +361,8 @@ export interface ExperimentalConfig {
   serverActionsBodySizeLimit?: SizeLimit
 
   /**
-   * enables the minification of server code.
+   * enables the minification of server code. Unset by default, which means
+   * disabled with webpack and enabled with `next build --turbo`.
    */
   serverMinification?: boolean
 
@@ -297,6 +370,13 @@ export interface ExperimentalConfig {
    * Enables source maps generation for the server production bundle.
    */
   serverSourceMaps?: boolean
+
+  /**
+   * (`next build --turbo` only) Emits the source maps of the client chunks
+   * without referencing them from the chunks, so they can be uploaded to an
+   * error tracker without being exposed to browsers.
+   */
+  hiddenSourceMaps?: boolean
 }
 
 export type ExportPathMap = {
@@ -513,6 +593,16 @@ export interface NextConfig extends Record<string, any> {
    */
   reactProductionProfiling?: boolean
 
+  /**
+   * (`next --turbo` only) The maximum length in characters of the `Link`
+   * header of a rendered page. Above it, the last preload links, which have
+   * the lowest priority, are dropped, so that proxies with strict header size
+   * limits don't reject the response.
+   *
+   * @default 6000
+   */
+  reactMaxHeadersLength?: number
+
   /**
    * The Next.js runtime is Strict Mode-compliant.
    *
@@ -687,7 +777,6 @@ export const defaultConfig: NextConfig = {
   output: !!process.env.NEXT_PRIVATE_STANDALONE ? 'standalone' : undefined,
   modularizeImports: undefined,
   experimental: {
-    serverMinification: false,
     serverSourceMaps: false,
     caseSensitiveRoutes: false,
     useDeploymentId: false,